@@ -0,0 +1,137 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    CITE_COMMANDS,
+    REF_COMMANDS,
+    iter_commands,
+    iter_environments,
+    mask_non_prose,
+    mask_source,
+    report,
+)
+
+RE_WORD = re.compile(r"[^\W_]+(?:['’-][^\W_]+)*")
+# Keywords are separated by commas, or \sep and \and for elsarticle and llncs
+RE_KEYWORD_SEPARATOR = re.compile(r"[,;]|\\sep(?![a-zA-Z@])|\\and(?![a-zA-Z@])")
+
+KEYWORD_ENVIRONMENTS = ["IEEEkeywords", "keyword", "keywords"]
+
+
+def check_keywords(
+    file_name: str,
+    text: str,
+    offset: int,
+    content: str,
+    min_keywords: int,
+    max_keywords: int,
+) -> bool:
+    keywords = [k for k in RE_KEYWORD_SEPARATOR.split(content) if k.strip()]
+    if min_keywords <= len(keywords) <= max_keywords:
+        return False
+    report(
+        file_name,
+        text,
+        offset,
+        f"{len(keywords)} keywords, use between {min_keywords} and {max_keywords}",
+    )
+    return True
+
+
+def search(
+    files: t.List[t.IO[str]],
+    max_words: int,
+    allow_references: bool,
+    min_keywords: int,
+    max_keywords: int,
+) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        for env in iter_environments(text, ["abstract"]):
+            body = text[env.body_start : env.body_end]
+            words = len(RE_WORD.findall(mask_non_prose(body)))
+            if words > max_words:
+                found_error = True
+                message = f"Abstract has {words} words (maximum {max_words})"
+                report(f.name, text, env.start, message)
+            if not allow_references:
+                names = CITE_COMMANDS + REF_COMMANDS + ["footnote"]
+                for cmd in iter_commands(body, names, nargs=0):
+                    found_error = True
+                    message = f"\\{cmd.name} should not be used in the abstract"
+                    report(f.name, text, env.body_start + cmd.start, message)
+
+        for cmd in iter_commands(text, ["keywords"]):
+            if cmd.args:
+                found_error |= check_keywords(
+                    f.name, text, cmd.start, cmd.args[0], min_keywords, max_keywords
+                )
+        for env in iter_environments(text, KEYWORD_ENVIRONMENTS):
+            found_error |= check_keywords(
+                f.name,
+                text,
+                env.start,
+                text[env.body_start : env.body_end],
+                min_keywords,
+                max_keywords,
+            )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-words",
+        type=int,
+        default=250,
+        help="Maximum number of words in the abstract",
+    )
+    parser.add_argument(
+        "--allow-references",
+        action="store_true",
+        help="Allow citations, references, and footnotes in the abstract",
+    )
+    parser.add_argument(
+        "--min-keywords",
+        type=int,
+        default=3,
+        help="Minimum number of keywords",
+    )
+    parser.add_argument(
+        "--max-keywords",
+        type=int,
+        default=6,
+        help="Maximum number of keywords",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(
+        args.files,
+        args.max_words,
+        args.allow_references,
+        args.min_keywords,
+        args.max_keywords,
+    )
+    if found_error:
+        sys.exit("Found problems with the abstract or keywords")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
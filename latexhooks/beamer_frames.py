@@ -0,0 +1,138 @@
+#!/usr/bin/env python3
+import argparse
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    check_projects,
+    iter_commands,
+    iter_environments,
+    read_project,
+    report,
+    resolve_inputs,
+)
+
+LIST_ENVIRONMENTS = ["itemize", "enumerate", "description"]
+
+
+def check_frame(
+    file_name: str,
+    text: str,
+    start: int,
+    end: int,
+    max_lines: int,
+    max_items: int,
+    max_depth: int,
+) -> bool:
+    found_error = False
+    body = text[start:end]
+    lines = sum(1 for line in body.splitlines() if line.strip())
+    if lines > max_lines:
+        found_error = True
+        message = f"Frame has {lines} lines of content (maximum {max_lines})"
+        report(file_name, text, start, message)
+    items = sum(1 for _ in iter_commands(body, ["item"], nargs=0))
+    if items > max_items:
+        found_error = True
+        message = f"Frame has {items} bullet items (maximum {max_items})"
+        report(file_name, text, start, message)
+
+    lists = list(iter_environments(body, LIST_ENVIRONMENTS))
+    for env in lists:
+        depth = sum(1 for other in lists if other.start <= env.start < other.end)
+        if depth == max_depth + 1:
+            found_error = True
+            message = f"Lists are nested {depth} levels deep (maximum {max_depth})"
+            report(file_name, text, start + env.start, message)
+    return found_error
+
+
+def is_beamer(text: str) -> bool:
+    """The text is a root document using the beamer class"""
+    return any(
+        cmd.args and cmd.args[0].strip() == "beamer"
+        for cmd in iter_commands(text, ["documentclass"])
+    )
+
+
+def search(
+    files: t.List[t.IO[str]], max_lines: int, max_items: int, max_depth: int
+) -> bool:
+    texts = read_project(files)
+    # Frames and sections only belong together in presentations
+    slides: t.Set[str] = set()
+    for file_name, text in texts:
+        if is_beamer(text):
+            slides.update(resolve_inputs(file_name))
+
+    found_error = False
+    for file_name, text in texts:
+        if file_name not in slides:
+            continue
+        frames = list(iter_environments(text, ["frame"]))
+        for frame in frames:
+            found_error |= check_frame(
+                file_name,
+                text,
+                frame.body_start,
+                frame.body_end,
+                max_lines,
+                max_items,
+                max_depth,
+            )
+
+        sections = list(iter_commands(text, ["section"]))
+        for idx, section in enumerate(sections):
+            end = sections[idx + 1].start if idx + 1 < len(sections) else len(text)
+            if not any(section.end <= frame.start < end for frame in frames):
+                found_error = True
+                report(file_name, text, section.start, "Section contains no frames")
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-lines",
+        type=int,
+        default=20,
+        help="Maximum number of non-empty lines in a frame",
+    )
+    parser.add_argument(
+        "--max-items",
+        type=int,
+        default=8,
+        help="Maximum number of \\item in a frame",
+    )
+    parser.add_argument(
+        "--max-depth",
+        type=int,
+        default=2,
+        help="Maximum nesting depth of lists",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, args.max_lines, args.max_items, args.max_depth),
+        "Found problems with beamer frames",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,120 @@
+#!/usr/bin/env python3
+import argparse
+import typing as t
+
+from latexhooks.common import (
+    CITE_COMMANDS,
+    add_root_argument,
+    bibliography_files,
+    check_projects,
+    iter_bib_keys,
+    iter_commands,
+    line_number,
+    mask_comments,
+    read_project,
+    report,
+    split_list,
+)
+
+
+def comments_only(text: str) -> str:
+    """Replace everything but the content of comments with spaces, keeping offsets"""
+    masked = mask_comments(text)
+    return "".join(
+        c if c != m or c == "\n" else " " for c, m in zip(text, masked)
+    ).replace("%", " ")
+
+
+def iter_citations(text: str) -> t.Iterator[t.Tuple[str, int]]:
+    """Find all cited keys together with the offset of the citing command"""
+    for cmd in iter_commands(text, CITE_COMMANDS):
+        for key in split_list(cmd.args):
+            yield key, cmd.start
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    bib_names = [f.name for f in files if f.name.endswith(".bib")]
+    texts = read_project([f for f in files if not f.name.endswith(".bib")])
+    for file_name, text in texts:
+        for path in bibliography_files(file_name, text):
+            if path not in bib_names:
+                bib_names.append(path)
+    if not bib_names:
+        return False
+
+    # Citation keys mapped to the location of their entry
+    entries: t.Dict[str, t.Tuple[str, str, int]] = dict()
+    for bib_name in bib_names:
+        with open(bib_name, encoding="utf-8") as f:
+            bib = f.read()
+        for key, offset in iter_bib_keys(bib):
+            entries.setdefault(key, (bib_name, bib, offset))
+
+    # Citation keys mapped to all citing locations, in active text or in comments
+    cited: t.Dict[str, t.List[t.Tuple[str, str, int]]] = dict()
+    commented: t.Dict[str, t.List[t.Tuple[str, str, int]]] = dict()
+    for file_name, text in texts:
+        for key, offset in iter_citations(text):
+            cited.setdefault(key, list()).append((file_name, text, offset))
+        with open(file_name, encoding="utf-8") as f:
+            raw = f.read()
+        for key, offset in iter_citations(comments_only(raw)):
+            commented.setdefault(key, list()).append((file_name, raw, offset))
+
+    found_error = False
+    for key, locations in cited.items():
+        if key != "*" and key not in entries:
+            for file_name, text, offset in locations:
+                found_error = True
+                report(file_name, text, offset, f"Citation key {key} is not defined")
+    for key, locations in commented.items():
+        if key != "*" and key not in entries and key not in cited:
+            for file_name, text, offset in locations:
+                found_error = True
+                message = f"Undefined citation key {key} is only cited in a comment"
+                report(file_name, text, offset, message)
+
+    if "*" in cited:
+        return found_error
+    for key, (bib_name, bib, offset) in entries.items():
+        if key in cited:
+            continue
+        found_error = True
+        if key in commented:
+            file_name, text, cite_offset = commented[key][0]
+            location = f"{file_name}:{line_number(text, cite_offset)}"
+            message = f"Entry {key} is only cited in a comment at {location}"
+        else:
+            message = f"Entry {key} is never cited"
+        report(bib_name, bib, offset, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        search,
+        "Found unused bibliography entries or undefined citation keys",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
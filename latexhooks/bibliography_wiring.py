@@ -0,0 +1,121 @@
+#!/usr/bin/env python3
+import argparse
+import os
+import typing as t
+
+from latexhooks.common import (
+    CITE_COMMANDS,
+    Command,
+    add_root_argument,
+    check_projects,
+    iter_commands,
+    read_project,
+    report,
+)
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    texts = read_project(files)
+
+    def commands(names: t.List[str]) -> t.List[t.Tuple[str, str, Command]]:
+        return [
+            (file_name, text, cmd)
+            for file_name, text in texts
+            for cmd in iter_commands(text, names)
+        ]
+
+    # Without the root document we cannot say whether a bibliography is missing
+    if not commands(["documentclass"]):
+        return False
+
+    found_error = False
+    cites = commands(CITE_COMMANDS)
+    bibtex = commands(["bibliography"])
+    biblatex = commands(["printbibliography"])
+    resources = commands(["addbibresource"])
+    styles = commands(["bibliographystyle"])
+
+    if cites and not bibtex and not biblatex:
+        found_error = True
+        file_name, text, cmd = cites[0]
+        report(
+            file_name,
+            text,
+            cmd.start,
+            "Citations are used, but neither \\bibliography nor \\printbibliography is",
+        )
+    if bibtex and biblatex:
+        found_error = True
+        file_name, text, cmd = bibtex[0]
+        report(
+            file_name,
+            text,
+            cmd.start,
+            "Both \\bibliography and \\printbibliography are used, choose one of them",
+        )
+    for file_name, text, cmd in bibtex[1:]:
+        found_error = True
+        report(file_name, text, cmd.start, "\\bibliography is used multiple times")
+
+    if bibtex and not styles:
+        found_error = True
+        file_name, text, cmd = bibtex[0]
+        report(file_name, text, cmd.start, "Missing \\bibliographystyle")
+    if biblatex and not resources:
+        found_error = True
+        file_name, text, cmd = biblatex[0]
+        report(file_name, text, cmd.start, "Missing \\addbibresource")
+
+    # Check that all referenced bib files exist
+    for file_name, text, cmd in bibtex + resources:
+        if not cmd.args:
+            continue
+        directory = os.path.dirname(file_name)
+        for resource in cmd.args[0].split(","):
+            resource = resource.strip()
+            if cmd.name == "bibliography":
+                path = os.path.join(directory, resource)
+                if not path.endswith(".bib"):
+                    path += ".bib"
+            else:
+                if not resource.endswith(".bib"):
+                    found_error = True
+                    report(
+                        file_name,
+                        text,
+                        cmd.start,
+                        f"\\addbibresource needs the extension, use {resource}.bib",
+                    )
+                    continue
+                path = os.path.join(directory, resource)
+            if not os.path.isfile(path):
+                found_error = True
+                report(file_name, text, cmd.start, f"Bibliography {path} is missing")
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files, args.root, search, "Found problems with the bibliography setup"
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,107 @@
+#!/usr/bin/env python3
+import argparse
+import sys
+import typing as t
+
+from latexhooks.common import (
+    CITE_COMMANDS,
+    iter_commands,
+    mask_non_prose,
+    mask_source,
+    report,
+)
+
+CHECKS = ["citations", "footnotes", "sentences"]
+FOOTNOTE_COMMANDS = ["footnote", "footnotemark", "footnotetext"]
+# Caption commands and the index of the argument with the caption text
+CAPTION_COMMANDS = {"caption": 0, "subcaption": 0, "captionof": 1}
+
+
+def captions(text: str) -> t.List[t.Tuple[int, str]]:
+    """Return the offsets and contents of all captions in the order of the text"""
+    result: t.List[t.Tuple[int, str]] = []
+    for name, index in CAPTION_COMMANDS.items():
+        for cmd in iter_commands(text, [name], nargs=index + 1):
+            if len(cmd.args) > index:
+                result.append((cmd.arg_offsets[index], cmd.args[index]))
+    return sorted(result)
+
+
+def check_sentence(caption: str) -> t.Optional[str]:
+    """Return why the caption is not a full sentence, if it is not one"""
+    prose = mask_non_prose(caption).strip()
+    # Captions of only math or commands cannot be checked
+    if not prose:
+        return None
+    # Captions may start with math, like "$n$ over time."
+    starts_with_math = caption.lstrip().startswith(("$", "\\("))
+    if not starts_with_math and prose[0].isalpha() and not prose[0].isupper():
+        return "Caption does not start with a capital letter"
+    if prose[-1] not in ".!?":
+        return "Caption does not end with a full stop"
+    return None
+
+
+def search(files: t.List[t.IO[str]], checks: t.List[str]) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        for offset, caption in captions(text):
+            if "citations" in checks:
+                for cmd in iter_commands(caption, CITE_COMMANDS, nargs=0):
+                    found_error = True
+                    report(
+                        f.name,
+                        text,
+                        offset + cmd.start,
+                        f"Citation \\{cmd.name} in a caption, cite in the text instead",
+                    )
+            if "footnotes" in checks:
+                for cmd in iter_commands(caption, FOOTNOTE_COMMANDS, nargs=0):
+                    found_error = True
+                    report(
+                        f.name,
+                        text,
+                        offset + cmd.start,
+                        f"Footnote \\{cmd.name} in a caption, move it into the text",
+                    )
+            if "sentences" in checks:
+                message = check_sentence(caption)
+                if message is not None:
+                    found_error = True
+                    report(f.name, text, offset, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--disable",
+        action="append",
+        choices=CHECKS,
+        default=list(),
+        help="Check which is not performed. Can be given multiple times.",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    checks = [check for check in CHECKS if check not in args.disable]
+    found_error = search(args.files, checks)
+    if found_error:
+        sys.exit("Found captions violating the content rules")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,166 @@
+#!/usr/bin/env python3
+import argparse
+import bisect
+import difflib
+import re
+import typing as t
+
+from latexhooks.common import (
+    REF_COMMANDS,
+    RE_ABBREVIATION,
+    RE_SENTENCE_END,
+    add_root_argument,
+    blank_spans,
+    check_projects,
+    iter_commands,
+    iter_environments,
+    line_number,
+    mask_non_prose,
+    read_project,
+    report,
+    split_list,
+)
+
+FLOATS = ["figure", "figure*", "table", "table*"]
+RE_PARAGRAPH_BREAK = re.compile(r"\n[ \t]*\n")
+RE_WORD = re.compile(r"\w+")
+
+
+class Prose:
+    """The prose of a file outside of floats, split into sentences"""
+
+    def __init__(self, text: str) -> None:
+        floats = [(env.start, env.end) for env in iter_environments(text, FLOATS)]
+        self.prose = blank_spans(mask_non_prose(text), floats)
+        # Dots of abbreviations do not end a sentence
+        dots = RE_ABBREVIATION.sub(lambda m: m[0].replace(".", " "), self.prose)
+        bounds = {m.end() for m in RE_SENTENCE_END.finditer(dots)}
+        bounds |= {m.start() for m in RE_PARAGRAPH_BREAK.finditer(dots)}
+        self.bounds = sorted(bounds | {0, len(text)})
+
+    def sentences(self, offset: int, count: int) -> t.List[t.Tuple[int, t.List[str]]]:
+        """Return the offsets and words of up to `count` sentences from `offset` on"""
+        result: t.List[t.Tuple[int, t.List[str]]] = []
+        idx = max(bisect.bisect_right(self.bounds, offset) - 1, 0)
+        while len(result) < count and idx + 1 < len(self.bounds):
+            sentence = self.prose[self.bounds[idx] : self.bounds[idx + 1]]
+            words = words_of(sentence)
+            if words:
+                start = self.bounds[idx] + len(sentence) - len(sentence.lstrip())
+                result.append((start, words))
+            idx += 1
+        return result
+
+
+def words_of(prose: str) -> t.List[str]:
+    return RE_WORD.findall(prose.lower())
+
+
+def caption_sentences(caption: str, min_words: int) -> t.List[t.List[str]]:
+    """Split the caption into sentences and return those with at least `min_words`"""
+    prose = mask_non_prose(caption)
+    prose = RE_ABBREVIATION.sub(lambda m: m[0].replace(".", " "), prose)
+    sentences = [words_of(sentence) for sentence in RE_SENTENCE_END.split(prose)]
+    return [words for words in sentences if len(words) >= min_words]
+
+
+def coverage(caption: t.List[str], sentence: t.List[str]) -> float:
+    """Fraction of the caption words which appear in the same order in the sentence"""
+    matcher = difflib.SequenceMatcher(None, caption, sentence, autojunk=False)
+    matched = sum(block.size for block in matcher.get_matching_blocks())
+    return matched / len(caption)
+
+
+def search(files: t.List[t.IO[str]], threshold: float, min_words: int) -> bool:
+    texts = read_project(files)
+    contents = dict(texts)
+    prose = {file_name: Prose(text) for file_name, text in texts}
+
+    # References to each label with the file and the offset of the reference
+    references: t.Dict[str, t.List[t.Tuple[str, int]]] = dict()
+    for file_name, text in texts:
+        for cmd in iter_commands(text, REF_COMMANDS):
+            for label in split_list(cmd.args):
+                references.setdefault(label, []).append((file_name, cmd.start))
+
+    found_error = False
+    for file_name, text in texts:
+        for env in iter_environments(text, FLOATS):
+            body = text[env.body_start : env.body_end]
+            captions = list(iter_commands(body, ["caption"]))
+            if not captions or not captions[0].args:
+                continue
+            caption = caption_sentences(captions[0].args[0], min_words)
+            labels = [
+                cmd.args[0].strip()
+                for cmd in iter_commands(body, ["label"])
+                if cmd.args
+            ]
+
+            # The sentences referencing the float and the first sentence after it
+            candidates = [
+                (ref_file, offset, words)
+                for label in labels
+                for ref_file, ref_offset in references.get(label, [])
+                for offset, words in prose[ref_file].sentences(ref_offset, 2)
+            ]
+            candidates += [
+                (file_name, offset, words)
+                for offset, words in prose[file_name].sentences(env.end, 1)
+            ]
+            for ref_file, offset, words in candidates:
+                if any(coverage(sentence, words) >= threshold for sentence in caption):
+                    found_error = True
+                    report(
+                        file_name,
+                        text,
+                        env.body_start + captions[0].start,
+                        f"Caption of the {env.name} is repeated in "
+                        f"{ref_file}:{line_number(contents[ref_file], offset)}, "
+                        "the text should not restate it",
+                    )
+                    break
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--threshold",
+        type=float,
+        default=0.8,
+        help="Fraction of the words of a caption sentence which must reappear in order "
+        "in the text to count as repetition (default: 0.8)",
+    )
+    parser.add_argument(
+        "--min-words",
+        type=int,
+        default=5,
+        help="Shorter caption sentences are not checked (default: 5)",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, args.threshold, args.min_words),
+        "Found captions repeated in the text",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,106 @@
+#!/usr/bin/env python3
+import argparse
+import codecs
+import sys
+import typing as t
+
+# Legacy encodings tried in order when transcoding a file to UTF-8
+LEGACY_ENCODINGS = ["cp1252", "latin-1"]
+
+
+def detect_encoding(data: bytes, encodings: t.List[str]) -> t.Optional[str]:
+    """Return the first of `encodings` which can decode `data`"""
+    for encoding in encodings:
+        try:
+            data.decode(encoding)
+        except UnicodeDecodeError:
+            continue
+        return encoding
+    return None
+
+
+def check_file(file_name: str, bom: str, fix: bool, encodings: t.List[str]) -> bool:
+    with open(file_name, "rb") as f:
+        data = f.read()
+
+    has_bom = data.startswith(codecs.BOM_UTF8)
+    content = data[len(codecs.BOM_UTF8) :] if has_bom else data
+    try:
+        content.decode("utf-8")
+        invalid: t.Optional[UnicodeDecodeError] = None
+    except UnicodeDecodeError as err:
+        invalid = err
+
+    found_error = False
+    if invalid is not None:
+        found_error = True
+        offset = invalid.start + (len(codecs.BOM_UTF8) if has_bom else 0)
+        line = data.count(b"\n", 0, offset) + 1
+        byte = data[offset : offset + 1].hex()
+        encoding = detect_encoding(content, encodings)
+        if fix and encoding is not None:
+            print(f"{file_name}:{line} Transcoded from {encoding} to UTF-8")
+            content = content.decode(encoding).encode("utf-8")
+        else:
+            print(f"{file_name}:{line} Invalid UTF-8 byte 0x{byte} at offset {offset}")
+            return found_error
+
+    if bom == "forbid" and has_bom:
+        found_error = True
+        print(f"{file_name}:1 File starts with a UTF-8 byte order mark")
+        has_bom = not fix
+    elif bom == "require" and not has_bom:
+        found_error = True
+        print(f"{file_name}:1 File does not start with a UTF-8 byte order mark")
+        has_bom = fix
+
+    if fix and found_error:
+        with open(file_name, "wb") as f:
+            f.write((codecs.BOM_UTF8 if has_bom else b"") + content)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--bom",
+        choices=["forbid", "require", "allow"],
+        default="forbid",
+        help="Whether files must not, must, or may start with a byte order mark",
+    )
+    parser.add_argument(
+        "--encoding",
+        action="append",
+        metavar="ENCODING",
+        help="Legacy encoding to transcode from. Can be given multiple times.",
+        default=list(),
+    )
+    parser.add_argument(
+        "--fix",
+        action="store_true",
+        help="Transcode files to UTF-8 and fix the byte order mark in place",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = False
+    for file_name in args.files:
+        if check_file(file_name, args.bom, args.fix, args.encoding or LEGACY_ENCODINGS):
+            found_error = True
+    if found_error:
+        sys.exit("Found files which are not encoded as UTF-8")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,192 @@
+#!/usr/bin/env python3
+import argparse
+import subprocess
+import sys
+import typing as t
+from dataclasses import dataclass
+
+from latexhooks.common import disabled_regions, is_disabled, line_offset, report
+
+# Separator between the fields of a chktex message, unlikely to occur in a message
+SEP = "\x1f"
+OUTPUT_FORMAT = SEP.join(["%f", "%l", "%c", "%n", "%k", "%m"]) + "\n"
+
+# Rule ids of the chktex warning numbers, used in the output, `--ignore`, and
+# `% latex-hooks: off` directives. Unknown numbers become `chktex-<number>`.
+RULES = {
+    1: "command-space",
+    2: "nonbreaking-space",
+    3: "parenthesis-braces",
+    4: "italic-correction-non-italic",
+    5: "italic-correction-twice",
+    6: "italic-correction-missing",
+    7: "accent-dotless",
+    8: "dash-length",
+    9: "unmatched-delimiter",
+    10: "solo-delimiter",
+    11: "ellipsis",
+    12: "interword-spacing",
+    13: "intersentence-spacing",
+    14: "missing-argument",
+    15: "no-match",
+    16: "math-mode-open",
+    17: "delimiter-count",
+    18: "double-quote",
+    19: "quote-character",
+    20: "user-pattern",
+    21: "unintended-command",
+    22: "comment-displayed",
+    23: "thin-space-quotes",
+    24: "space-before-label",
+    25: "braces-suggested",
+    26: "space-before-punctuation",
+    27: "latex-command-failed",
+    28: "italic-correction-punctuation",
+    29: "times-symbol",
+    30: "multiple-spaces",
+    31: "ignored-text",
+    32: "opening-quote",
+    33: "closing-quote",
+    34: "mixed-quotes",
+    35: "math-operator",
+    36: "space-around-parenthesis",
+    37: "no-space-around-parenthesis",
+    38: "punctuation-at-quote",
+    39: "double-space",
+    40: "math-punctuation",
+    41: "tex-primitive",
+    42: "space-before-command",
+    43: "unexpected-follower",
+    44: "user-regex",
+    45: "display-math-dollars",
+    46: "inline-math-dollars",
+}
+
+
+@dataclass
+class Diagnostic:
+    """A single message reported by chktex"""
+
+    file_name: str
+    line_number: int
+    """ Line of the message, 1-based """
+    column: int
+    """ Column of the message, 1-based """
+    number: int
+    """ Number of the chktex warning """
+    kind: str
+    """ One of "Error", "Warning", or "Message" """
+    message: str
+
+    @property
+    def rule(self) -> str:
+        return RULES.get(self.number, f"chktex-{self.number}")
+
+    @property
+    def severity(self) -> str:
+        """The kind as `error`, `warning`, or `info`"""
+        return {"Error": "error", "Warning": "warning"}.get(self.kind, "info")
+
+
+def parse_output(output: str) -> t.List[Diagnostic]:
+    diagnostics: t.List[Diagnostic] = []
+    for line in output.splitlines():
+        fields = line.split(SEP)
+        if len(fields) != 6:
+            continue
+        file_name, line_number, column, number, kind, message = fields
+        diagnostics.append(
+            Diagnostic(
+                file_name=file_name,
+                line_number=int(line_number),
+                column=int(column),
+                number=int(number),
+                kind=kind,
+                message=message.strip(),
+            )
+        )
+    return diagnostics
+
+
+def run_chktex(file_name: str, ignore: t.List[int]) -> t.List[Diagnostic]:
+    cmd = ["chktex", "--quiet", "--verbosity=0", f"--format={OUTPUT_FORMAT}"]
+    cmd += [f"--nowarn={number}" for number in ignore]
+    cmd.append(file_name)
+    try:
+        # chktex signals found warnings using the exit code, so do not check it
+        result = subprocess.run(cmd, capture_output=True, encoding="utf-8")
+    except FileNotFoundError:
+        sys.exit("chktex is not installed or cannot be found in PATH")
+    return parse_output(result.stdout)
+
+
+def rule_number(value: str) -> int:
+    """Parse the value of `--ignore`, a chktex warning number or its rule id"""
+    if value.isdigit():
+        return int(value)
+    if value.startswith("chktex-") and value[len("chktex-") :].isdigit():
+        return int(value[len("chktex-") :])
+    numbers = {rule: number for number, rule in RULES.items()}
+    if value not in numbers:
+        raise argparse.ArgumentTypeError(f"unknown chktex rule '{value}'")
+    return numbers[value]
+
+
+def search(files: t.List[str], ignore: t.List[int], fail_on: t.Set[str]) -> bool:
+    found_error = False
+    for file_name in files:
+        with open(file_name, encoding="utf-8") as f:
+            text = f.read()
+        regions = disabled_regions(text, "chktex")
+        for diag in run_chktex(file_name, ignore):
+            offset = line_offset(text, diag.line_number, diag.column)
+            if is_disabled(regions, offset, diag.rule):
+                continue
+            if diag.kind in fail_on:
+                found_error = True
+            report(
+                file_name, text, offset, f"{diag.severity} {diag.rule}: {diag.message}"
+            )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--ignore",
+        action="append",
+        metavar="RULE",
+        type=rule_number,
+        help="Rule id or number of a chktex warning to ignore, e.g., command-space "
+        "or 1. Can be given multiple times.",
+        default=list(),
+    )
+    parser.add_argument(
+        "--fail-on-messages",
+        action="store_true",
+        help="Also fail if chktex only reports messages, not only warnings and errors",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        nargs="+",
+        help="List of filenames to check",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    fail_on = {"Error", "Warning"}
+    if args.fail_on_messages:
+        fail_on.add("Message")
+    found_error = search(sorted(args.files), args.ignore, fail_on)
+    if found_error:
+        sys.exit("chktex found problems")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
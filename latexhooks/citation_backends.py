@@ -0,0 +1,91 @@
+#!/usr/bin/env python3
+import argparse
+import sys
+import typing as t
+
+from latexhooks.common import (
+    BACKEND_COMMANDS,
+    add_root_argument,
+    bibliography_files,
+    citation_backend,
+    is_root,
+    iter_commands,
+    read_masked,
+    read_project,
+    report,
+)
+
+
+def search(files: t.List[t.IO[str]], roots: t.List[str]) -> bool:
+    # Without --root all root documents are compared
+    if not roots:
+        roots = [f.name for f in files if is_root(read_masked(f.name))]
+
+    # Backend of each root, the roots using a .bib file, and the roots including a file
+    backends: t.Dict[str, t.Optional[str]] = dict()
+    bib_users: t.Dict[str, t.List[str]] = dict()
+    includers: t.Dict[str, t.List[str]] = dict()
+    for root in roots:
+        with open(root, encoding="utf-8") as f:
+            texts = read_project([f])
+        backends[root] = citation_backend(texts)
+        for file_name, text in texts:
+            for bib_name in bibliography_files(file_name, text):
+                bib_users.setdefault(bib_name, list()).append(root)
+            if file_name != texts[0][0]:
+                includers.setdefault(file_name, list()).append(root)
+
+    found_error = False
+    for bib_name, users in sorted(bib_users.items()):
+        if len({backends[root] for root in users}) > 1:
+            found_error = True
+            print(f"Found different citation backends for {bib_name}")
+            for root in users:
+                print(f"  {root}: {backends[root] or 'neither natbib nor biblatex'}")
+            print()
+
+    # Files shared between roots must only use commands all of them provide
+    for file_name, roots_of_file in sorted(includers.items()):
+        if len(roots_of_file) < 2:
+            continue
+        text = read_masked(file_name)
+        for backend, commands in BACKEND_COMMANDS.items():
+            other_roots = [root for root in roots_of_file if backends[root] != backend]
+            if not other_roots:
+                continue
+            for cmd in iter_commands(text, commands, nargs=0):
+                found_error = True
+                message = (
+                    f"\\{cmd.name} requires {backend}, but the file is also"
+                    f" included by {other_roots[0]}"
+                )
+                report(file_name, text, cmd.start, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    files = sorted(args.files, key=lambda f: f.name)
+    found_error = search(files, args.root)
+    if found_error:
+        sys.exit("Found inconsistent citation backends")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,92 @@
+#!/usr/bin/env python3
+import argparse
+import typing as t
+
+from latexhooks.common import (
+    CITE_COMMANDS,
+    add_root_argument,
+    check_projects,
+    iter_commands,
+    read_project,
+    report,
+    split_list,
+    split_sections,
+)
+
+SECTIONING_COMMANDS = ["chapter", "section", "subsection"]
+
+
+def search(
+    files: t.List[t.IO[str]], min_citations: t.Dict[str, int], max_keys: int
+) -> bool:
+    texts = read_project(files)
+
+    found_error = False
+    counts: t.Dict[str, int] = dict()
+    for section in split_sections(texts, SECTIONING_COMMANDS):
+        body = section.text[section.start : section.end]
+        title = section.title.lower()
+        for cmd in iter_commands(body, CITE_COMMANDS):
+            keys = split_list(cmd.args)
+            counts[title] = counts.get(title, 0) + len(keys)
+            if len(keys) > max_keys:
+                found_error = True
+                report(
+                    section.file_name,
+                    section.text,
+                    section.start + cmd.start,
+                    f"\\{cmd.name} cites {len(keys)} entries at once, "
+                    f"the maximum is {max_keys}",
+                )
+
+    for title, minimum in min_citations.items():
+        count = counts.get(title.lower(), 0)
+        if count < minimum:
+            found_error = True
+            print(f"Section {title} has {count} citations, the minimum is {minimum}")
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--min-citations",
+        action="append",
+        metavar=("title=count"),
+        type=lambda x: x.rsplit("=", 1),
+        help="Minimum number of citations in the section with the title",
+        default=list(),
+    )
+    parser.add_argument(
+        "--max-keys-per-cite",
+        type=int,
+        default=5,
+        help="Maximum number of entries cited by a single citation command",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    min_citations = {title: int(count) for title, count in args.min_citations}
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, min_citations, args.max_keys_per_cite),
+        "Found sections with too few or too dense citations",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,88 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    check_projects,
+    citation_backend,
+    iter_commands,
+    read_project,
+    report,
+)
+
+# Bare citation commands and the command for citing the authors as a noun
+BACKENDS = {
+    "natbib": (["cite", "citep"], "\\citet"),
+    "biblatex": (["cite", "parencite", "autocite"], "\\textcite"),
+}
+
+# Verbs with the citation as their subject, like "\cite{x} shows that ..."
+RE_VERB = re.compile(
+    r"\s+(?:show|propose|introduce|present|demonstrate|argue|describe|find|use"
+    r"|suggest|report|develop|study|observe|prove|extend|define)(?:s|d|ed|es)?\b"
+    r"|\s+(?:found|studied|studies)\b"
+    r"|'s\b"
+)
+# The citation starts a sentence or paragraph
+RE_SENTENCE_START = re.compile(r"(?:^|[.!?]|\n[ \t]*\n)\s*$")
+
+
+def search(files: t.List[t.IO[str]], backend: str) -> bool:
+    texts = read_project(files)
+    if backend == "auto":
+        backend = citation_backend(texts) or "natbib"
+    commands, suggestion = BACKENDS[backend]
+
+    found_error = False
+    for file_name, text in texts:
+        for cmd in iter_commands(text, commands):
+            if not cmd.args:
+                continue
+            at_sentence_start = RE_SENTENCE_START.search(text[: cmd.start])
+            # "Smith et al.~\cite{x} show" already names the authors
+            after_authors = text[: cmd.start].endswith("~")
+            if at_sentence_start or (
+                not after_authors and RE_VERB.match(text, cmd.end)
+            ):
+                found_error = True
+                message = f"\\{cmd.name} used as a noun, cite the authors with "
+                report(file_name, text, cmd.start, message + suggestion)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--backend",
+        choices=["auto", *BACKENDS],
+        default="auto",
+        help="Citation commands to check, auto detects them from the loaded packages",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, args.backend),
+        "Found citations used as nouns",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
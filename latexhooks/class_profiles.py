@@ -0,0 +1,177 @@
+#!/usr/bin/env python3
+import argparse
+import typing as t
+from dataclasses import dataclass, field
+
+from latexhooks.common import (
+    add_root_argument,
+    check_projects,
+    is_root,
+    iter_commands,
+    iter_environments,
+    iter_packages,
+    read_project,
+    report,
+)
+
+# Lengths defining the page layout, which publishers do not allow to change
+LAYOUT_LENGTHS = [
+    "\\textwidth",
+    "\\textheight",
+    "\\oddsidemargin",
+    "\\evensidemargin",
+    "\\topmargin",
+    "\\headheight",
+    "\\headsep",
+    "\\footskip",
+    "\\columnsep",
+]
+
+
+@dataclass
+class Profile:
+    """Rules of a publisher for documents using its document class"""
+
+    classes: t.List[str]
+    """ Document classes the profile is used for """
+    forbidden_packages: t.Dict[str, str] = field(default_factory=dict)
+    """ Packages which must not be used, mapped to the reason """
+    required_commands: t.List[str] = field(default_factory=list)
+    """ Commands which must be used somewhere in the document """
+    required_environments: t.List[str] = field(default_factory=list)
+    """ Environments which must be used somewhere in the document """
+    fixed_layout: bool = False
+    """ The page layout must not be changed """
+
+
+PROFILES = {
+    "acm": Profile(
+        classes=["acmart"],
+        forbidden_packages={
+            "times": "acmart already sets the fonts",
+            "geometry": "acmart sets the page layout",
+            "fullpage": "acmart sets the page layout",
+        },
+        required_commands=["citestyle"],
+        fixed_layout=True,
+    ),
+    "ieee": Profile(
+        classes=["IEEEtran"],
+        forbidden_packages={
+            "geometry": "IEEEtran sets the page layout",
+            "fullpage": "IEEEtran sets the page layout",
+        },
+        required_environments=["IEEEkeywords"],
+        fixed_layout=True,
+    ),
+    "lncs": Profile(
+        classes=["llncs"],
+        forbidden_packages={
+            "geometry": "LNCS does not allow changing the margins",
+            "fullpage": "LNCS does not allow changing the margins",
+        },
+        fixed_layout=True,
+    ),
+    "elsevier": Profile(
+        classes=["elsarticle"],
+        forbidden_packages={
+            "geometry": "elsarticle sets the page layout",
+        },
+        required_environments=["frontmatter", "keyword"],
+    ),
+}
+
+
+def detect_profile(text: str) -> t.Optional[str]:
+    """Return the name of the profile matching the document class"""
+    for cmd in iter_commands(text, ["documentclass"]):
+        if not cmd.args:
+            continue
+        for name, profile in PROFILES.items():
+            if cmd.args[0].strip() in profile.classes:
+                return name
+    return None
+
+
+def check_profile(name: str, texts: t.List[t.Tuple[str, str]]) -> bool:
+    """Check the project, whose root is the first file, against the profile"""
+    profile = PROFILES[name]
+    found_error = False
+    root_name, root_text = texts[0]
+    for file_name, text in texts:
+        for cmd, package in iter_packages(text):
+            if package in profile.forbidden_packages:
+                found_error = True
+                reason = profile.forbidden_packages[package]
+                message = f"Package {package} is not allowed for {name}: {reason}"
+                report(file_name, text, cmd.start, message)
+        if profile.fixed_layout:
+            for cmd in iter_commands(text, ["setlength", "addtolength"]):
+                if cmd.args and cmd.args[0].strip() in LAYOUT_LENGTHS:
+                    found_error = True
+                    length = cmd.args[0].strip()
+                    message = f"Changing {length} is not allowed for {name}"
+                    report(file_name, text, cmd.start, message)
+            for cmd in iter_commands(text, ["newgeometry"], nargs=0):
+                found_error = True
+                message = f"Changing the page layout is not allowed for {name}"
+                report(file_name, text, cmd.start, message)
+
+    for command in profile.required_commands:
+        if not any(any(iter_commands(text, [command], nargs=0)) for _, text in texts):
+            found_error = True
+            message = f"The {name} profile requires \\{command}"
+            report(root_name, root_text, 0, message)
+    for environment in profile.required_environments:
+        if not any(any(iter_environments(text, [environment])) for _, text in texts):
+            found_error = True
+            message = f"The {name} profile requires the {environment} environment"
+            report(root_name, root_text, 0, message)
+    return found_error
+
+
+def search(files: t.List[t.IO[str]], profile_name: t.Optional[str]) -> bool:
+    found_error = False
+    for f in files:
+        texts = read_project([f])
+        if not texts or not is_root(texts[0][1]):
+            continue
+        name = profile_name or detect_profile(texts[0][1])
+        if name is not None:
+            found_error |= check_profile(name, texts)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--profile",
+        choices=sorted(PROFILES),
+        help="Use this profile instead of detecting it from the document class",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, args.profile),
+        "Found violations of the publisher profile",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
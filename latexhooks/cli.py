@@ -0,0 +1,591 @@
+#!/usr/bin/env python3
+import argparse
+import contextlib
+import importlib
+import io
+import os
+import re
+import shutil
+import subprocess
+import sys
+import typing as t
+from xml.sax.saxutils import escape, quoteattr
+
+import yaml
+
+from latexhooks.common import (
+    ENGINE_PACKAGES,
+    is_root,
+    iter_commands,
+    iter_packages,
+    magic_program,
+    read_project,
+)
+
+# Hooks whose module is not named after the hook id
+HOOK_MODULES = {
+    "latex-merge-conflict": "merge_conflict",
+    "unique-tikz-external-names": "tikz_external_names",
+}
+# Hooks which check BibTeX files too
+BIB_HOOKS = {
+    "bibliography-keys",
+    "check-encoding",
+    "label-bib-collisions",
+    "latex-merge-conflict",
+}
+# Hooks which are not written in Python, with the command running them
+COMMAND_HOOKS = {"ensure-labels-for-sections": "ensure-labels"}
+TEX_EXTENSIONS = (".tex", ".sty", ".cls")
+# Hooks added or changed in each version of the rule set, starting with the hooks of
+# release 1.4.0. Every release adding hooks or changing their results adds a version.
+RULE_SETS: t.Dict[int, t.List[str]] = {
+    1: [
+        "american-eg-ie",
+        "cleveref-capitalization",
+        "cispa-syssec-forbidden-words",
+        "consistent-spelling",
+        "csquotes",
+        "ensure-labels-for-sections",
+        "no-space-in-cite",
+        "tilde-cite",
+        "unique-labels",
+        "cleveref-instead-of-autoref",
+    ],
+    2: [
+        "abstract-keywords",
+        "beamer-frames",
+        "bibliography-keys",
+        "bibliography-wiring",
+        "caption-content",
+        "caption-repetition",
+        "check-encoding",
+        "chktex",
+        "citation-backends",
+        "citation-density",
+        "citation-nouns",
+        "class-profiles",
+        "colon-capitalization",
+        "color-usage",
+        "commented-floats",
+        "consistent-spelling",
+        "counter-resets",
+        "date-freshness",
+        "duplicate-preambles",
+        "duplicate-titles",
+        "ensure-labels-for-sections",
+        "ensuremath",
+        "environment-spacing",
+        "eof-hygiene",
+        "external-references",
+        "final-document",
+        "float-centering",
+        "float-widths",
+        "font-encoding",
+        "footnote-length",
+        "footnote-marks",
+        "heading-length",
+        "hyphenation-hacks",
+        "image-files",
+        "label-after-caption",
+        "label-bib-collisions",
+        "language-consistency",
+        "latex-merge-conflict",
+        "latexindent-check",
+        "latin-abbreviations",
+        "listing-line-length",
+        "longtable-headers",
+        "macro-arguments",
+        "math-fonts",
+        "math-units",
+        "no-def",
+        "nonbreaking-spaces",
+        "number-ranges",
+        "package-conflicts",
+        "paragraph-lengths",
+        "pdf-metadata",
+        "preamble-commands",
+        "proper-nouns",
+        "readability",
+        "required-elements",
+        "siunitx-columns",
+        "spacing",
+        "spellcheck",
+        "subcaption-labels",
+        "supplementary-references",
+        "table-notes",
+        "table-row-spacing",
+        "term-introductions",
+        "theorem-environments",
+        "theorem-proofs",
+        "tikz-libraries",
+        "toc-entries",
+        "track-changes",
+        "unbreakable-strings",
+        "unicode-math",
+        "unique-labels",
+        "unique-tikz-external-names",
+        "unsectioned-text",
+        "verb-in-arguments",
+        "wordcount",
+    ],
+}
+
+
+def project_files(paths: t.List[str]) -> t.List[str]:
+    """Return `paths` or, if empty, all files in the current directory"""
+    if not paths:
+        for directory, dirs, names in os.walk("."):
+            dirs[:] = sorted(d for d in dirs if not d.startswith("."))
+            paths += [os.path.join(directory, n) for n in sorted(names)]
+    return paths
+
+
+def find_roots(paths: t.List[str]) -> t.List[str]:
+    """Return the root documents among `paths` or in the current directory"""
+    paths = project_files(paths)
+    roots: t.List[str] = []
+    for path in paths:
+        if not path.endswith(".tex") or not os.path.isfile(path):
+            continue
+        with open(path, encoding="utf-8") as f:
+            if is_root(f.read()):
+                roots.append(os.path.normpath(path))
+    return roots
+
+
+def configured_hooks(config: str) -> t.List[t.Dict[str, t.Any]]:
+    """Return the hooks of this repository enabled in the configuration
+
+    The `files` and `exclude` patterns of the configuration are combined with the
+    ones of each hook.
+    """
+    if not os.path.isfile(config):
+        return []
+    with open(config, encoding="utf-8") as f:
+        try:
+            data = yaml.safe_load(f)
+        except yaml.YAMLError as error:
+            sys.exit(f"Cannot parse {config}: {error}")
+    if not isinstance(data, dict):
+        return []
+    hooks: t.List[t.Dict[str, t.Any]] = []
+    for repo in data.get("repos") or []:
+        if "pre-commit-latex-hooks" not in str(repo.get("repo", "")):
+            continue
+        for hook in repo.get("hooks") or []:
+            hooks.append(
+                {
+                    "id": str(hook["id"]),
+                    "args": [str(arg) for arg in hook.get("args") or []],
+                    "files": [data.get("files", ""), hook.get("files", "")],
+                    "exclude": [
+                        data.get("exclude", "^$"),
+                        hook.get("exclude", "^$"),
+                    ],
+                }
+            )
+    return hooks
+
+
+def hook_files(hook: t.Dict[str, t.Any], paths: t.List[str]) -> t.List[str]:
+    """Return the `paths` matching the `files` and `exclude` patterns of the hook"""
+    return [
+        path
+        for path in paths
+        if all(re.search(pattern, path) for pattern in hook["files"])
+        and not any(re.search(pattern, path) for pattern in hook["exclude"])
+    ]
+
+
+def active_hooks(config: str) -> t.List[str]:
+    """Return the ids of the hooks of this repository enabled in the configuration"""
+    return [hook["id"] for hook in configured_hooks(config)]
+
+
+def known_rules() -> t.List[str]:
+    """Return the ids of all hooks of this repository"""
+    return sorted({rule for rules in RULE_SETS.values() for rule in rules})
+
+
+def newer_rules(rule_set: int) -> t.List[str]:
+    """Return the hooks added or changed after version `rule_set` of the rule set"""
+    return sorted(
+        {
+            rule
+            for version, rules in RULE_SETS.items()
+            if version > rule_set
+            for rule in rules
+        }
+    )
+
+
+def add_rule_selection(parser: argparse.ArgumentParser) -> None:
+    parser.add_argument(
+        "--only",
+        action="append",
+        metavar="RULES",
+        default=list(),
+        help="Comma separated list of hook ids, only these are used",
+    )
+    parser.add_argument(
+        "--skip",
+        action="append",
+        metavar="RULES",
+        default=list(),
+        help="Comma separated list of hook ids which are not used",
+    )
+    parser.add_argument(
+        "--rule-set",
+        type=int,
+        choices=sorted(RULE_SETS),
+        default=max(RULE_SETS),
+        metavar="VERSION",
+        help="Pin the version of the rule set, hooks added or changed later are not "
+        "used. Defaults to the latest version.",
+    )
+
+
+def select_rules(rules: t.List[str], args: argparse.Namespace) -> t.List[str]:
+    """Filter `rules` by the `--only`, `--skip`, and `--rule-set` options"""
+    only = {r.strip() for value in args.only for r in value.split(",") if r.strip()}
+    skip = {r.strip() for value in args.skip for r in value.split(",") if r.strip()}
+    newer = newer_rules(args.rule_set)
+    return [
+        rule
+        for rule in rules
+        if (not only or rule in only) and rule not in skip and rule not in newer
+    ]
+
+
+def pinned_rules(rules: t.List[str], args: argparse.Namespace) -> t.List[str]:
+    """Return the `rules` which are not used, because they changed after `--rule-set`"""
+    newer = newer_rules(args.rule_set)
+    return [rule for rule in rules if rule in newer]
+
+
+def unknown_rules(args: argparse.Namespace, known: t.List[str]) -> t.List[str]:
+    """Return the rules named in `--only` and `--skip` which do not exist"""
+    return [
+        rule.strip()
+        for value in args.only + args.skip
+        for rule in value.split(",")
+        if rule.strip() and rule.strip() not in known
+    ]
+
+
+def info(args: argparse.Namespace) -> None:
+    roots = find_roots(list(args.files))
+    if not roots:
+        print("No root document with \\documentclass found")
+    for root in roots:
+        with open(root, encoding="utf-8") as f:
+            program = magic_program(f.read())
+            f.seek(0)
+            texts = read_project([f])
+        classes = [
+            cmd.args[0].strip()
+            for cmd in iter_commands(texts[0][1], ["documentclass"])
+            if cmd.args
+        ]
+        packages: t.List[str] = []
+        for _, text in texts:
+            for _, package in iter_packages(text):
+                if package not in packages:
+                    packages.append(package)
+        engines = [
+            f"{' or '.join(ENGINE_PACKAGES[p])} ({p})"
+            for p in packages
+            if p in ENGINE_PACKAGES
+        ]
+        if program is not None:
+            engines.insert(0, f"{program} (magic comment)")
+
+        print(f"Root document: {root}")
+        print(f"Included files: {', '.join(name for name, _ in texts[1:]) or '-'}")
+        print(f"Document class: {', '.join(classes) or '-'}")
+        print(f"Engine: {', '.join(engines) or 'any'}")
+        print(f"Packages: {', '.join(packages) or '-'}")
+        print()
+
+    hooks = active_hooks(args.config)
+    print(f"Rule set: {args.rule_set} (latest {max(RULE_SETS)})")
+    selected = select_rules(hooks, args)
+    print(f"Active checks in {args.config}: {', '.join(selected) or '-'}")
+    pinned = pinned_rules(hooks, args)
+    if pinned:
+        print(f"Changed after rule set {args.rule_set}: {', '.join(pinned)}")
+
+
+class FileCache:
+    """Texts of the checked files, which are read only once for all hooks"""
+
+    def __init__(self) -> None:
+        self.texts: t.Dict[str, str] = dict()
+
+    def open(self, path: str) -> t.IO[str]:
+        """Return the text of the file as file object, reading it on first use"""
+        if path not in self.texts:
+            try:
+                with open(path, encoding="utf-8") as f:
+                    self.texts[path] = f.read()
+            except OSError as error:
+                raise argparse.ArgumentTypeError(str(error))
+        text = io.StringIO(self.texts[path])
+        text.name = path
+        return text
+
+    def forget(self, paths: t.List[str]) -> None:
+        """Drop files which a hook might have fixed in place"""
+        for path in paths:
+            self.texts.pop(path, None)
+
+
+def hook_module(hook: str) -> t.Optional[t.Any]:
+    """Import the module implementing the hook, if it is a Python hook"""
+    name = HOOK_MODULES.get(hook, hook.replace("-", "_"))
+    if name in ["cli", "common"]:
+        return None
+    try:
+        return importlib.import_module(f"latexhooks.{name}")
+    except ImportError:
+        return None
+
+
+def run_hook(
+    module: t.Any, hook: str, args: t.List[str], files: t.List[str], cache: FileCache
+) -> t.Tuple[bool, str]:
+    """Run the hook with the cached texts and return whether it failed and its output
+
+    Hooks taking file objects get the cached texts. Hooks taking file names, which can
+    fix the files in place, read them themselves.
+    """
+    parser = module.build_parser()
+    parser.prog = hook
+    files_action = next(a for a in parser._actions if a.dest == "files")
+    takes_names = files_action.type is None
+    if not takes_names:
+        files_action.type = cache.open
+    output = io.StringIO()
+    failed = False
+    try:
+        with contextlib.redirect_stdout(output), contextlib.redirect_stderr(output):
+            module.run(parser.parse_args(args + files))
+    except SystemExit as error:
+        if isinstance(error.code, str):
+            failed = True
+            print(error.code, file=output)
+        else:
+            failed = bool(error.code)
+    finally:
+        if takes_names:
+            cache.forget(files)
+    return failed, output.getvalue()
+
+
+def run_command(
+    command: str, args: t.List[str], files: t.List[str]
+) -> t.Tuple[bool, str]:
+    """Run a hook which is not written in Python and return whether it failed"""
+    process = subprocess.run(
+        [command] + args + files,
+        stdout=subprocess.PIPE,
+        stderr=subprocess.STDOUT,
+        encoding="utf-8",
+    )
+    return process.returncode != 0, process.stdout
+
+
+def junit_report(results: t.List[t.Tuple[str, t.List[str], bool, str]]) -> str:
+    """
+    Return a JUnit XML report with a test suite per hook and a test case per file
+
+    This is the grouping of `ensure-labels --format=junit`. The output lines starting
+    with the name of a file fail its test case. A failed hook without such lines fails
+    an additional test case named after the hook.
+    """
+    lines = ['<?xml version="1.0" encoding="UTF-8"?>', "<testsuites>"]
+    for hook, files, failed, output in results:
+        suite = quoteattr(f"latex-hooks.{hook}")
+        cases: t.Dict[str, t.List[str]] = {path: [] for path in files}
+        for line in output.splitlines():
+            path = next((p for p in files if line.startswith(f"{p}:")), None)
+            if path is not None:
+                cases[path].append(line)
+        if failed and not any(cases.values()):
+            cases[hook] = output.splitlines()
+        failures = sum(1 for case in cases.values() if failed and case)
+        lines.append(
+            f'  <testsuite name={suite} tests="{len(cases)}" failures="{failures}">'
+        )
+        for path, case in cases.items():
+            lines.append(f"    <testcase classname={suite} name={quoteattr(path)}>")
+            if failed and case:
+                message = quoteattr(f"{hook} failed")
+                report = escape("\n".join(case) + "\n")
+                lines.append(f"      <failure message={message}>{report}</failure>")
+            lines.append("    </testcase>")
+        lines.append("  </testsuite>")
+    lines.append("</testsuites>")
+    return "\n".join(lines)
+
+
+def run(args: argparse.Namespace) -> None:
+    paths = [os.path.normpath(p) for p in project_files(list(args.files))]
+    tex_files = [p for p in paths if p.endswith(TEX_EXTENSIONS)]
+    bib_files = [p for p in paths if p.endswith(".bib")]
+
+    hooks = configured_hooks(args.config)
+    selected = select_rules([hook["id"] for hook in hooks], args)
+    cache = FileCache()
+    results: t.List[t.Tuple[str, t.List[str], bool, str]] = []
+    unsupported: t.List[str] = []
+    not_installed: t.List[str] = []
+    for hook in hooks:
+        if hook["id"] not in selected:
+            continue
+        module = hook_module(hook["id"])
+        command = COMMAND_HOOKS.get(hook["id"])
+        if module is None and command is None:
+            unsupported.append(hook["id"])
+            continue
+        if module is None and shutil.which(command) is None:
+            not_installed.append(command)
+            continue
+        files = tex_files + bib_files if hook["id"] in BIB_HOOKS else tex_files
+        files = hook_files(hook, files)
+        if not files:
+            continue
+        if module is not None:
+            hook_failed, output = run_hook(
+                module, hook["id"], hook["args"], files, cache
+            )
+        else:
+            hook_failed, output = run_command(command, hook["args"], files)
+            cache.forget(files)
+        results.append((hook["id"], files, hook_failed, output))
+
+    failed = [hook for hook, _, hook_failed, _ in results if hook_failed]
+    if args.format == "junit":
+        print(junit_report(results))
+    else:
+        for hook, _, hook_failed, output in results:
+            if hook_failed or output:
+                print(f"{hook}: {'Failed' if hook_failed else 'Passed'}")
+                for line in output.splitlines():
+                    print(f"  {line}" if line else "")
+                print()
+        if unsupported:
+            print(f"Not supported, use pre-commit for: {', '.join(unsupported)}")
+        if not_installed:
+            print(f"Not run, not found on the PATH: {', '.join(not_installed)}")
+        pinned = pinned_rules([hook["id"] for hook in hooks], args)
+        if pinned:
+            print(
+                f"Not run, changed after rule set {args.rule_set}: {', '.join(pinned)}"
+            )
+        print(f"{len(failed)} of {len(results)} checks failed")
+    if failed:
+        sys.exit(1)
+
+
+def add_hook_command(
+    subcommands: t.Any, name: str, hook: str, help: str
+) -> argparse.ArgumentParser:
+    """Add a subcommand running a single hook with its own options"""
+    module = hook_module(hook)
+    parser: argparse.ArgumentParser = subcommands.add_parser(
+        name, parents=[module.build_parser()], add_help=False, help=help
+    )
+    add_rule_selection(parser)
+
+    def run_selected(args: argparse.Namespace) -> None:
+        if hook not in select_rules([hook], args):
+            print(f"Not run, {hook} is not selected")
+            return
+        module.run(args)
+
+    parser.set_defaults(func=run_selected)
+    return parser
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser(prog="latex-hooks")
+    subcommands = parser.add_subparsers(dest="command", metavar="COMMAND")
+    subcommands.required = True
+
+    info_parser = subcommands.add_parser(
+        "info",
+        help="Print what the hooks detect about the LaTeX project",
+    )
+    info_parser.add_argument(
+        "--config",
+        default=".pre-commit-config.yaml",
+        help="pre-commit configuration to read the active checks from",
+    )
+    info_parser.add_argument(
+        "files",
+        metavar="FILE",
+        nargs="*",
+        help="Root documents, by default all in the current directory",
+    )
+    add_rule_selection(info_parser)
+    info_parser.set_defaults(func=info)
+
+    run_parser = subcommands.add_parser(
+        "run",
+        help="Run all enabled hooks in one process and print a combined report",
+    )
+    run_parser.add_argument(
+        "--config",
+        default=".pre-commit-config.yaml",
+        help="pre-commit configuration to read the hooks and their args from",
+    )
+    run_parser.add_argument(
+        "--format",
+        choices=["text", "junit"],
+        default="text",
+        help="Format of the report",
+    )
+    run_parser.add_argument(
+        "files",
+        metavar="FILE",
+        nargs="*",
+        help="Files to check, by default all in the current directory",
+    )
+    add_rule_selection(run_parser)
+    run_parser.set_defaults(func=run)
+
+    add_hook_command(
+        subcommands,
+        "fmt-check",
+        "latexindent-check",
+        help="Check that the files are formatted with latexindent",
+    )
+    add_hook_command(
+        subcommands,
+        "spellcheck",
+        "spellcheck",
+        help="Check the prose of the files for unknown words with hunspell",
+    )
+    add_hook_command(
+        subcommands,
+        "wordcount",
+        "wordcount",
+        help="Count the words per section and check them against their budgets",
+    )
+    return parser
+
+
+def main() -> None:
+    parser = build_parser()
+    args = parser.parse_args()
+    config = getattr(args, "config", ".pre-commit-config.yaml")
+    unknown = unknown_rules(args, known_rules() + active_hooks(config))
+    if unknown:
+        parser.error(f"unknown rules: {', '.join(unknown)}")
+    args.func(args)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,93 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    RE_INLINE_MATH,
+    add_root_argument,
+    blank_spans,
+    check_projects,
+    iter_commands,
+    read_project,
+    report,
+)
+
+TITLE_COMMANDS = [
+    "part",
+    "chapter",
+    "section",
+    "subsection",
+    "subsubsection",
+    "paragraph",
+    "caption",
+    "title",
+]
+
+# Word after a colon, like "Method: the idea". Words like "LaTeX" or "GPU" are skipped.
+RE_AFTER_COLON = re.compile(r":\s+([^\W\d_])([^\W\d_]*)")
+
+
+def search(files: t.List[t.IO[str]], style: str) -> bool:
+    texts = read_project(files)
+
+    # Whether the word is capitalized, file name, text, and offset of the word
+    occurrences: t.List[t.Tuple[bool, str, str, int]] = []
+    for file_name, text in texts:
+        for cmd in iter_commands(text, TITLE_COMMANDS):
+            for arg, offset in zip(cmd.args, cmd.arg_offsets):
+                arg = blank_spans(arg, [m.span() for m in RE_INLINE_MATH.finditer(arg)])
+                for match in RE_AFTER_COLON.finditer(arg):
+                    if any(c.isupper() for c in match[2]):
+                        continue
+                    capitalized = match[1].isupper()
+                    location = (file_name, text, offset + match.start(1))
+                    occurrences.append((capitalized, *location))
+
+    if style == "consistent":
+        capitalized = sum(1 for c, _, _, _ in occurrences if c)
+        style = "upper" if capitalized * 2 >= len(occurrences) else "lower"
+
+    found_error = False
+    for capitalized, file_name, text, offset in occurrences:
+        if capitalized != (style == "upper"):
+            found_error = True
+            expected = "uppercase" if style == "upper" else "lowercase"
+            report(file_name, text, offset, f"Use {expected} after the colon")
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--style",
+        choices=["consistent", "upper", "lower"],
+        default="consistent",
+        help="Capitalization of the word after a colon, consistent uses the majority",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, args.style),
+        "Found inconsistent capitalization after colons",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,171 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    check_projects,
+    is_root,
+    iter_commands,
+    iter_packages,
+    read_project,
+    report,
+    split_list,
+)
+
+# Colors which are always defined by xcolor
+XCOLOR_COLORS = {
+    "red",
+    "green",
+    "blue",
+    "cyan",
+    "magenta",
+    "yellow",
+    "black",
+    "gray",
+    "white",
+    "darkgray",
+    "lightgray",
+    "brown",
+    "lime",
+    "olive",
+    "orange",
+    "pink",
+    "purple",
+    "teal",
+    "violet",
+    # Special names usable in color expressions
+    "none",
+    ".",
+}
+
+# Colors defined by the `dvipsnames` option of xcolor
+DVIPS_COLORS = set(
+    """
+    Apricot Aquamarine Bittersweet Black Blue BlueGreen BlueViolet BrickRed Brown
+    BurntOrange CadetBlue CarnationPink Cerulean CornflowerBlue Cyan Dandelion
+    DarkOrchid Emerald ForestGreen Fuchsia Goldenrod Gray Green GreenYellow JungleGreen
+    Lavender LimeGreen Magenta Mahogany Maroon Melon MidnightBlue Mulberry NavyBlue
+    OliveGreen Orange OrangeRed Orchid Peach Periwinkle PineGreen Plum ProcessBlue
+    Purple RawSienna Red RedOrange RedViolet Rhodamine RoyalBlue RoyalPurple RubineRed
+    Salmon SeaGreen Sepia SkyBlue SpringGreen Tan TealBlue Thistle Turquoise Violet
+    VioletRed White WildStrawberry Yellow YellowGreen YellowOrange
+    """.split()
+)
+
+DEFINING_COMMANDS = ["definecolor", "colorlet", "providecolor"]
+USING_COMMANDS = [
+    "color",
+    "textcolor",
+    "colorbox",
+    "fcolorbox",
+    "pagecolor",
+    "rowcolor",
+    "columncolor",
+    "cellcolor",
+]
+
+# Color keys in the options of TikZ, tcolorbox, and similar packages
+RE_COLOR_KEY = re.compile(
+    r"\b(?:color|fill|draw|text|top color|bottom color|colback|colframe)\s*=\s*"
+    r"([^,\]\}\s][^,\]\}]*)"
+)
+
+
+def color_names(expression: str) -> t.List[str]:
+    """Split a color expression like `red!50!black` into the colors used in it"""
+    names: t.List[str] = []
+    for part in expression.strip().lstrip("-").split("!"):
+        part = part.strip()
+        if part and not re.fullmatch(r"[\d.]+", part):
+            names.append(part)
+    return names
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    texts = read_project(files)
+
+    # Without the root document we cannot know all definitions and usages
+    if not any(is_root(text) for _, text in texts):
+        return False
+
+    known = set(XCOLOR_COLORS)
+    check_undefined = True
+    for _, text in texts:
+        for cmd, package in iter_packages(text):
+            options = split_list(cmd.optional)
+            if package == "xcolor" and "dvipsnames" in options:
+                known |= DVIPS_COLORS
+            # We do not ship the long lists of svg and x11 colors
+            if package == "xcolor" and ("svgnames" in options or "x11names" in options):
+                check_undefined = False
+
+    defined: t.Dict[str, t.Tuple[str, str, int]] = dict()
+    # Color expressions with the file and offset of their usage
+    usages: t.List[t.Tuple[str, str, str, int]] = []
+    for file_name, text in texts:
+        for cmd in iter_commands(text, DEFINING_COMMANDS, nargs=2):
+            if not cmd.args:
+                continue
+            defined.setdefault(cmd.args[0].strip(), (file_name, text, cmd.start))
+            # colorlet defines a color based on other colors
+            if cmd.name == "colorlet" and len(cmd.args) == 2:
+                usages.append((cmd.args[1], file_name, text, cmd.start))
+        for cmd in iter_commands(text, USING_COMMANDS, nargs=2):
+            # Colors with an explicit model, like \color[rgb]{1,0,0}, are not named
+            if cmd.optional:
+                continue
+            args = cmd.args if cmd.name == "fcolorbox" else cmd.args[:1]
+            for arg in args:
+                usages.append((arg, file_name, text, cmd.start))
+        for match in RE_COLOR_KEY.finditer(text):
+            usages.append((match[1], file_name, text, match.start()))
+
+    found_error = False
+    check_unused = True
+    used_names: t.Set[str] = set()
+    for expression, file_name, text, offset in usages:
+        # A macro like \newcommand{\colored}[2]{\textcolor{#1}{#2}} forwards its
+        # parameter, such that any color may be used via the macro
+        if "#" in expression:
+            check_unused = False
+            continue
+        for name in color_names(expression):
+            used_names.add(name)
+            if check_undefined and name not in known and name not in defined:
+                found_error = True
+                message = f"Color '{name}' is used but never defined"
+                report(file_name, text, offset, message)
+    if check_unused:
+        for name, (file_name, text, offset) in defined.items():
+            if name not in used_names:
+                found_error = True
+                message = f"Color '{name}' is defined but never used"
+                report(file_name, text, offset, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(args.files, args.root, search, "Found undefined or unused colors")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
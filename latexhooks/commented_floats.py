@@ -0,0 +1,88 @@
+#!/usr/bin/env python3
+import argparse
+import sys
+import typing as t
+
+from latexhooks.common import RE_BEGIN_END, mask_comments, mask_verbatim, report
+
+FLOAT_ENVIRONMENTS = [
+    "figure",
+    "figure*",
+    "table",
+    "table*",
+    "wrapfigure",
+    "wraptable",
+    "sidewaysfigure",
+    "sidewaystable",
+    "algorithm",
+    "subfigure",
+]
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_verbatim(f.read())
+        masked = mask_comments(text)
+
+        # Match the active \begin and \end, commented ones explain the leftovers
+        stacks: t.Dict[str, t.List[int]] = dict()
+        unmatched_ends: t.List[t.Tuple[str, int]] = []
+        commented: t.List[t.Tuple[str, str, int]] = []
+        for match in RE_BEGIN_END.finditer(text):
+            kind, name = match[1], match[2].strip()
+            if name not in FLOAT_ENVIRONMENTS:
+                continue
+            if masked[match.start()] != "\\":
+                commented.append((kind, name, match.start()))
+            elif kind == "begin":
+                stacks.setdefault(name, list()).append(match.start())
+            elif stacks.get(name):
+                stacks[name].pop()
+            else:
+                unmatched_ends.append((name, match.start()))
+
+        for name, begins in stacks.items():
+            for begin in begins:
+                if any(
+                    k == "end" and n == name and offset > begin
+                    for k, n, offset in commented
+                ):
+                    found_error = True
+                    message = f"\\begin{{{name}}} is active, but \\end is commented out"
+                    report(f.name, text, begin, message)
+        for name, end in unmatched_ends:
+            if any(
+                k == "begin" and n == name and offset < end
+                for k, n, offset in commented
+            ):
+                found_error = True
+                message = f"\\end{{{name}}} is active, but \\begin is commented out"
+                report(f.name, text, end, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files)
+    if found_error:
+        sys.exit("Found partially commented out floats")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
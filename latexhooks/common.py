@@ -0,0 +1,1046 @@
+"""Helpers shared by the hooks, mostly for scanning LaTeX source."""
+import argparse
+import contextlib
+import os
+import re
+import subprocess
+import sys
+import typing as t
+from dataclasses import dataclass, field
+
+RE_BEGIN_END = re.compile(r"\\(begin|end)\s*\{([^\{\}]+)\}")
+RE_DEFINITION = re.compile(
+    r"\\(?:(?:re)?newcommand|providecommand|DeclareRobustCommand)\*?"
+    r"|\\[gex]?def\s*\\([a-zA-Z@]+)((?:\s*#\d)*)"
+)
+
+# Commands referencing labels. Their argument can be a comma separated list of labels.
+REF_COMMANDS = [
+    "ref",
+    "eqref",
+    "pageref",
+    "autoref",
+    "nameref",
+    "vref",
+    "cref",
+    "Cref",
+    "cpageref",
+    "Cpageref",
+    "labelcref",
+    "namecref",
+    "nameCref",
+]
+
+# Commands citing bibliography entries. Their argument is a comma separated list of keys.
+CITE_COMMANDS = [
+    "cite",
+    "Cite",
+    "citep",
+    "citet",
+    "citealp",
+    "citealt",
+    "citeauthor",
+    "citeyear",
+    "autocite",
+    "Autocite",
+    "parencite",
+    "Parencite",
+    "textcite",
+    "Textcite",
+    "footcite",
+    "smartcite",
+    "nocite",
+]
+
+
+@dataclass
+class Command:
+    """A LaTeX command together with its parsed arguments"""
+
+    name: str
+    """ Name of the command, without the leading backslash """
+    start: int
+    """ Offset of the backslash """
+    end: int
+    """ Offset after the last parsed argument """
+    star: bool = False
+    """ The command is the starred variant """
+    optional: t.List[str] = field(default_factory=list)
+    """ Content of the optional arguments, i.e., `[...]` """
+    args: t.List[str] = field(default_factory=list)
+    """ Content of the required arguments, i.e., `{...}` """
+    arg_offsets: t.List[int] = field(default_factory=list)
+    """ Offset of the content of each required argument """
+
+
+@dataclass
+class Environment:
+    """A matched `\\begin{...}` / `\\end{...}` pair"""
+
+    name: str
+    """ Name of the environment, e.g., "figure" """
+    start: int
+    """ Offset of the `\\begin` """
+    end: int
+    """ Offset after the `\\end{...}` """
+    body_start: int
+    """ Offset after the `\\begin{...}` """
+    body_end: int
+    """ Offset of the `\\end` """
+
+
+@dataclass
+class Definition:
+    """A macro defined via `\\newcommand` and friends or `\\def`"""
+
+    name: str
+    """ Name of the macro, without the leading backslash """
+    start: int
+    """ Offset of the defining command """
+    end: int
+    """ Offset after the body of the definition """
+    nargs: int
+    """ Number of arguments, including the optional one """
+    default: t.Optional[str]
+    """ Default value of the optional first argument, if any """
+    body: str
+    """ Replacement text of the macro """
+
+
+@dataclass
+class Section:
+    """A part of a file belonging to a section"""
+
+    title: str
+    """ Title of the section, empty before the first section """
+    file_name: str
+    text: str
+    """ Content of the whole file """
+    start: int
+    """ Offset of the sectioning command """
+    end: int
+    """ Offset of the next sectioning command or the end of the file """
+
+
+def mask_comments(text: str) -> str:
+    """
+    Replace all comments with spaces.
+
+    The returned string has the same length as `text` and all newlines are kept, such that
+    offsets and line numbers stay valid.
+    """
+
+    def blank(match: t.Match[str]) -> str:
+        return match[1] + " " * len(match[2])
+
+    return re.sub(r"(?<!\\)((?:\\\\)*)(%[^\n]*)", blank, text)
+
+
+def line_number(text: str, offset: int) -> int:
+    """Convert an offset into `text` into a 1-based line number"""
+    return text.count("\n", 0, offset) + 1
+
+
+def parse_group(
+    text: str, pos: int, open: str = "{", close: str = "}"
+) -> t.Optional[t.Tuple[int, int]]:
+    """
+    Parse a balanced group starting at `pos`, skipping leading whitespace.
+
+    Returns the start and end offset of the group content, or `None` if there is no group.
+    The closing delimiter is located at the returned end offset.
+    """
+    while pos < len(text) and text[pos] in " \t\n":
+        # A blank line ends the command
+        if text.startswith("\n\n", pos):
+            return None
+        pos += 1
+    if pos >= len(text) or text[pos] != open:
+        return None
+
+    depth = 0
+    idx = pos
+    while idx < len(text):
+        c = text[idx]
+        if c == "\\":
+            idx += 2
+            continue
+        if c == open:
+            depth += 1
+        elif c == close:
+            depth -= 1
+            if depth == 0:
+                return (pos + 1, idx)
+        idx += 1
+    return None
+
+
+def iter_commands(
+    text: str, names: t.Iterable[str], nargs: int = 1
+) -> t.Iterator[Command]:
+    """
+    Find all usages of the commands in `names` and parse up to `nargs` required arguments.
+
+    Optional arguments are only parsed before the first required argument.
+    """
+    pattern = r"\\(" + "|".join(re.escape(n) for n in names) + r")(?![a-zA-Z@])(\*?)"
+    for match in re.finditer(pattern, text):
+        cmd = Command(
+            name=match[1], start=match.start(), end=match.end(), star=bool(match[2])
+        )
+        while len(cmd.args) < nargs:
+            if not cmd.args:
+                opt = parse_group(text, cmd.end, "[", "]")
+                if opt is not None:
+                    cmd.optional.append(text[opt[0] : opt[1]])
+                    cmd.end = opt[1] + 1
+                    continue
+            arg = parse_group(text, cmd.end)
+            if arg is None:
+                break
+            cmd.args.append(text[arg[0] : arg[1]])
+            cmd.arg_offsets.append(arg[0])
+            cmd.end = arg[1] + 1
+        yield cmd
+
+
+def iter_definitions(text: str) -> t.Iterator[Definition]:
+    """Find all macro definitions using `\\newcommand` and friends or `\\def`"""
+    for match in RE_DEFINITION.finditer(text):
+        pos = match.end()
+        if match[1] is not None:
+            # \def\name#1#2{body}
+            name = match[1]
+            nargs = match[2].count("#")
+            default = None
+        else:
+            group = parse_group(text, pos)
+            if group is not None:
+                name = text[group[0] : group[1]].strip().lstrip("\\")
+                pos = group[1] + 1
+            else:
+                # \newcommand\name without braces around the name
+                name_match = re.match(r"\s*\\([a-zA-Z@]+)", text[pos:])
+                if name_match is None:
+                    continue
+                name = name_match[1]
+                pos += name_match.end()
+            nargs = 0
+            default = None
+            group = parse_group(text, pos, "[", "]")
+            if group is not None:
+                count = text[group[0] : group[1]].strip()
+                nargs = int(count) if count.isdigit() else 0
+                pos = group[1] + 1
+                group = parse_group(text, pos, "[", "]")
+                if group is not None:
+                    default = text[group[0] : group[1]]
+                    pos = group[1] + 1
+
+        group = parse_group(text, pos)
+        if group is None:
+            continue
+        yield Definition(
+            name=name,
+            start=match.start(),
+            end=group[1] + 1,
+            nargs=nargs,
+            default=default,
+            body=text[group[0] : group[1]],
+        )
+
+
+def split_sections(
+    texts: t.List[t.Tuple[str, str]], commands: t.List[str]
+) -> t.List[Section]:
+    """
+    Split the texts into sections, each starting at one of the sectioning `commands`.
+
+    Text before the first heading of a file belongs to the previous section. Text before
+    the first heading of the project is part of a section with an empty title.
+    """
+    sections: t.List[Section] = []
+    for file_name, text in texts:
+        headings = list(iter_commands(text, commands))
+        end = headings[0].start if headings else len(text)
+        title = sections[-1].title if sections else ""
+        if end > 0:
+            sections.append(Section(title, file_name, text, 0, end))
+        for idx, heading in enumerate(headings):
+            end = headings[idx + 1].start if idx + 1 < len(headings) else len(text)
+            title = " ".join(heading.args[0].split()) if heading.args else ""
+            sections.append(Section(title, file_name, text, heading.start, end))
+    return sections
+
+
+def split_list(values: t.Iterable[str]) -> t.List[str]:
+    """Split comma separated lists, like package options, into their stripped items"""
+    return [v.strip() for value in values for v in value.split(",") if v.strip()]
+
+
+def is_root(text: str) -> bool:
+    """The text is a root document, i.e., it contains a `\\documentclass`"""
+    return any(True for _ in iter_commands(text, ["documentclass"]))
+
+
+def iter_packages(text: str) -> t.Iterator[t.Tuple[Command, str]]:
+    """Find all packages loaded via `\\usepackage` or `\\RequirePackage`"""
+    for cmd in iter_commands(text, ["usepackage", "RequirePackage"]):
+        if cmd.args:
+            for package in split_list(cmd.args):
+                yield cmd, package
+
+
+def iter_environments(
+    text: str, names: t.Optional[t.Iterable[str]] = None
+) -> t.Iterator[Environment]:
+    """
+    Find all environments, optionally restricted to those named in `names`.
+
+    Environments are yielded in the order in which they are closed, so inner environments
+    come before the outer ones. Unbalanced `\\begin` or `\\end` are ignored.
+    """
+    wanted = None if names is None else set(names)
+    stack: t.List[t.Tuple[str, int, int]] = []
+    for match in RE_BEGIN_END.finditer(text):
+        kind, name = match[1], match[2].strip()
+        if kind == "begin":
+            stack.append((name, match.start(), match.end()))
+            continue
+        # Find the closest matching \begin
+        for idx in range(len(stack) - 1, -1, -1):
+            if stack[idx][0] == name:
+                _, start, body_start = stack[idx]
+                del stack[idx:]
+                if wanted is None or name in wanted:
+                    yield Environment(
+                        name=name,
+                        start=start,
+                        end=match.end(),
+                        body_start=body_start,
+                        body_end=match.start(),
+                    )
+                break
+
+
+def brace_depth(text: str, offset: int) -> int:
+    """Return the number of groups open at `offset`"""
+    stripped = re.sub(r"\\.", "", text[:offset])
+    return stripped.count("{") - stripped.count("}")
+
+
+def is_local(text: str, offset: int) -> bool:
+    """Whether assignments at `offset` are local to a group or an environment"""
+    in_environment = any(
+        env.body_start <= offset < env.body_end
+        for env in iter_environments(text)
+        if env.name != "document"
+    )
+    return in_environment or brace_depth(text, offset) > 0
+
+
+def read_wordlist(path: str) -> t.List[str]:
+    """Read a list of words, one per line. Lines starting with `#` are ignored."""
+    with open(path, encoding="utf-8") as f:
+        return [line.strip() for line in f if line.strip() and not line.startswith("#")]
+
+
+RE_DIRECTIVE = re.compile(
+    r"(?:^|[^\\])%[ \t]*latex-hooks:[ \t]*(off|on)\b"
+    r"((?:[ \t]*[\w-]+)(?:[ \t]*,[ \t]*[\w-]+)*)?",
+    re.M,
+)
+
+
+@dataclass
+class DisabledRegion:
+    """A region between `% latex-hooks: off` and `% latex-hooks: on`"""
+
+    start: int
+    end: int
+    rule: t.Optional[str]
+    """ The only rule disabled in the region, all rules if `None` """
+
+
+def disabled_regions(text: str, hook: str) -> t.List[DisabledRegion]:
+    """
+    Find the regions in which the rules of `hook` are disabled.
+
+    Directives without rules or naming the `hook` disable all of its rules, otherwise
+    only the named rules. Regions without a closing directive end with the text. This
+    matches the directives of `ensure-labels`.
+    """
+    regions: t.List[DisabledRegion] = []
+    all_off: t.Optional[int] = None
+    rules_off: t.Dict[str, int] = dict()
+    for match in RE_DIRECTIVE.finditer(mask_verbatim(text)):
+        offset = match.start(1)
+        is_off = match[1] == "off"
+        rules = split_list([match[2] or ""])
+        if not rules or hook in rules:
+            if is_off:
+                all_off = offset if all_off is None else all_off
+                continue
+            if all_off is not None:
+                regions.append(DisabledRegion(all_off, offset, None))
+                all_off = None
+            regions += [DisabledRegion(s, offset, r) for r, s in rules_off.items()]
+            rules_off.clear()
+            continue
+        for rule in rules:
+            if is_off:
+                rules_off.setdefault(rule, offset)
+            elif rule in rules_off:
+                regions.append(DisabledRegion(rules_off.pop(rule), offset, rule))
+    if all_off is not None:
+        regions.append(DisabledRegion(all_off, len(text), None))
+    regions += [DisabledRegion(s, len(text), r) for r, s in rules_off.items()]
+    return sorted(regions, key=lambda region: region.start)
+
+
+def is_disabled(regions: t.List[DisabledRegion], offset: int, rule: str) -> bool:
+    return any(
+        region.start <= offset < region.end and region.rule in (None, rule)
+        for region in regions
+    )
+
+
+def line_offset(text: str, line: int, column: int = 1) -> int:
+    """Return the offset of a 1-based line and column, the inverse of `line_number`"""
+    start = 0
+    for _ in range(line - 1):
+        newline = text.find("\n", start)
+        if newline == -1:
+            return len(text)
+        start = newline + 1
+    end = text.find("\n", start)
+    if end == -1:
+        end = len(text)
+    # Columns past the end of the line stay on it
+    return min(start + column - 1, end)
+
+
+def report(file_name: str, text: str, offset: int, message: str) -> None:
+    """Print a finding in the `file:line message` format"""
+    print(f"{file_name}:{line_number(text, offset)} {message}")
+
+
+def current_branch() -> t.Optional[str]:
+    """Return the name of the checked out git branch, if any"""
+    try:
+        result = subprocess.run(
+            ["git", "rev-parse", "--abbrev-ref", "HEAD"],
+            capture_output=True,
+            encoding="utf-8",
+            check=True,
+        )
+    except (FileNotFoundError, subprocess.CalledProcessError):
+        return None
+    return result.stdout.strip()
+
+
+INPUT_COMMANDS = ["input", "include", "subfile"]
+
+
+def resolve_input(path: str) -> t.Optional[str]:
+    """Normalize the path of an included file, adding `.tex` if necessary, if it exists"""
+    path = os.path.normpath(path)
+    if not os.path.isfile(path) and os.path.isfile(path + ".tex"):
+        path += ".tex"
+    return path if os.path.isfile(path) else None
+
+
+def resolve_inputs(path: str) -> t.List[str]:
+    """
+    Return `path` and all files it includes, recursively, in document order.
+
+    Files included via `\\input`, `\\include`, and `\\subfile` are resolved relative to the
+    directory of `path`, as LaTeX does for the root document. Missing files are skipped.
+    """
+    directory = os.path.dirname(path)
+    result: t.List[str] = []
+
+    def visit(current: str) -> None:
+        if current in result:
+            return
+        result.append(current)
+        text = read_masked(current)
+        for cmd in iter_commands(text, INPUT_COMMANDS):
+            if not cmd.args:
+                continue
+            included = resolve_input(os.path.join(directory, cmd.args[0].strip()))
+            if included is not None:
+                visit(included)
+
+    start = resolve_input(path)
+    if start is not None:
+        visit(start)
+    return result
+
+
+def read_project(files: t.List[t.IO[str]]) -> t.List[t.Tuple[str, str]]:
+    """
+    Read the files together with all files included by them.
+
+    This allows project wide checks, even if only the root document is passed. Returns the
+    file names and the file contents with masked comments and verbatim content.
+    """
+    names: t.List[str] = []
+    for f in files:
+        for name in resolve_inputs(f.name):
+            if name not in names:
+                names.append(name)
+
+    return [(name, read_masked(name)) for name in names]
+
+
+def document_body(files: t.List[t.IO[str]]) -> t.Dict[str, t.Tuple[int, str]]:
+    """
+    Find the document body of the roots among `files` and the files they include.
+
+    Maps each file to the offset where its body starts and the file including it.
+    Roots start at `\\begin{document}` and files included into the body at 0. Files
+    included into the preamble are missing.
+    """
+    body: t.Dict[str, t.Tuple[int, str]] = dict()
+    for f in files:
+        root = resolve_input(f.name)
+        if root is None or not is_root(read_masked(root)):
+            continue
+        directory = os.path.dirname(root)
+        begin_document = read_masked(root).find("\\begin{document}")
+        if begin_document != -1:
+            body.setdefault(root, (begin_document, root))
+        # Included files belong to the preamble or the body, just like their children
+        pending = [(root, True)]
+        visited: t.Set[str] = set()
+        while pending:
+            current, in_preamble = pending.pop()
+            if current in visited:
+                continue
+            visited.add(current)
+            for cmd in iter_commands(read_masked(current), INPUT_COMMANDS):
+                if not cmd.args:
+                    continue
+                included = resolve_input(os.path.join(directory, cmd.args[0].strip()))
+                if included is None:
+                    continue
+                before_body = begin_document == -1 or cmd.start < begin_document
+                child_in_preamble = in_preamble and (current != root or before_body)
+                if not child_in_preamble:
+                    body.setdefault(included, (0, current))
+                pending.append((included, child_in_preamble))
+    return body
+
+
+# Files with masked comments, shared between all roots checked in one run
+_MASKED_FILES: t.Dict[str, str] = dict()
+
+
+def read_masked(path: str) -> str:
+    """Read a file and mask its comments and verbatim content, caching the result"""
+    if path not in _MASKED_FILES:
+        with open(path, encoding="utf-8") as f:
+            _MASKED_FILES[path] = mask_source(f.read())
+    return _MASKED_FILES[path]
+
+
+def add_root_argument(parser: argparse.ArgumentParser) -> None:
+    parser.add_argument(
+        "--root",
+        action="append",
+        metavar="FILE",
+        help="Root document to check separately. Can be given multiple times.",
+        default=list(),
+    )
+
+
+@contextlib.contextmanager
+def split_roots(
+    files: t.List[t.IO[str]], roots: t.List[str]
+) -> t.Iterator[t.List[t.List[t.IO[str]]]]:
+    """
+    Split the files into the projects to check separately.
+
+    Without `roots` all files form a single project. Otherwise each root is its own project,
+    which `read_project` extends by the included files. The remaining `.tex` files, which no
+    root includes, form another project. The roots are opened until the context is left.
+    """
+    if not roots:
+        yield [files]
+        return
+    with contextlib.ExitStack() as stack:
+        projects: t.List[t.List[t.IO[str]]] = []
+        included: t.Set[str] = set()
+        for root in roots:
+            included.update(resolve_inputs(root))
+            projects.append([stack.enter_context(open(root, encoding="utf-8"))])
+        rest = [
+            f
+            for f in files
+            if f.name.endswith(".tex") and os.path.normpath(f.name) not in included
+        ]
+        if rest:
+            projects.append(rest)
+        yield projects
+
+
+def check_projects(
+    files: t.List[t.IO[str]],
+    roots: t.List[str],
+    search: t.Callable[[t.List[t.IO[str]]], bool],
+    message: str,
+) -> None:
+    """
+    Run `search` on every project of `split_roots` and exit with `message` on a finding.
+
+    This is the `run` of the project wide hooks, which take the `--root` argument.
+    """
+    found_error = False
+    with split_roots(sorted(files, key=lambda f: f.name), roots) as projects:
+        for project in projects:
+            if search(project):
+                found_error = True
+    if found_error:
+        sys.exit(message)
+
+
+RE_BIB_ENTRY = re.compile(r"@\s*([a-zA-Z]+)\s*[\{\(]\s*([^\s,\{\}\(\)]+)\s*,")
+
+
+def iter_bib_keys(text: str) -> t.Iterator[t.Tuple[str, int]]:
+    """Find the keys of all entries in a `.bib` file together with their offset"""
+    for match in RE_BIB_ENTRY.finditer(text):
+        if match[1].lower() not in ("string", "comment", "preamble"):
+            yield match[2], match.start(2)
+
+
+def bibliography_files(file_name: str, text: str) -> t.List[str]:
+    """Return the existing `.bib` files of `\\bibliography` or `\\addbibresource`"""
+    directory = os.path.dirname(file_name)
+    paths: t.List[str] = []
+    for cmd in iter_commands(text, ["bibliography", "addbibresource"]):
+        for resource in split_list(cmd.args):
+            path = os.path.normpath(os.path.join(directory, resource))
+            if not path.endswith(".bib"):
+                path += ".bib"
+            if os.path.isfile(path) and path not in paths:
+                paths.append(path)
+    return paths
+
+
+# Citation commands provided by only one of the bibliography packages
+BACKEND_COMMANDS = {
+    "natbib": ["citet", "Citet", "citep", "Citep", "citealp", "citealt", "citenum"],
+    "biblatex": [
+        "textcite",
+        "Textcite",
+        "parencite",
+        "Parencite",
+        "autocite",
+        "Autocite",
+        "footcite",
+        "smartcite",
+        "fullcite",
+    ],
+}
+
+
+def citation_backend(texts: t.List[t.Tuple[str, str]]) -> t.Optional[str]:
+    """Return the bibliography package loaded by the project, natbib or biblatex"""
+    for _, text in texts:
+        for _, package in iter_packages(text):
+            if package in BACKEND_COMMANDS:
+                return package
+    return None
+
+
+RE_MAGIC_COMMENT = re.compile(
+    r"^[ \t]*%[ \t]*!\s*TeX\s+([\w-]+)\s*=[ \t]*(.*?)\s*$", re.I | re.M
+)
+
+# Packages which only work with specific engines
+ENGINE_PACKAGES = {
+    "fontspec": ["lualatex", "xelatex"],
+    "unicode-math": ["lualatex", "xelatex"],
+    "polyglossia": ["lualatex", "xelatex"],
+    "luacode": ["lualatex"],
+    "luatexbase": ["lualatex"],
+    "luaotfload": ["lualatex"],
+    "xltxtra": ["xelatex"],
+    "xunicode": ["xelatex"],
+    "inputenc": ["pdflatex", "latex"],
+}
+
+
+def magic_comments(text: str) -> t.Dict[str, str]:
+    """
+    Parse magic comments like `% !TeX spellcheck = en_GB`, as used by TeXstudio.
+
+    The keys are lowercase, e.g., `spellcheck`, `program`, or `root`. The first
+    occurrence of each key wins.
+    """
+    comments: t.Dict[str, str] = dict()
+    for match in RE_MAGIC_COMMENT.finditer(text):
+        comments.setdefault(match[1].lower(), match[2])
+    return comments
+
+
+def magic_program(text: str) -> t.Optional[str]:
+    """Return the engine selected via `% !TeX program` or `% !TeX TS-program`"""
+    comments = magic_comments(text)
+    program = comments.get("program") or comments.get("ts-program")
+    return program.lower() if program else None
+
+
+# Environments whose content is never prose
+VERBATIM_ENVIRONMENTS = ["verbatim", "Verbatim", "lstlisting", "minted", "comment"]
+MATH_ENVIRONMENTS = [
+    "equation",
+    "equation*",
+    "align",
+    "align*",
+    "alignat",
+    "alignat*",
+    "flalign",
+    "flalign*",
+    "gather",
+    "gather*",
+    "multline",
+    "multline*",
+    "eqnarray",
+    "eqnarray*",
+    "displaymath",
+    "math",
+]
+NON_PROSE_ENVIRONMENTS = VERBATIM_ENVIRONMENTS + MATH_ENVIRONMENTS + ["tikzpicture"]
+
+# Label prefixes of common theorem-like environments
+THEOREM_PREFIXES = {
+    "theorem": "thm",
+    "lemma": "lem",
+    "corollary": "cor",
+    "proposition": "prop",
+    "definition": "def",
+    "conjecture": "conj",
+    "remark": "rem",
+    "example": "ex",
+    "assumption": "asm",
+}
+
+# Commands whose first argument is never prose
+NON_PROSE_COMMANDS = REF_COMMANDS + CITE_COMMANDS + [
+    "label",
+    "usepackage",
+    "RequirePackage",
+    "documentclass",
+    "usetikzlibrary",
+    "input",
+    "include",
+    "subfile",
+    "includegraphics",
+    "url",
+    "path",
+    "bibliography",
+    "bibliographystyle",
+    "addbibresource",
+    "selectlanguage",
+    "foreignlanguage",
+    "newcommand",
+    "renewcommand",
+    "providecommand",
+    "setlength",
+    "hspace",
+    "vspace",
+    "begin",
+    "end",
+]
+
+# Units which siunitx can typeset, as written manually
+UNITS = [
+    "ns",
+    "us",
+    "ms",
+    "s",
+    "min",
+    "h",
+    "B",
+    "KB",
+    "kB",
+    "MB",
+    "GB",
+    "TB",
+    "KiB",
+    "MiB",
+    "GiB",
+    "Hz",
+    "kHz",
+    "MHz",
+    "GHz",
+    "mm",
+    "cm",
+    "m",
+    "km",
+    "g",
+    "kg",
+    "W",
+    "kW",
+    "V",
+    "mA",
+    "A",
+]
+
+RE_INLINE_MATH = re.compile(
+    r"\$\$.*?\$\$|(?<!\\)\$.*?(?<!\\)\$|\\\(.*?\\\)|\\\[.*?\\\]", re.DOTALL
+)
+RE_ENVIRONMENT_OPTIONS = re.compile(r"\\begin\s*\{[^\{\}]*\}\s*\[[^\]]*\]")
+# A comment, the beginning of a verbatim-like environment, or any other command
+RE_VERBATIM_TOKEN = re.compile(
+    r"%|\\(?:begin\s*\{((?:"
+    + "|".join(VERBATIM_ENVIRONMENTS)
+    + r")\*?)\}|[a-zA-Z@]+\*?|.)",
+    re.DOTALL,
+)
+# Inline verbatim commands up to their delimiter, e.g., `\verb|x|` or `\mintinline{c}{x}`
+RE_INLINE_VERBATIM = re.compile(
+    r"\\(?:verb\*?|lstinline(?:\[[^\]\n]*\])?|mintinline(?:\[[^\]\n]*\])?\{[^\{\}\n]*\})"
+)
+RE_COMMAND = re.compile(r"\\(?:[a-zA-Z@]+\*?|.)")
+RE_SENTENCE_END = re.compile(r"[.!?]+(?=\s|$)")
+# Abbreviations whose dots do not end a sentence
+RE_ABBREVIATION = re.compile(r"\b(?:e\.\s?g|i\.\s?e|et al|etc|cf|vs|Fig|Sec|Eq)\.")
+
+
+def count_sentences(prose: str) -> int:
+    """Count the sentences in prose, as returned by `mask_non_prose`"""
+    return len(RE_SENTENCE_END.findall(RE_ABBREVIATION.sub("", prose)))
+
+
+def blank_spans(text: str, spans: t.Iterable[t.Tuple[int, int]]) -> str:
+    """Replace all characters in the spans with spaces, except for newlines"""
+    chars = list(text)
+    for start, end in spans:
+        for idx in range(start, end):
+            if chars[idx] != "\n":
+                chars[idx] = " "
+    return "".join(chars)
+
+
+def inline_verbatim(text: str, pos: int) -> t.Optional[t.Tuple[int, int]]:
+    """
+    Return the span of the content of the inline verbatim command at `pos`.
+
+    The content of `\\verb` ends at the next occurrence of its delimiter, the one of
+    `\\lstinline` and `\\mintinline` may also be enclosed in braces. Unclosed content and
+    content spanning several lines are not verbatim.
+    """
+    match = RE_INLINE_VERBATIM.match(text, pos)
+    if match is None or match.end() >= len(text):
+        return None
+    delimiter = text[match.end()]
+    if delimiter.isalpha() or delimiter.isspace():
+        return None
+    if delimiter == "{" and not match[0].startswith("\\verb"):
+        delimiter = "}"
+    end = text.find(delimiter, match.end() + 1)
+    if end == -1 or "\n" in text[match.end() : end]:
+        return None
+    return match.end() + 1, end
+
+
+def verbatim_spans(text: str) -> t.List[t.Tuple[int, int]]:
+    """
+    Return the spans of the content of verbatim-like environments and inline verbatim.
+
+    Environments starting in a comment are ignored. The `ensure-labels` hook masks the
+    same content.
+    """
+    spans: t.List[t.Tuple[int, int]] = []
+    pos = 0
+    while True:
+        match = RE_VERBATIM_TOKEN.search(text, pos)
+        if match is None:
+            break
+        pos = match.end()
+        if match[0] == "%":
+            end = text.find("\n", pos)
+            pos = len(text) if end == -1 else end
+        elif match[1]:
+            end = text.find(f"\\end{{{match[1]}}}", pos)
+            spans.append((pos, len(text) if end == -1 else end))
+            pos = spans[-1][1]
+        else:
+            span = inline_verbatim(text, match.start())
+            if span is not None:
+                spans.append(span)
+                # Skip the closing delimiter
+                pos = span[1] + 1
+    return spans
+
+
+def mask_verbatim(text: str) -> str:
+    """
+    Replace all verbatim content, as found by `verbatim_spans`, with spaces.
+
+    The returned string has the same length as `text` and all newlines are kept, such
+    that offsets and line numbers stay valid.
+    """
+    return blank_spans(text, verbatim_spans(text))
+
+
+def mask_source(text: str) -> str:
+    """Mask the comments and all verbatim content, as all hooks do"""
+    return mask_comments(mask_verbatim(text))
+
+
+def mask_non_prose(text: str) -> str:
+    """
+    Replace everything except prose with spaces.
+
+    This masks comments, math, all verbatim content, commands, and the arguments of commands
+    which never contain prose, like `\\label` or `\\cite`. The returned string has the same
+    length as `text` such that offsets and line numbers stay valid.
+    """
+    text = mask_source(text)
+    spans = [
+        (env.start, env.end) for env in iter_environments(text, NON_PROSE_ENVIRONMENTS)
+    ]
+    spans += [m.span() for m in RE_INLINE_MATH.finditer(text)]
+    spans += [m.span() for m in RE_ENVIRONMENT_OPTIONS.finditer(text)]
+    spans += [(cmd.start, cmd.end) for cmd in iter_commands(text, NON_PROSE_COMMANDS)]
+    text = blank_spans(text, spans)
+    text = RE_COMMAND.sub(lambda m: " " * len(m[0]), text)
+    return re.sub(r"[\{\}~]", " ", text)
+
+
+@dataclass
+class TableCell:
+    """A single cell of a tabular environment"""
+
+    row: int
+    """ Index of the row, starting at 0 """
+    column: int
+    """ Index of the first column spanned by the cell, starting at 0 """
+    start: int
+    """ Offset of the content of the cell """
+    content: str
+    """ Content of the cell without surrounding whitespace and table rules """
+
+
+TABULAR_ENVIRONMENTS = ["tabular", "tabular*", "tabularx", "longtable", "xltabular"]
+
+RE_TABLE_RULE = re.compile(
+    r"\\(?:toprule|midrule|bottomrule)(?:\s*\[[^\]]*\])?"
+    r"|\\(?:hline|endhead|endfirsthead|endfoot|endlastfoot)(?![a-zA-Z@])"
+    r"|\\(?:cmidrule|cline)\s*(?:\([^\)]*\))?\s*\{[^\}]*\}"
+)
+RE_ROW_SPACING = re.compile(r"\s*\[[^\]]*\]")
+
+
+def table_columns(text: str, env: Environment) -> t.Tuple[t.List[str], int]:
+    """
+    Parse the column specification of a tabular environment.
+
+    Returns the type of each column, like `l` or `S`, and the offset where the first row
+    starts.
+    """
+    pos = env.body_start
+    if env.name in ("longtable", "xltabular"):
+        position = parse_group(text, pos, "[", "]")
+        if position is not None:
+            pos = position[1] + 1
+    if env.name in ("tabular*", "tabularx", "xltabular"):
+        width = parse_group(text, pos)
+        if width is not None:
+            pos = width[1] + 1
+    spec = parse_group(text, pos)
+    if spec is None:
+        return [], pos
+    return parse_column_spec(text[spec[0] : spec[1]]), spec[1] + 1
+
+
+def parse_column_spec(spec: str) -> t.List[str]:
+    """Expand a column specification, like `l*{2}{S[round-mode=places]}`, into types"""
+    columns: t.List[str] = []
+    pos = 0
+    while pos < len(spec):
+        c = spec[pos]
+        pos += 1
+        if c in "@!><":
+            # Inter-column material and code inserted before or after cells
+            group = parse_group(spec, pos)
+            pos = group[1] + 1 if group is not None else pos
+        elif c == "*":
+            count = parse_group(spec, pos)
+            repeated = parse_group(spec, count[1] + 1) if count is not None else None
+            if count is None or repeated is None:
+                break
+            number = spec[count[0] : count[1]].strip()
+            sub = parse_column_spec(spec[repeated[0] : repeated[1]])
+            columns += sub * (int(number) if number.isdigit() else 1)
+            pos = repeated[1] + 1
+        elif c.isalpha():
+            columns.append(c)
+            # Options of S columns and widths of p, m, b, and X columns
+            for open, close in (("[", "]"), ("{", "}")):
+                group = parse_group(spec, pos, open, close)
+                if group is not None:
+                    pos = group[1] + 1
+    return columns
+
+
+def iter_table_cells(text: str, start: int, end: int) -> t.Iterator[TableCell]:
+    """
+    Split the rows of a table between `start` and `end` into cells.
+
+    Cells are separated by `&` and rows by `\\\\`, both only outside of groups. Table rules
+    like `\\midrule` are not part of the cell content. `\\multicolumn` cells span the given
+    number of columns.
+    """
+    rules = [m.span() for m in RE_TABLE_RULE.finditer(text, start, end)]
+    text = blank_spans(text, rules)
+    row = 0
+    column = 0
+    cell_start = start
+    depth = 0
+    pos = start
+
+    def make_cell() -> TableCell:
+        raw = text[cell_start:pos]
+        offset = cell_start + len(raw) - len(raw.lstrip())
+        return TableCell(row=row, column=column, start=offset, content=raw.strip())
+
+    while pos < end:
+        c = text[pos]
+        if c == "\\" and text.startswith("\\\\", pos) and depth == 0:
+            yield make_cell()
+            row += 1
+            column = 0
+            pos += 2
+            spacing = RE_ROW_SPACING.match(text, pos, end)
+            if spacing is not None:
+                pos = spacing.end()
+            cell_start = pos
+            continue
+        if c == "\\":
+            pos += 2
+            continue
+        if c == "{":
+            depth += 1
+        elif c == "}":
+            depth -= 1
+        elif c == "&" and depth == 0:
+            cell = make_cell()
+            yield cell
+            column += multicolumn_span(cell.content)
+            cell_start = pos + 1
+        pos += 1
+    cell = make_cell()
+    if cell.content:
+        yield cell
+
+
+def multicolumn_span(content: str) -> int:
+    """Return the number of columns spanned by a cell"""
+    match = re.match(r"\\multicolumn\s*\{\s*(\d+)\s*\}", content)
+    return int(match[1]) if match else 1
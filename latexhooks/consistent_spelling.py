@@ -5,6 +5,8 @@ import sys
 import typing as t
 from dataclasses import dataclass
 
+from latexhooks.common import magic_comments, mask_non_prose, report
+
 RED = "\u001b[31m"
 GREEN = "\u001b[32m"
 YELLOW = "\u001b[33m"
@@ -14,6 +16,68 @@ CYAN = "\u001b[36m"
 COLOR_RESET = "\u001b[0m"
 ID2COLOR = {0: RED, 1: GREEN, 2: YELLOW, 3: BLUE, 4: MAGENTA, 5: CYAN}
 
+# Word stems spelled -or in American and -our in British English
+OUR_STEMS = ["behavio", "colo", "favo", "flavo", "hono", "humo", "labo", "neighbo"]
+OUR_SUFFIXES = r"(?:s|ed|ing|al|ful|able|ite|ites|hood)?"
+# Word stems spelled -ize in American and -ise in British English
+IZE_STEMS = [
+    "categori",
+    "characteri",
+    "customi",
+    "emphasi",
+    "generali",
+    "initiali",
+    "maximi",
+    "minimi",
+    "normali",
+    "optimi",
+    "organi",
+    "paralleli",
+    "prioriti",
+    "randomi",
+    "reali",
+    "recogni",
+    "seriali",
+    "speciali",
+    "standardi",
+    "summari",
+    "synchroni",
+    "utili",
+    "visuali",
+]
+# The suffix is required, as "emphasis" and "analyses" are spelled the same in both
+IZE_SUFFIXES = r"(?:e|es|ed|ing|er|ers|ation|ations)"
+# Pairs of regexes matching the American and the British spelling of whole words
+DIALECT_PAIRS = (
+    [(stem + "r" + OUR_SUFFIXES, stem + "ur" + OUR_SUFFIXES) for stem in OUR_STEMS]
+    + [(stem + "z" + IZE_SUFFIXES, stem + "s" + IZE_SUFFIXES) for stem in IZE_STEMS]
+    + [
+        (r"analyz(?:e|ed|ing|er|ers)", r"analys(?:e|ed|ing|er|ers)"),
+        (r"cent(?:er|ers|ered|ering)", r"cent(?:re|res|red|ring)"),
+        (r"fib(?:er|ers)", r"fib(?:re|res)"),
+        (r"model(?:ed|ing|er|ers)", r"modell(?:ed|ing|er|ers)"),
+        (r"label(?:ed|ing)", r"labell(?:ed|ing)"),
+        (r"travel(?:ed|ing|er|ers)", r"travell(?:ed|ing|er|ers)"),
+        (r"cancel(?:ed|ing)", r"cancell(?:ed|ing)"),
+        (r"signal(?:ed|ing)", r"signall(?:ed|ing)"),
+        (r"gray(?:s|ed|ish)?", r"grey(?:s|ed|ish)?"),
+        (r"defense(?:s|less)?", r"defence(?:s|less)?"),
+        (r"artifact(?:s)?", r"artefact(?:s)?"),
+        (r"aluminum", r"aluminium"),
+        (r"skeptic(?:s|al|ism)?", r"sceptic(?:s|al|ism)?"),
+        (r"catalog(?:s|ed|ing)?", r"catalogue(?:s|d)?"),
+        (r"fulfill(?:s|ment)?", r"fulfil(?:s|ment)?"),
+    ]
+)
+RE_AMERICAN = re.compile(
+    r"\b(?:" + "|".join(us for us, _ in DIALECT_PAIRS) + r")\b", re.IGNORECASE
+)
+RE_BRITISH = re.compile(
+    r"\b(?:" + "|".join(gb for _, gb in DIALECT_PAIRS) + r")\b", re.IGNORECASE
+)
+# Regions of `% !TeX spellcheck` dictionaries using British spelling
+BRITISH_REGIONS = ["gb", "au", "nz", "ie", "za", "in"]
+
 
 @dataclass
 class Rule:
@@ -97,7 +161,73 @@ def search(rules: t.List[Rule], files: t.List[t.IO[str]]) -> bool:
     return found_different_spellings
 
 
-def main() -> None:
+def expected_dialect(text: str) -> t.Optional[str]:
+    """Return the dialect selected via `% !TeX spellcheck`, American or British"""
+    language = magic_comments(text).get("spellcheck", "").lower().replace("-", "_")
+    code, _, region = language.partition("_")
+    if code != "en" or not region:
+        return None
+    if region == "us":
+        return "American"
+    return "British" if region in BRITISH_REGIONS else None
+
+
+def check_dialects(files: t.List[t.IO[str]]) -> t.Tuple[bool, bool]:
+    """
+    Flag spellings of the other dialect in files with an English `% !TeX spellcheck`.
+
+    Returns whether spellings were flagged and whether any file selects a dialect.
+    """
+    found_error = False
+    has_dialect = False
+    for f in files:
+        text = f.read()
+        f.seek(0)
+        dialect = expected_dialect(text)
+        if dialect is None:
+            continue
+        has_dialect = True
+        if dialect == "American":
+            regex, other = RE_BRITISH, "British"
+        else:
+            regex, other = RE_AMERICAN, "American"
+        for match in regex.finditer(mask_non_prose(text)):
+            found_error = True
+            report(
+                f.name,
+                text,
+                match.start(),
+                f"{other} spelling '{match[0]}' in a file spellchecked as {dialect}",
+            )
+    return found_error, has_dialect
+
+
+def dialect_report(files: t.List[t.IO[str]]) -> None:
+    """Print how many words of each file are spelled in American and British English"""
+    print("Spelling dialects, American : British")
+    total_us, total_gb = 0, 0
+    for f in files:
+        text = f.read()
+        f.seek(0)
+        prose = mask_non_prose(text)
+        us, gb = len(RE_AMERICAN.findall(prose)), len(RE_BRITISH.findall(prose))
+        total_us += us
+        total_gb += gb
+        dialect = expected_dialect(text)
+        expected = f", expected {dialect}" if dialect is not None else ""
+        print(f"{f.name}: {dialect_ratio(us, gb)}{expected}")
+    print(f"Total: {dialect_ratio(total_us, total_gb)}")
+
+
+def dialect_ratio(us: int, gb: int) -> str:
+    if us + gb == 0:
+        return "0 : 0"
+    if us >= gb:
+        return f"{us} : {gb} ({round(100 * us / (us + gb))}% American)"
+    return f"{us} : {gb} ({round(100 * gb / (us + gb))}% British)"
+
+
+def build_parser() -> argparse.ArgumentParser:
     parser = argparse.ArgumentParser()
     parser.add_argument(
         "--emph",
@@ -114,6 +244,11 @@ def main() -> None:
         help="Match all occurences of regex",
         default=list(),
     )
+    parser.add_argument(
+        "--dialect-report",
+        action="store_true",
+        help="Print the ratio of American and British spellings per file",
+    )
     parser.add_argument(
         "files",
         metavar="FILE",
@@ -121,20 +256,32 @@ def main() -> None:
         nargs="+",
         help="List of filenames to search in",
     )
-    args = parser.parse_args()
+    return parser
+
 
+def run(args: argparse.Namespace) -> None:
     rules: t.List[Rule] = list()
     rules += [emph_rule(phrase) for phrase in args.emph]
     rules += [Rule(name=name, regex=re.compile(r)) for name, r in args.regex]
 
-    if len(rules) == 0:
+    files = sorted(args.files, key=lambda f: f.name)
+    if args.dialect_report:
+        dialect_report(files)
+
+    found_different_spellings, has_dialect = check_dialects(files)
+    if len(rules) == 0 and not has_dialect:
+        if args.dialect_report:
+            return
         sys.exit("No rules specified. See --help for how to use them.")
 
-    files = sorted(args.files, key=lambda f: f.name)
-    found_different_spellings = search(rules, files)
+    found_different_spellings |= search(rules, files)
     if found_different_spellings:
         sys.exit("Found different spellings")
 
 
+def main() -> None:
+    run(build_parser().parse_args())
+
+
 if __name__ == "__main__":
     main()
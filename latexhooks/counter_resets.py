@@ -0,0 +1,100 @@
+#!/usr/bin/env python3
+import argparse
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    check_projects,
+    document_body,
+    iter_commands,
+    iter_definitions,
+    read_project,
+    report,
+)
+
+# Counters whose manual reset produces duplicate numbers
+DEFAULT_COUNTERS = ["equation", "figure", "table"]
+WITHIN_COMMANDS = ["numberwithin", "counterwithin"]
+
+
+def search(files: t.List[t.IO[str]], counters: t.List[str]) -> bool:
+    texts = read_project(files)
+    body = document_body(files)
+
+    # Counters which are reset automatically together with their parent counter
+    within: t.Dict[str, str] = dict()
+    for _, text in texts:
+        for cmd in iter_commands(text, WITHIN_COMMANDS, nargs=2):
+            if len(cmd.args) == 2:
+                within[cmd.args[0].strip()] = f"\\{cmd.name}{{{cmd.args[0].strip()}}}"
+
+    found_error = False
+    for file_name, text in texts:
+        definitions = [(d.start, d.end) for d in iter_definitions(text)]
+        body_start = body[file_name][0] if file_name in body else None
+        for cmd in iter_commands(text, ["setcounter"], nargs=2):
+            if len(cmd.args) != 2:
+                continue
+            counter = cmd.args[0].strip()
+            if counter in within:
+                found_error = True
+                report(
+                    file_name,
+                    text,
+                    cmd.start,
+                    f"Counter {counter} is reset manually and by {within[counter]}, "
+                    "remove the manual reset",
+                )
+                continue
+            in_definition = any(start <= cmd.start < end for start, end in definitions)
+            in_body = body_start is not None and cmd.start >= body_start
+            if counter in counters and in_body and not in_definition:
+                found_error = True
+                report(
+                    file_name,
+                    text,
+                    cmd.start,
+                    f"Counter {counter} is reset manually in the document, which "
+                    "produces duplicate numbers, use "
+                    f"\\numberwithin{{{counter}}}{{section}}",
+                )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--counter",
+        action="append",
+        metavar="NAME",
+        default=list(),
+        help="Counter which must not be reset in the document, replaces the default "
+        f"{', '.join(DEFAULT_COUNTERS)}. Can be given multiple times.",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    counters = args.counter or DEFAULT_COUNTERS
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, counters),
+        "Found manually reset counters",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
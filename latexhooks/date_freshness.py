@@ -0,0 +1,128 @@
+#!/usr/bin/env python3
+import argparse
+import os
+import re
+import subprocess
+import sys
+import typing as t
+
+from latexhooks.common import iter_commands, iter_definitions, mask_source, report
+
+RE_HUNK_LINE = re.compile(r"^[+-](?![+-])(.*)$", re.M)
+
+
+def git(*args: str) -> t.Optional[str]:
+    """Return the output of git, or `None` if git is missing or fails"""
+    try:
+        result = subprocess.run(
+            ["git", *args],
+            capture_output=True,
+            encoding="utf-8",
+            check=True,
+        )
+    except (FileNotFoundError, subprocess.CalledProcessError):
+        return None
+    return result.stdout
+
+
+def check_version_macros(files: t.List[str], macros: t.List[str]) -> bool:
+    """
+    Ensure the version macros are updated whenever content files change.
+
+    Only the staged changes are compared, so nothing is checked outside of a git
+    repository or if none of the `files` is staged, e.g., with `--all-files`.
+    """
+    staged = git("diff", "--cached", "--name-only")
+    tracked = git("ls-files", "--", "*.tex", "*.sty", "*.cls")
+    if staged is None or tracked is None:
+        return False
+    staged_files = {os.path.normpath(name) for name in staged.splitlines()}
+    changed_files = [
+        os.path.normpath(name)
+        for name in files
+        if os.path.normpath(name) in staged_files
+    ]
+
+    found_error = False
+    for file_name in tracked.splitlines():
+        try:
+            with open(file_name, encoding="utf-8") as f:
+                text = mask_source(f.read())
+        except OSError:
+            continue
+        for definition in iter_definitions(text):
+            # Only content changes in other files require a new version
+            if definition.name not in macros or all(
+                name == os.path.normpath(file_name) for name in changed_files
+            ):
+                continue
+            diff = git("diff", "--cached", "-U0", "--", file_name) or ""
+            changed = "\n".join(m[1] for m in RE_HUNK_LINE.finditer(diff))
+            pattern = r"\\" + re.escape(definition.name) + r"(?![a-zA-Z@])"
+            if not re.search(pattern, changed):
+                found_error = True
+                report(
+                    file_name,
+                    text,
+                    definition.start,
+                    f"Content changed, but \\{definition.name} was not updated",
+                )
+    return found_error
+
+
+def check_date(files: t.List[str]) -> bool:
+    """Ensure that \\date uses \\today instead of a hard-coded date"""
+    found_error = False
+    for file_name in files:
+        with open(file_name, encoding="utf-8") as f:
+            text = mask_source(f.read())
+        for cmd in iter_commands(text, ["date"]):
+            if cmd.args and "\\today" not in cmd.args[0] and cmd.args[0].strip():
+                found_error = True
+                report(
+                    file_name,
+                    text,
+                    cmd.start,
+                    f"Hard-coded date '{cmd.args[0].strip()}', use \\today instead",
+                )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--version-macro",
+        action="append",
+        metavar="NAME",
+        help="Macro with the document version or date. Can be given multiple times.",
+        default=list(),
+    )
+    parser.add_argument(
+        "--expect-today",
+        action="store_true",
+        help="The argument of \\date must contain \\today",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    macros = args.version_macro or ["thesisversion", "docdate"]
+    found_error = check_version_macros(args.files, macros)
+    if args.expect_today:
+        found_error |= check_date(args.files)
+    if found_error:
+        sys.exit("Found stale version or date information")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
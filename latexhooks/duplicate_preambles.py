@@ -0,0 +1,104 @@
+#!/usr/bin/env python3
+import argparse
+import difflib
+import itertools
+import sys
+import typing as t
+
+from latexhooks.common import is_root, read_masked
+
+# A preamble line, i.e., its line number and its content without whitespace and comments
+Line = t.Tuple[int, str]
+
+
+def preamble_lines(file_name: str) -> t.List[Line]:
+    """Return the non-empty lines before `\\begin{document}` but `\\documentclass`"""
+    text = read_masked(file_name)
+    end = text.find("\\begin{document}")
+    if end == -1:
+        return []
+    lines: t.List[Line] = []
+    for number, line in enumerate(text[:end].splitlines(), 1):
+        line = " ".join(line.split())
+        if line and not line.startswith("\\documentclass"):
+            lines.append((number, line))
+    return lines
+
+
+def print_diverging(file_name: str, lines: t.List[Line], other_name: str) -> None:
+    if lines:
+        print(f"  Only in {file_name}, not in {other_name}:")
+        for number, line in lines:
+            print(f"    {file_name}:{number} {line}")
+
+
+def search(files: t.List[t.IO[str]], min_similarity: float, min_lines: int) -> bool:
+    preambles = [
+        (f.name, preamble_lines(f.name)) for f in files if is_root(read_masked(f.name))
+    ]
+
+    found_error = False
+    for (name_a, lines_a), (name_b, lines_b) in itertools.combinations(preambles, 2):
+        if len(lines_a) < min_lines or len(lines_b) < min_lines:
+            continue
+        matcher = difflib.SequenceMatcher(
+            None, [line for _, line in lines_a], [line for _, line in lines_b]
+        )
+        similarity = matcher.ratio()
+        if similarity < min_similarity:
+            continue
+
+        found_error = True
+        print(
+            f"Found similar preambles in {name_a} and {name_b}"
+            f" (similarity {similarity:.0%}), move them into a shared preamble.tex"
+        )
+        only_a: t.List[Line] = []
+        only_b: t.List[Line] = []
+        for tag, a_start, a_end, b_start, b_end in matcher.get_opcodes():
+            if tag != "equal":
+                only_a += lines_a[a_start:a_end]
+                only_b += lines_b[b_start:b_end]
+        print_diverging(name_a, only_a, name_b)
+        print_diverging(name_b, only_b, name_a)
+        print()
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--min-similarity",
+        type=float,
+        default=0.8,
+        help="Report preambles whose lines are at least this similar, between 0 and 1",
+    )
+    parser.add_argument(
+        "--min-lines",
+        type=int,
+        default=5,
+        help="Ignore preambles with fewer lines",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    files = sorted(args.files, key=lambda f: f.name)
+    found_error = search(files, args.min_similarity, args.min_lines)
+    if found_error:
+        sys.exit("Found duplicated preambles")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
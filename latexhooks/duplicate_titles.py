@@ -0,0 +1,82 @@
+#!/usr/bin/env python3
+import argparse
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    check_projects,
+    iter_commands,
+    line_number,
+    read_project,
+)
+
+SECTIONING_COMMANDS = [
+    "part",
+    "chapter",
+    "section",
+    "subsection",
+    "subsubsection",
+    "paragraph",
+]
+
+
+def search(files: t.List[t.IO[str]], commands: t.List[str]) -> bool:
+    texts = read_project(files)
+
+    # Level and normalized title mapped to all places using it
+    titles: t.Dict[t.Tuple[str, str], t.List[t.Tuple[str, int, str]]] = dict()
+    for file_name, text in texts:
+        for cmd in iter_commands(text, commands):
+            if not cmd.args:
+                continue
+            title = " ".join(cmd.args[0].split())
+            location = (file_name, line_number(text, cmd.start), title)
+            titles.setdefault((cmd.name, title.lower()), list()).append(location)
+
+    found_error = False
+    for (level, _), locations in titles.items():
+        if len(locations) > 1:
+            found_error = True
+            print(f"Found multiple {level} commands titled '{locations[0][2]}'")
+            for file_name, line, _ in locations:
+                print(f"  {file_name}:{line}")
+            print()
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--level",
+        action="append",
+        metavar="COMMAND",
+        choices=SECTIONING_COMMANDS,
+        help="Only check this sectioning command. Can be given multiple times.",
+        default=list(),
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, args.level or SECTIONING_COMMANDS),
+        "Found duplicate section titles",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,101 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    MATH_ENVIRONMENTS,
+    RE_INLINE_MATH,
+    Definition,
+    add_root_argument,
+    check_projects,
+    iter_commands,
+    iter_definitions,
+    iter_environments,
+    read_project,
+    report,
+)
+
+# Tokens which are only valid in math mode
+RE_MATH_ONLY = re.compile(
+    r"\\(?:math[a-z]+|frac|dfrac|sqrt|lVert|rVert|lvert|rvert|left|right|sum|prod|int"
+    r"|alpha|beta|gamma|delta|epsilon|varepsilon|theta|lambda|mu|pi|sigma|phi|omega"
+    r"|Delta|Gamma|Sigma|Omega|leq|geq|neq|cdot|times|infty|operatorname|hat|bar"
+    r"|vec|tilde|in|subseteq|mid)(?![a-zA-Z])|[\^_]"
+)
+# Tokens which switch into math mode or make the macro mode independent
+RE_MODE_SWITCH = re.compile(r"\$|\\\(|\\\[|\\ensuremath\b|\\text\b|\\mbox\b")
+
+
+def is_math_macro(definition: Definition) -> bool:
+    body = definition.body
+    return bool(RE_MATH_ONLY.search(body)) and not RE_MODE_SWITCH.search(body)
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    texts = read_project(files)
+
+    definitions: t.Dict[str, t.Tuple[str, str, Definition]] = dict()
+    for file_name, text in texts:
+        for definition in iter_definitions(text):
+            if is_math_macro(definition):
+                definitions[definition.name] = (file_name, text, definition)
+    if not definitions:
+        return False
+
+    inside: t.Set[str] = set()
+    outside: t.Set[str] = set()
+    for _, text in texts:
+        math = [m.span() for m in RE_INLINE_MATH.finditer(text)]
+        math += [
+            (env.start, env.end) for env in iter_environments(text, MATH_ENVIRONMENTS)
+        ]
+        # Usages in other definitions do not tell us anything about the mode
+        skipped = [(d.start, d.end) for d in iter_definitions(text)]
+        for cmd in iter_commands(text, definitions.keys(), nargs=0):
+            if any(start <= cmd.start < end for start, end in skipped):
+                continue
+            if any(start <= cmd.start < end for start, end in math):
+                inside.add(cmd.name)
+            else:
+                outside.add(cmd.name)
+
+    found_error = False
+    for name in sorted(inside & outside):
+        found_error = True
+        file_name, text, definition = definitions[name]
+        report(
+            file_name,
+            text,
+            definition.start,
+            f"\\{name} is used in and outside of math mode, "
+            "wrap its definition in \\ensuremath{...}",
+        )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files, args.root, search, "Found math macros which need \\ensuremath"
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,110 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import iter_environments, mask_source, report
+
+FLOAT_ENVIRONMENTS = ["figure", "figure*", "table", "table*", "algorithm"]
+DISPLAY_MATH_ENVIRONMENTS = [
+    "equation",
+    "equation*",
+    "align",
+    "align*",
+    "gather",
+    "gather*",
+    "multline",
+    "multline*",
+    "flalign",
+    "flalign*",
+    "displaymath",
+]
+
+RE_DISPLAY_MATH = re.compile(r"(?<!\\)\\\[")
+RE_OPTIONS = re.compile(r"[ \t]*\[[^\]\n]*\]")
+
+
+def line_before(text: str, offset: int) -> t.Optional[str]:
+    """Return the previous line, if only whitespace precedes `offset` in its line"""
+    line_start = text.rfind("\n", 0, offset) + 1
+    if text[line_start:offset].strip() or line_start == 0:
+        return None
+    previous_start = text.rfind("\n", 0, line_start - 1) + 1
+    return text[previous_start : line_start - 1]
+
+
+def blank_line_after(text: str, offset: int) -> bool:
+    """Check whether the rest of the line after `offset` is followed by a blank line"""
+    line_end = text.find("\n", offset)
+    if line_end == -1 or text[offset:line_end].strip():
+        return False
+    next_end = text.find("\n", line_end + 1)
+    next_line = text[line_end + 1 : next_end if next_end != -1 else len(text)]
+    return next_end != -1 and not next_line.strip()
+
+
+def search(files: t.List[t.IO[str]], blank_before_floats: bool) -> bool:
+    found_error = False
+    for f in files:
+        # Keep comment lines, since they are not blank and do not end a paragraph
+        text = f.read()
+        masked = mask_source(text)
+
+        # Start offset, offset after \begin, description, and whether it is a float
+        begins: t.List[t.Tuple[int, int, str, bool]] = []
+        for env in iter_environments(masked, FLOAT_ENVIRONMENTS):
+            options = RE_OPTIONS.match(masked, env.body_start)
+            body_start = options.end() if options else env.body_start
+            begins.append((env.start, body_start, f"\\begin{{{env.name}}}", True))
+        for env in iter_environments(masked, DISPLAY_MATH_ENVIRONMENTS):
+            begins.append((env.start, env.body_start, f"\\begin{{{env.name}}}", False))
+        for match in RE_DISPLAY_MATH.finditer(masked):
+            begins.append((match.start(), match.end(), "\\[", False))
+
+        for start, body_start, name, is_float in sorted(begins):
+            previous = line_before(text, start)
+            if previous is not None:
+                blank = not previous.strip()
+                if is_float and blank_before_floats and not blank:
+                    found_error = True
+                    report(f.name, text, start, f"Missing blank line before {name}")
+                elif blank and (not is_float or not blank_before_floats):
+                    found_error = True
+                    report(f.name, text, start, f"Remove the blank line before {name}")
+            if blank_line_after(masked, body_start):
+                found_error = True
+                report(f.name, text, start, f"Remove the blank line after {name}")
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--float-spacing",
+        choices=["blank", "no-blank"],
+        default="blank",
+        help="Whether floats must be preceded by a blank line or must not be",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files, args.float_spacing == "blank")
+    if found_error:
+        sys.exit("Found wrong blank lines around environments")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,72 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import is_root, mask_source, report
+
+RE_END_DOCUMENT = re.compile(r"\\end\s*\{document\}")
+RE_NON_SPACE = re.compile(r"\S")
+
+
+def search(files: t.List[t.IO[str]], require_endinput: bool) -> bool:
+    found_error = False
+    for f in files:
+        text = f.read()
+        masked = mask_source(text)
+
+        content = text.rstrip(" \t\n")
+        if not content:
+            continue
+        if not text.endswith("\n"):
+            found_error = True
+            report(f.name, text, len(text), "File does not end with a newline")
+        elif text != content + "\n":
+            found_error = True
+            message = "File ends with blank lines or trailing whitespace"
+            report(f.name, text, len(content), message)
+
+        if is_root(masked):
+            match = RE_END_DOCUMENT.search(masked)
+            trailing = RE_NON_SPACE.search(masked, match.end()) if match else None
+            if trailing is not None:
+                found_error = True
+                message = "Content after \\end{document} is ignored"
+                report(f.name, text, trailing.start(), message)
+        elif require_endinput and not masked.rstrip().endswith("\\endinput"):
+            found_error = True
+            message = "File does not end with \\endinput"
+            report(f.name, text, len(masked.rstrip()), message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--require-endinput",
+        action="store_true",
+        help="Files without \\documentclass must end with \\endinput",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files, args.require_endinput)
+    if found_error:
+        sys.exit("Found problems at the end of files")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,147 @@
+#!/usr/bin/env python3
+import argparse
+import os
+import re
+import sys
+import typing as t
+from dataclasses import dataclass
+
+from latexhooks.common import (
+    REF_COMMANDS,
+    iter_commands,
+    mask_source,
+    report,
+    resolve_inputs,
+)
+
+RE_NEWLABEL = re.compile(r"^\\newlabel\{([^\{\}]*)\}", re.MULTILINE)
+
+
+@dataclass
+class ExternalDocument:
+    """A document loaded via `\\externaldocument`"""
+
+    prefix: str
+    """ Prefix prepended to all labels of the external document """
+    labels: t.Set[str]
+    """ All labels defined in the external document """
+
+
+def defined_labels(text: str) -> t.Set[str]:
+    return {cmd.args[0].strip() for cmd in iter_commands(text, ["label"]) if cmd.args}
+
+
+def external_labels(base: str) -> t.Optional[t.Set[str]]:
+    """
+    Collect all labels of an external document.
+
+    The `.aux` file is preferred as it is what xr reads.
+    Otherwise, the labels are read from the sources of the external document.
+    """
+    if os.path.isfile(base + ".aux"):
+        with open(base + ".aux", encoding="utf-8") as f:
+            return set(RE_NEWLABEL.findall(f.read()))
+
+    sources = resolve_inputs(base)
+    if not sources:
+        return None
+    labels: t.Set[str] = set()
+    for source in sources:
+        with open(source, encoding="utf-8") as f:
+            text = mask_source(f.read())
+        labels |= defined_labels(text)
+    return labels
+
+
+def is_defined(label: str, ext: ExternalDocument) -> bool:
+    return label.startswith(ext.prefix) and label[len(ext.prefix) :] in ext.labels
+
+
+def search(files: t.List[t.IO[str]], check_all_refs: bool) -> bool:
+    texts = [(f.name, mask_source(f.read())) for f in files]
+
+    found_error = False
+    local_labels: t.Set[str] = set()
+    externals: t.List[ExternalDocument] = []
+    for file_name, text in texts:
+        local_labels |= defined_labels(text)
+        for cmd in iter_commands(text, ["externaldocument"]):
+            if not cmd.args:
+                continue
+            base = os.path.join(os.path.dirname(file_name), cmd.args[0].strip())
+            labels = external_labels(base)
+            if labels is None:
+                found_error = True
+                report(
+                    file_name,
+                    text,
+                    cmd.start,
+                    f"Cannot find external document {base}.aux or {base}.tex",
+                )
+                continue
+            prefix = cmd.optional[0].strip() if cmd.optional else ""
+            externals.append(ExternalDocument(prefix=prefix, labels=labels))
+
+    if not externals:
+        return found_error
+
+    for file_name, text in texts:
+        for cmd in iter_commands(text, REF_COMMANDS):
+            if not cmd.args:
+                continue
+            for label in cmd.args[0].split(","):
+                label = label.strip()
+                matching = [
+                    ext
+                    for ext in externals
+                    if ext.prefix and label.startswith(ext.prefix)
+                ]
+                if matching:
+                    if not any(is_defined(label, ext) for ext in matching):
+                        found_error = True
+                        report(
+                            file_name,
+                            text,
+                            cmd.start,
+                            f"Undefined reference to external label '{label}'",
+                        )
+                elif check_all_refs and label not in local_labels:
+                    if not any(is_defined(label, ext) for ext in externals):
+                        found_error = True
+                        report(
+                            file_name, text, cmd.start, f"Undefined reference '{label}'"
+                        )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--check-all-refs",
+        action="store_true",
+        help="Also check references without an external prefix. "
+        "This requires all files of the document to be passed.",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    files = sorted(args.files, key=lambda f: f.name)
+    found_error = search(files, args.check_all_refs)
+    if found_error:
+        sys.exit("Found undefined references into external documents")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
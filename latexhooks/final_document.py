@@ -0,0 +1,108 @@
+#!/usr/bin/env python3
+import argparse
+import fnmatch
+import sys
+import typing as t
+
+from latexhooks.common import (
+    current_branch,
+    iter_commands,
+    mask_source,
+    report,
+    split_list,
+)
+
+PROFILES = ["draft", "final"]
+# Options which leave draft watermarks, line numbers, or placeholders in the PDF
+DEFAULT_DRAFT_OPTIONS = ["draft", "linenumbers"]
+OPTION_COMMANDS = ["documentclass", "usepackage", "RequirePackage"]
+
+
+def is_enabled(option: str, names: t.List[str]) -> bool:
+    """The option is one of `names`, also as `name=true`"""
+    name, _, value = option.partition("=")
+    return name.strip() in names and value.strip().lower() in ["", "true"]
+
+
+def search(files: t.List[t.IO[str]], draft_options: t.List[str]) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        for cmd in iter_commands(text, ["today"], nargs=0):
+            found_error = True
+            report(
+                f.name,
+                text,
+                cmd.start,
+                "\\today prints the compile date in the final document, "
+                "use a fixed date",
+            )
+        for cmd in iter_commands(text, OPTION_COMMANDS):
+            for option in split_list(cmd.optional):
+                if is_enabled(option, draft_options):
+                    found_error = True
+                    report(
+                        f.name,
+                        text,
+                        cmd.start,
+                        f"Draft option '{option}' of \\{cmd.name} "
+                        "in the final document",
+                    )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--profile",
+        choices=PROFILES,
+        default="draft",
+        help="The final profile always checks the files, "
+        "the draft profile only on release branches",
+    )
+    parser.add_argument(
+        "--release-branch",
+        action="append",
+        metavar="PATTERN",
+        default=list(),
+        help="Glob pattern of branches with final documents, e.g., release/*",
+    )
+    parser.add_argument(
+        "--draft-option",
+        action="append",
+        metavar="NAME",
+        default=list(),
+        help="Class or package option which must not be set in the final document, "
+        f"replaces the default {', '.join(DEFAULT_DRAFT_OPTIONS)}. "
+        "Can be given multiple times.",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    branch = current_branch()
+    is_release = branch is not None and any(
+        fnmatch.fnmatchcase(branch, pattern) for pattern in args.release_branch
+    )
+    if args.profile != "final" and not is_release:
+        return
+
+    draft_options = args.draft_option or DEFAULT_DRAFT_OPTIONS
+    found_error = search(args.files, draft_options)
+    if found_error:
+        sys.exit("Found \\today or draft options in the final document")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,111 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import iter_environments, line_number, mask_source
+
+FLOAT_ENVIRONMENTS = ["figure", "figure*", "table", "table*"]
+
+RE_OPTIONS = re.compile(r"[ \t]*\[[^\]\n]*\]")
+RE_CENTERING = re.compile(r"\\centering(?![a-zA-Z@])")
+RE_CENTERING_LINE = re.compile(r"^[ \t]*\\centering[ \t]*\n", re.M)
+
+
+def find_centering(text: str, centered: bool) -> t.List[t.Tuple[int, int, str, str]]:
+    """
+    Return the edits, as start, end, replacement, and message, to apply the policy.
+
+    Problems which cannot be fixed automatically are empty edits.
+    """
+    masked = mask_source(text)
+    edits: t.List[t.Tuple[int, int, str, str]] = []
+    for env in iter_environments(masked, FLOAT_ENVIRONMENTS):
+        body = masked[env.body_start : env.body_end]
+        has_centering = RE_CENTERING.search(body) is not None
+        has_center = "\\begin{center}" in body
+        if centered and has_center:
+            message = "Use \\centering instead of the center environment"
+            edits.append((env.start, env.start, "", message))
+        elif centered and not has_centering:
+            # The options must end inside of the environment
+            options = RE_OPTIONS.match(masked, env.body_start, env.body_end)
+            insert = options.end() if options else env.body_start
+            # Use the indentation of the following line
+            next_line = re.match(r"[ \t]*\n([ \t]*)\S", masked[insert:])
+            indent = next_line[1] if next_line else "    "
+            replacement = f"\n{indent}\\centering"
+            end = insert
+            # Content on the line of the \begin moves to its own line
+            content = re.match(r"([ \t]*)(?=[^ \t\n])", masked[insert:])
+            if content:
+                replacement += f"\n{indent}"
+                end += len(content[1])
+            message = f"Missing \\centering in {env.name}"
+            edits.append((insert, end, replacement, message))
+        elif not centered and (has_centering or has_center):
+            lines = list(RE_CENTERING_LINE.finditer(text, env.body_start, env.body_end))
+            if has_centering and lines:
+                for line in lines:
+                    message = f"Remove \\centering from {env.name}"
+                    edits.append((line.start(), line.end(), "", message))
+            else:
+                message = f"{env.name} should not be centered"
+                edits.append((env.start, env.start, "", message))
+    return sorted(edits)
+
+
+def search(files: t.List[str], centered: bool, fix: bool) -> bool:
+    found_error = False
+    for file_name in files:
+        with open(file_name, encoding="utf-8") as f:
+            text = f.read()
+        edits = find_centering(text, centered)
+        for start, _, _, message in edits:
+            found_error = True
+            print(f"{file_name}:{line_number(text, start)} {message}")
+
+        if fix and edits:
+            # Replace from the back, such that the offsets stay valid
+            for start, end, replacement, _ in reversed(edits):
+                text = text[:start] + replacement + text[end:]
+            with open(file_name, "w", encoding="utf-8") as f:
+                f.write(text)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--policy",
+        choices=["centered", "not-centered"],
+        default="centered",
+        help="Whether floats must use \\centering or must not",
+    )
+    parser.add_argument(
+        "--fix",
+        action="store_true",
+        help="Add or remove \\centering in place",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(sorted(args.files), args.policy == "centered", args.fix)
+    if found_error:
+        sys.exit("Found floats violating the centering policy")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
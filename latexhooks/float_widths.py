@@ -0,0 +1,110 @@
+#!/usr/bin/env python3
+import argparse
+import typing as t
+
+from latexhooks.common import (
+    TABULAR_ENVIRONMENTS,
+    add_root_argument,
+    check_projects,
+    is_root,
+    iter_commands,
+    iter_environments,
+    read_project,
+    report,
+    split_list,
+    table_columns,
+)
+
+WIDE_FLOATS = ["figure*", "table*", "algorithm*"]
+
+# Document classes typesetting two columns by default
+TWO_COLUMN_CLASSES = ["IEEEtran"]
+# Class options switching to two columns, besides the standard twocolumn
+TWO_COLUMN_OPTIONS = {
+    "acmart": ["sigconf", "sigplan", "sigchi"],
+    "elsarticle": ["3p", "5p"],
+    "revtex4-1": ["reprint"],
+    "revtex4-2": ["reprint"],
+}
+
+
+def is_two_column(text: str) -> bool:
+    """The document class of the root document typesets two columns"""
+    for cmd in iter_commands(text, ["documentclass"]):
+        if not cmd.args:
+            continue
+        documentclass = cmd.args[0].strip()
+        options = split_list(cmd.optional)
+        if "onecolumn" in options:
+            return False
+        if "twocolumn" in options or documentclass in TWO_COLUMN_CLASSES:
+            return True
+        return any(o in options for o in TWO_COLUMN_OPTIONS.get(documentclass, []))
+    return False
+
+
+def search(files: t.List[t.IO[str]], max_columns: int) -> bool:
+    found_error = False
+    for f in files:
+        texts = read_project([f])
+        if not texts or not is_root(texts[0][1]):
+            continue
+        two_column = is_two_column(texts[0][1])
+        for file_name, text in texts:
+            wide_floats = list(iter_environments(text, WIDE_FLOATS))
+            if not two_column:
+                for env in wide_floats:
+                    found_error = True
+                    message = f"{env.name} in a single-column document"
+                    report(file_name, text, env.start, message)
+                continue
+
+            for env in iter_environments(text, TABULAR_ENVIRONMENTS):
+                columns, _ = table_columns(text, env)
+                in_wide_float = any(
+                    wide.body_start <= env.start < wide.body_end for wide in wide_floats
+                )
+                if len(columns) > max_columns and not in_wide_float:
+                    found_error = True
+                    message = (
+                        f"Table with {len(columns)} columns does not fit into a column,"
+                        " use a starred float"
+                    )
+                    report(file_name, text, env.start, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-columns",
+        type=int,
+        default=6,
+        help="Maximum number of table columns outside of starred floats",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, args.max_columns),
+        "Found floats not matching the number of columns",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,118 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    ENGINE_PACKAGES,
+    add_root_argument,
+    check_projects,
+    iter_packages,
+    magic_program,
+    read_project,
+    report,
+    split_list,
+)
+
+# Letters outside of ASCII, which OT1 cannot hyphenate or copy correctly
+RE_ACCENTED = re.compile(r"[^\W\d_a-zA-Z]")
+
+UTF8_OPTIONS = ["utf8", "utf8x"]
+
+
+def detect_engine(
+    texts: t.List[t.Tuple[str, str]], raw: t.List[str]
+) -> t.Tuple[str, t.Optional[str]]:
+    """Return the engine and the file selecting it via a magic comment, if any"""
+    for (file_name, _), text in zip(texts, raw):
+        program = magic_program(text)
+        if program is not None:
+            return program, file_name
+    packages = {package for _, text in texts for _, package in iter_packages(text)}
+    if packages & {"fontspec", "unicode-math", "polyglossia"}:
+        return "lualatex", None
+    return "pdflatex", None
+
+
+def search(files: t.List[t.IO[str]], engine: t.Optional[str]) -> bool:
+    texts = read_project(files)
+    raw: t.List[str] = []
+    for file_name, _ in texts:
+        with open(file_name, encoding="utf-8") as f:
+            raw.append(f.read())
+    selected_by = None
+    if engine is None:
+        engine, selected_by = detect_engine(texts, raw)
+    legacy_engine = engine in ENGINE_PACKAGES["inputenc"]
+
+    found_error = False
+    has_fontenc = False
+    for file_name, text in texts:
+        for cmd, package in iter_packages(text):
+            options = split_list(cmd.optional)
+            if package == "fontenc" and set(options) - {"OT1"}:
+                has_fontenc = True
+            if package == "inputenc":
+                for option in options:
+                    if option not in UTF8_OPTIONS:
+                        found_error = True
+                        report(
+                            file_name,
+                            text,
+                            cmd.start,
+                            f"inputenc option {option} does not match UTF-8 files, "
+                            "use utf8",
+                        )
+            if package == "fontspec" and legacy_engine:
+                found_error = True
+                source = f"{selected_by} selects" if selected_by else "using"
+                message = f"fontspec needs lualatex or xelatex, but {source} {engine}"
+                report(file_name, text, cmd.start, message)
+
+    if legacy_engine and not has_fontenc:
+        for file_name, text in texts:
+            match = RE_ACCENTED.search(text)
+            if match:
+                found_error = True
+                message = (
+                    f"Character {match[0]} needs \\usepackage[T1]{{fontenc}} "
+                    f"with {engine}"
+                )
+                report(file_name, text, match.start(), message)
+                break
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--engine",
+        choices=["pdflatex", "latex", "lualatex", "xelatex"],
+        help="Engine used to compile the document, by default detected from the files",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, args.engine),
+        "Found misconfigured font or input encodings",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
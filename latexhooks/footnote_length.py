@@ -0,0 +1,96 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    CITE_COMMANDS,
+    count_sentences,
+    iter_commands,
+    mask_non_prose,
+    mask_source,
+    report,
+)
+
+RE_WORD = re.compile(r"[^\W_]+(?:['’-][^\W_]+)*")
+
+
+def search(
+    files: t.List[t.IO[str]], max_words: int, max_sentences: int, max_citations: int
+) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        for cmd in iter_commands(text, ["footnote"]):
+            if not cmd.args:
+                continue
+            content = cmd.args[0]
+            prose = mask_non_prose(content)
+            problems: t.List[str] = []
+
+            words = len(RE_WORD.findall(prose))
+            if words > max_words:
+                problems.append(f"{words} words (maximum {max_words})")
+            sentences = count_sentences(prose)
+            if sentences > max_sentences:
+                problems.append(f"{sentences} sentences (maximum {max_sentences})")
+            citations = sum(1 for _ in iter_commands(content, CITE_COMMANDS))
+            if citations > max_citations:
+                problems.append(f"{citations} citations (maximum {max_citations})")
+
+            if problems:
+                found_error = True
+                report(
+                    f.name,
+                    text,
+                    cmd.start,
+                    f"Footnote has {', '.join(problems)}, consider moving it into the text",
+                )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-words",
+        type=int,
+        default=40,
+        help="Maximum number of words in a footnote",
+    )
+    parser.add_argument(
+        "--max-sentences",
+        type=int,
+        default=1,
+        help="Maximum number of sentences in a footnote",
+    )
+    parser.add_argument(
+        "--max-citations",
+        type=int,
+        default=1,
+        help="Maximum number of citation commands in a footnote",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(
+        args.files, args.max_words, args.max_sentences, args.max_citations
+    )
+    if found_error:
+        sys.exit("Found overlong footnotes")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
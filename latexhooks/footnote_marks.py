@@ -0,0 +1,116 @@
+#!/usr/bin/env python3
+import argparse
+import sys
+import typing as t
+
+from latexhooks.common import (
+    TABULAR_ENVIRONMENTS,
+    iter_commands,
+    iter_environments,
+    line_number,
+    mask_source,
+    parse_group,
+    report,
+)
+
+# \footnote does not work inside these environments, use \footnotemark instead
+FOOTNOTE_FREE_ENVIRONMENTS = [
+    env for env in TABULAR_ENVIRONMENTS if env not in ("longtable", "xltabular")
+]
+
+
+def check_pairs(file_name: str, text: str, max_distance: int) -> bool:
+    """Pair each \\footnotemark with the next \\footnotetext using the same number"""
+    found_error = False
+    # Explicit number, or None for the running counter, mapped to the unmatched marks
+    pending: t.Dict[t.Optional[str], t.List[int]] = dict()
+    commands = sorted(
+        list(iter_commands(text, ["footnotemark"], nargs=0))
+        + list(iter_commands(text, ["footnotetext"])),
+        key=lambda cmd: cmd.start,
+    )
+    for cmd in commands:
+        if cmd.name == "footnotemark":
+            number = parse_group(text, cmd.end, "[", "]")
+            key = None if number is None else text[number[0] : number[1]].strip()
+            pending.setdefault(key, list()).append(cmd.start)
+            continue
+
+        key = cmd.optional[0].strip() if cmd.optional else None
+        marks = pending.get(key, [])
+        if not marks:
+            found_error = True
+            message = "\\footnotetext without a preceding \\footnotemark"
+            report(file_name, text, cmd.start, message)
+            continue
+        mark = marks.pop(0)
+        distance = line_number(text, cmd.start) - line_number(text, mark)
+        if distance > max_distance:
+            found_error = True
+            message = (
+                f"\\footnotetext is {distance} lines after its \\footnotemark"
+                f" (maximum {max_distance})"
+            )
+            report(file_name, text, cmd.start, message)
+
+    for marks in pending.values():
+        for mark in marks:
+            found_error = True
+            report(file_name, text, mark, "\\footnotemark without a \\footnotetext")
+    return found_error
+
+
+def search(files: t.List[t.IO[str]], max_distance: int) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        found_error |= check_pairs(f.name, text, max_distance)
+
+        captions = [(cmd.start, cmd.end) for cmd in iter_commands(text, ["caption"])]
+        tables = [
+            (env.body_start, env.body_end)
+            for env in iter_environments(text, FOOTNOTE_FREE_ENVIRONMENTS)
+        ]
+        for cmd in iter_commands(text, ["footnote"]):
+            if any(start <= cmd.start < end for start, end in captions):
+                place = "a caption"
+            elif any(start <= cmd.start < end for start, end in tables):
+                place = "a table"
+            else:
+                continue
+            found_error = True
+            message = f"\\footnote in {place}, use \\footnotemark and \\footnotetext"
+            report(f.name, text, cmd.start, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-distance",
+        type=int,
+        default=30,
+        help="Maximum number of lines between \\footnotemark and its \\footnotetext",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files, args.max_distance)
+    if found_error:
+        sys.exit("Found problems with \\footnotemark and \\footnotetext")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
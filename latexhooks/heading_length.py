@@ -0,0 +1,69 @@
+#!/usr/bin/env python3
+import argparse
+import sys
+import typing as t
+
+from latexhooks.common import iter_commands, mask_non_prose, mask_source, report
+
+SECTIONING_COMMANDS = ["part", "chapter", "section", "subsection", "subsubsection"]
+
+
+def search(files: t.List[t.IO[str]], max_chars: int, max_words: int) -> bool:
+    found_long_heading = False
+    for f in files:
+        text = mask_source(f.read())
+        for cmd in iter_commands(text, SECTIONING_COMMANDS):
+            # A short title is used for the running headers and the table of contents
+            if not cmd.args or cmd.optional:
+                continue
+            title = " ".join(mask_non_prose(cmd.args[0]).split())
+            chars = len(title)
+            words = len(title.split())
+            if chars > max_chars or words > max_words:
+                found_long_heading = True
+                report(
+                    f.name,
+                    text,
+                    cmd.start,
+                    f"Heading has {chars} characters and {words} words, "
+                    f"add a short title with \\{cmd.name}[short title]{{...}}",
+                )
+    return found_long_heading
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-chars",
+        type=int,
+        default=60,
+        help="Maximum number of characters in a heading without a short title",
+    )
+    parser.add_argument(
+        "--max-words",
+        type=int,
+        default=8,
+        help="Maximum number of words in a heading without a short title",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_long_heading = search(args.files, args.max_chars, args.max_words)
+    if found_long_heading:
+        sys.exit("Found overlong headings")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
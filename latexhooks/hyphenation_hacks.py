@@ -0,0 +1,89 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    VERBATIM_ENVIRONMENTS,
+    add_root_argument,
+    blank_spans,
+    check_projects,
+    is_local,
+    iter_commands,
+    iter_environments,
+    mask_non_prose,
+    read_project,
+    report,
+)
+
+RE_DISCRETIONARY = re.compile(r"(?<![\\])((?:\\\\)*)\\-")
+RE_WORD = re.compile(r"[^\W\d_]+(?:['’-][^\W\d_]+)*")
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    texts = read_project(files)
+
+    found_error = False
+    words: t.Set[str] = set()
+    hyphenations: t.List[t.Tuple[str, str, int, str]] = []
+    for file_name, text in texts:
+        for cmd in iter_commands(text, ["sloppy"], nargs=0):
+            if not is_local(text, cmd.start):
+                found_error = True
+                message = "\\sloppy affects the whole document, use sloppypar locally"
+                report(file_name, text, cmd.start, message)
+
+        # \- sets tab stops in tabbing environments
+        ignored = [
+            (env.start, env.end)
+            for env in iter_environments(text, VERBATIM_ENVIRONMENTS + ["tabbing"])
+        ]
+        for match in RE_DISCRETIONARY.finditer(blank_spans(text, ignored)):
+            found_error = True
+            offset = match.end() - 2
+            message = "Manual hyphenation with \\-, add the word to \\hyphenation"
+            report(file_name, text, offset, message)
+
+        for cmd in iter_commands(text, ["hyphenation"]):
+            if cmd.args:
+                for word in cmd.args[0].split():
+                    hyphenations.append((file_name, text, cmd.start, word))
+        prose = mask_non_prose(RE_DISCRETIONARY.sub(r"\1", text))
+        words |= {w.lower() for w in RE_WORD.findall(prose)}
+
+    for file_name, text, offset, word in hyphenations:
+        if word.replace("-", "").lower() not in words:
+            found_error = True
+            message = f"Hyphenation for '{word}', which does not occur in the document"
+            report(file_name, text, offset, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        search,
+        "Found manual hyphenation and line breaking hacks",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
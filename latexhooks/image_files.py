@@ -0,0 +1,190 @@
+#!/usr/bin/env python3
+import argparse
+import os
+import re
+import sys
+import typing as t
+
+from latexhooks.common import iter_commands, mask_source, report
+
+EXTENSIONS = [".pdf", ".png", ".jpg", ".jpeg", ".eps"]
+VECTOR_EXTENSIONS = {".pdf", ".eps", ".svg"}
+
+RE_ABSOLUTE_PATH = re.compile(r"^(?:[/~]|[a-zA-Z]:[/\\])")
+RE_PORTABLE_NAME = re.compile(r"[\x21-\x7e]+")
+
+
+def graphics_paths(text: str) -> t.List[str]:
+    """Return the directories configured via `\\graphicspath{{dir1/}{dir2/}}`"""
+    paths: t.List[str] = []
+    for cmd in iter_commands(text, ["graphicspath"]):
+        if cmd.args:
+            paths += [p for p in cmd.args[0].replace("}", "").split("{") if p.strip()]
+    return paths
+
+
+def resolve_image(
+    directory: str, name: str, search_paths: t.List[str]
+) -> t.Optional[str]:
+    for search_path in [""] + search_paths:
+        base = os.path.join(directory, search_path, name)
+        for candidate in [base] + [base + ext for ext in EXTENSIONS]:
+            if os.path.isfile(candidate):
+                return candidate
+    return None
+
+
+def path_problem(directory: str, path: str) -> t.Optional[str]:
+    """Check if the path only works on the author's machine"""
+    if RE_ABSOLUTE_PATH.match(path):
+        return "is an absolute path"
+    full_path = os.path.relpath(os.path.join(os.path.abspath(directory), path))
+    if full_path == ".." or full_path.startswith(".." + os.sep):
+        return "is outside of the repository"
+    return None
+
+
+def name_problem(
+    file_name: str, name: str, name_pattern: t.Optional[str]
+) -> t.Optional[str]:
+    """Check the file name of an image, without directory and extension"""
+    base = os.path.basename(name)
+    if os.path.splitext(base)[1].lower() in EXTENSIONS:
+        base = os.path.splitext(base)[0]
+    if not RE_PORTABLE_NAME.fullmatch(base):
+        return "contains spaces or non-ASCII characters"
+    if name_pattern is not None:
+        stem = os.path.splitext(os.path.basename(file_name))[0]
+        pattern = name_pattern.replace("{file}", re.escape(stem))
+        if not re.fullmatch(pattern, base):
+            return f"does not match the naming pattern {pattern}"
+    return None
+
+
+def is_progressive_jpeg(path: str) -> bool:
+    """Check if a JPEG uses progressive encoding, i.e., a SOF2 marker"""
+    with open(path, "rb") as f:
+        data = f.read()
+    idx = 2
+    while idx + 4 <= len(data):
+        if data[idx] != 0xFF:
+            return False
+        marker = data[idx + 1]
+        if marker == 0xC2:
+            return True
+        # Start of frame or start of scan, the remaining file is image data
+        if marker in (0xC0, 0xC1, 0xDA):
+            return False
+        length = int.from_bytes(data[idx + 2 : idx + 4], "big")
+        idx += 2 + length
+    return False
+
+
+def check_image(path: str, max_size: float, vector_dirs: t.List[str]) -> t.List[str]:
+    """Return all problems of the image"""
+    problems: t.List[str] = []
+    size = os.path.getsize(path) / 1024 / 1024
+    if size > max_size:
+        problems.append(f"{size:.1f} MB is larger than {max_size} MB")
+
+    extension = os.path.splitext(path)[1].lower()
+    norm_path = os.path.normpath(path)
+    for directory in vector_dirs:
+        inside = norm_path.startswith(os.path.normpath(directory) + os.sep)
+        if inside and extension not in VECTOR_EXTENSIONS:
+            problems.append(f"must be a vector graphic in {directory}")
+
+    if extension in (".jpg", ".jpeg") and is_progressive_jpeg(path):
+        problems.append("is a progressive JPEG, which some engines reject")
+    return problems
+
+
+def search(
+    files: t.List[t.IO[str]],
+    max_size: float,
+    vector_dirs: t.List[str],
+    name_pattern: t.Optional[str],
+) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        directory = os.path.dirname(f.name)
+        search_paths = graphics_paths(text)
+        for cmd in iter_commands(text, ["graphicspath"]):
+            for search_path in graphics_paths(text[cmd.start : cmd.end]):
+                problem = path_problem(directory, search_path)
+                if problem is not None:
+                    found_error = True
+                    message = f"Graphics path {search_path} {problem}"
+                    report(f.name, text, cmd.start, message)
+        for cmd in iter_commands(text, ["includegraphics"]):
+            if not cmd.args:
+                continue
+            name = cmd.args[0].strip()
+            problem = path_problem(directory, name)
+            if problem is not None:
+                found_error = True
+                message = (
+                    f"Image {name} {problem}, use a path relative to the repository "
+                    "and \\graphicspath"
+                )
+                report(f.name, text, cmd.start, message)
+                continue
+            problem = name_problem(f.name, name, name_pattern)
+            if problem is not None:
+                found_error = True
+                report(f.name, text, cmd.start, f"Image name {name} {problem}")
+            path = resolve_image(directory, name, search_paths)
+            if path is None:
+                found_error = True
+                report(f.name, text, cmd.start, f"Image {name} does not exist")
+                continue
+            for problem in check_image(path, max_size, vector_dirs):
+                found_error = True
+                report(f.name, text, cmd.start, f"Image {path} {problem}")
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-size",
+        type=float,
+        default=5,
+        help="Maximum size of an image in MB",
+    )
+    parser.add_argument(
+        "--vector-only",
+        action="append",
+        metavar="DIR",
+        help="Images in this directory must be vector graphics",
+        default=list(),
+    )
+    parser.add_argument(
+        "--name-pattern",
+        metavar="REGEX",
+        help="Image file names without extension must match this regex. "
+        "{file} is replaced by the name of the including file.",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files, args.max_size, args.vector_only, args.name_pattern)
+    if found_error:
+        sys.exit("Found problematic image files")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
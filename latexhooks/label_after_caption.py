@@ -0,0 +1,136 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    brace_depth,
+    iter_commands,
+    iter_environments,
+    line_number,
+    mask_source,
+)
+
+RE_CONTROL_WORD_END = re.compile(r"\\[a-zA-Z@]+$")
+FLOAT_ENVIRONMENTS = [
+    "figure",
+    "figure*",
+    "table",
+    "table*",
+    "subfigure",
+    "subtable",
+    "wrapfigure",
+    "wraptable",
+]
+
+
+def find_misplaced_labels(text: str) -> t.List[t.Tuple[int, int, str, str, int]]:
+    """
+    Return all labels placed before the caption of their float.
+
+    Returns the span of the text to replace, its replacement, the label command, and the
+    offset after the caption, where the label belongs.
+    """
+    masked = mask_source(text)
+    floats = list(iter_environments(masked, FLOAT_ENVIRONMENTS))
+    captions = list(iter_commands(masked, ["caption"]))
+    labels = list(iter_commands(masked, ["label"]))
+    # The caption of \subfloat is an optional argument
+    subfloats = [(cmd.start, cmd.end) for cmd in iter_commands(masked, ["subfloat"])]
+
+    result: t.List[t.Tuple[int, int, str, str, int]] = []
+    for env in floats:
+        nested = [
+            (other.start, other.end)
+            for other in floats
+            if env.body_start <= other.start and other.end <= env.body_end
+        ]
+
+        def is_direct(offset: int) -> bool:
+            return env.body_start <= offset < env.body_end and not any(
+                start <= offset < end for start, end in nested
+            )
+
+        caption = next((c for c in captions if is_direct(c.start)), None)
+        if caption is None:
+            continue
+        depth = brace_depth(masked, env.body_start)
+        for label in labels:
+            if not is_direct(label.start) or label.end > caption.start:
+                continue
+            # Leave malformed labels, e.g., missing the closing brace, alone
+            if not label.args or any(c in label.args[0] for c in "\\\n"):
+                continue
+            # Labels in arguments, like of \subcaptionbox, belong to the command
+            if brace_depth(masked, label.start) != depth or any(
+                start <= label.start < end for start, end in subfloats
+            ):
+                continue
+            start, end = label.start, label.end
+            # Remove the whole line, if the label is the only content
+            line_start = masked.rfind("\n", 0, start) + 1
+            line_end = masked.find("\n", end)
+            line_end = len(masked) if line_end == -1 else line_end
+            before, after = masked[line_start:start], masked[end:line_end]
+            replacement = ""
+            if not before.strip() and not after.strip():
+                start, end = line_start, min(line_end + 1, len(masked))
+            elif RE_CONTROL_WORD_END.search(before) and after[:1].isalpha():
+                # Keep a control word like \l from joining the following letters
+                replacement = " "
+            label_command = text[label.start : label.end]
+            result.append((start, end, replacement, label_command, caption.end))
+    return sorted(result)
+
+
+def search(files: t.List[str], fix: bool) -> bool:
+    found_error = False
+    for file_name in files:
+        with open(file_name, encoding="utf-8") as f:
+            text = f.read()
+        misplaced = find_misplaced_labels(text)
+        for start, _, _, label, _ in misplaced:
+            found_error = True
+            line = line_number(text, start)
+            print(f"{file_name}:{line} {label} must be placed after the \\caption")
+
+        if fix and misplaced:
+            edits = [(start, end, removal) for start, end, removal, _, _ in misplaced]
+            edits += [(offset, offset, label) for _, _, _, label, offset in misplaced]
+            # Replace from the back, such that the offsets stay valid
+            for start, end, replacement in sorted(edits, reverse=True):
+                text = text[:start] + replacement + text[end:]
+            with open(file_name, "w", encoding="utf-8") as f:
+                f.write(text)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--fix",
+        action="store_true",
+        help="Move the labels behind the caption in place",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(sorted(args.files), args.fix)
+    if found_error:
+        sys.exit("Found labels before the caption")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,72 @@
+#!/usr/bin/env python3
+import argparse
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    bibliography_files,
+    check_projects,
+    iter_bib_keys,
+    iter_commands,
+    line_number,
+    read_project,
+    report,
+)
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    bib_names = [f.name for f in files if f.name.endswith(".bib")]
+    texts = read_project([f for f in files if not f.name.endswith(".bib")])
+    for file_name, text in texts:
+        for path in bibliography_files(file_name, text):
+            if path not in bib_names:
+                bib_names.append(path)
+
+    # Citation key mapped to its location
+    keys: t.Dict[str, str] = dict()
+    for bib_name in bib_names:
+        with open(bib_name, encoding="utf-8") as f:
+            bib = f.read()
+        for key, offset in iter_bib_keys(bib):
+            keys.setdefault(key, f"{bib_name}:{line_number(bib, offset)}")
+
+    found_error = False
+    for file_name, text in texts:
+        for cmd in iter_commands(text, ["label"]):
+            if cmd.args and cmd.args[0].strip() in keys:
+                found_error = True
+                label = cmd.args[0].strip()
+                report(
+                    file_name,
+                    text,
+                    cmd.start,
+                    f"Label {label} is also a citation key in {keys[label]}",
+                )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files, args.root, search, "Found labels which are also citation keys"
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,212 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    iter_commands,
+    iter_environments,
+    magic_comments,
+    mask_non_prose,
+    mask_source,
+    report,
+    split_list,
+)
+
+# Common words which are very unlikely to appear in prose of other languages
+STOP_WORDS = {
+    "english": {"the", "and", "of", "is", "that", "with", "which", "are", "this"},
+    "german": {"der", "die", "und", "ist", "nicht", "mit", "dass", "ein", "eine"},
+    "french": {"le", "les", "et", "est", "une", "des", "pour", "avec", "dans"},
+    "spanish": {"el", "los", "las", "y", "es", "una", "para", "con", "por"},
+}
+
+# Map the babel and polyglossia names onto the names used by `STOP_WORDS`
+LANGUAGE_FAMILIES = {
+    "american": "english",
+    "british": "english",
+    "UKenglish": "english",
+    "USenglish": "english",
+    "australian": "english",
+    "canadian": "english",
+    "ngerman": "german",
+    "austrian": "german",
+    "naustrian": "german",
+    "swissgerman": "german",
+    "francais": "french",
+    "acadian": "french",
+    "canadien": "french",
+}
+
+# Map the language codes of `% !TeX spellcheck` magic comments onto language families
+LANGUAGE_CODES = {"en": "english", "de": "german", "fr": "french", "es": "spanish"}
+
+RE_WORD = re.compile(r"[^\W\d_]+")
+
+
+def family(language: str) -> str:
+    return LANGUAGE_FAMILIES.get(language, language)
+
+
+def magic_language(text: str) -> t.Optional[str]:
+    """Return the language selected via `% !TeX spellcheck = en_GB`, if any"""
+    code = magic_comments(text).get("spellcheck")
+    if not code:
+        return None
+    return LANGUAGE_CODES.get(re.split(r"[_-]", code)[0].lower(), code)
+
+
+def loaded_languages(text: str) -> t.Tuple[t.List[str], t.Optional[str]]:
+    """
+    Return all loaded languages and the main language of the document.
+
+    For babel, the last language passed to the package or the document class is the main
+    language, unless `main=` is used.
+    """
+    languages: t.List[str] = []
+    main: t.Optional[str] = None
+
+    class_options: t.List[str] = []
+    for cmd in iter_commands(text, ["documentclass"]):
+        class_options = split_list(cmd.optional)
+    for cmd in iter_commands(text, ["usepackage"]):
+        if not cmd.args or cmd.args[0].strip() != "babel":
+            continue
+        options = class_options + split_list(cmd.optional)
+        for opt in options:
+            if opt.startswith("main="):
+                main = opt[len("main=") :]
+                languages.append(main)
+            elif "=" not in opt:
+                languages.append(opt)
+                if not any(o.startswith("main=") for o in options):
+                    main = opt
+
+    for cmd in iter_commands(text, ["setdefaultlanguage", "setmainlanguage"]):
+        if cmd.args:
+            main = cmd.args[0].strip()
+            languages.append(main)
+    for cmd in iter_commands(text, ["setotherlanguage", "setotherlanguages"]):
+        if cmd.args:
+            languages += [lang.strip() for lang in cmd.args[0].split(",")]
+    return languages, main
+
+
+def guess_language(paragraph: str) -> t.Tuple[t.Optional[str], int]:
+    """Return the language with the most stop words and the number of words"""
+    words = [w.lower() for w in RE_WORD.findall(paragraph)]
+    counts = {
+        language: sum(1 for w in words if w in stop_words)
+        for language, stop_words in STOP_WORDS.items()
+    }
+    best = max(counts, key=lambda language: counts[language])
+    if counts[best] == 0:
+        return None, len(words)
+    return best, len(words)
+
+
+def search(files: t.List[t.IO[str]], min_words: int) -> bool:
+    raw_texts = [(f.name, f.read()) for f in files]
+    texts = [(file_name, mask_source(raw)) for file_name, raw in raw_texts]
+
+    languages: t.Set[str] = set()
+    main: t.Optional[str] = None
+    for _, text in texts:
+        loaded, main_language = loaded_languages(text)
+        languages |= set(loaded)
+        main = main_language or main
+    # Magic comments select the language of individual files
+    magic = {file_name: magic_language(raw) for file_name, raw in raw_texts}
+
+    found_error = False
+    for file_name, text in texts:
+        file_main = magic[file_name] or main
+        if file_main is None:
+            continue
+        # Without the preamble we cannot say which languages are loaded
+        for cmd in iter_commands(text, ["selectlanguage", "foreignlanguage"]):
+            if main is not None and cmd.args and cmd.args[0].strip() not in languages:
+                found_error = True
+                report(
+                    file_name,
+                    text,
+                    cmd.start,
+                    f"Language '{cmd.args[0].strip()}' is used but not loaded",
+                )
+        for env in iter_environments(text, ["otherlanguage", "otherlanguage*"]):
+            arg = text[env.body_start : env.body_end]
+            match = re.match(r"\s*\{([^\{\}]*)\}", arg)
+            if main is not None and match and match[1].strip() not in languages:
+                found_error = True
+                report(
+                    file_name,
+                    text,
+                    env.start,
+                    f"Language '{match[1].strip()}' is used but not loaded",
+                )
+
+        # Text in a different language is expected within otherlanguage environments
+        other = [
+            (env.start, env.end)
+            for env in iter_environments(text, ["otherlanguage", "otherlanguage*"])
+        ]
+        switches = [
+            (cmd.start, cmd.args[0].strip())
+            for cmd in iter_commands(text, ["selectlanguage"])
+            if cmd.args
+        ]
+        prose = mask_non_prose(text)
+        for paragraph in re.finditer(r"\S(?:[^\n]|\n(?!\s*\n))*", prose):
+            if any(start <= paragraph.start() < end for start, end in other):
+                continue
+            current = file_main
+            for offset, language in switches:
+                if offset < paragraph.start():
+                    current = language
+
+            guessed, words = guess_language(paragraph[0])
+            if words < min_words or guessed is None:
+                continue
+            if guessed != family(current):
+                found_error = True
+                report(
+                    file_name,
+                    text,
+                    paragraph.start(),
+                    f"Paragraph looks like {guessed}, but the language is {current}",
+                )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--min-words",
+        type=int,
+        default=30,
+        help="Minimum number of words in a paragraph before its language is guessed",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    files = sorted(args.files, key=lambda f: f.name)
+    found_error = search(files, args.min_words)
+    if found_error:
+        sys.exit("Found text in languages which are not active")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,71 @@
+#!/usr/bin/env python3
+import argparse
+import difflib
+import os
+import subprocess
+import sys
+import typing as t
+
+
+def format_file(file_name: str, settings: t.Optional[str]) -> str:
+    """Return the content of the file as formatted by latexindent"""
+    cmd = ["latexindent", "--silent", f"--logfile={os.devnull}"]
+    if settings is not None:
+        cmd.append(f"--local={settings}")
+    cmd.append(file_name)
+    try:
+        result = subprocess.run(cmd, capture_output=True, encoding="utf-8", check=True)
+    except FileNotFoundError:
+        sys.exit("latexindent is not installed or cannot be found in PATH")
+    except subprocess.CalledProcessError as err:
+        sys.exit(f"latexindent failed for {file_name}: {err.stderr.strip()}")
+    return result.stdout
+
+
+def search(files: t.List[str], settings: t.Optional[str]) -> bool:
+    found_unformatted = False
+    for file_name in files:
+        with open(file_name, encoding="utf-8") as f:
+            original = f.read()
+        formatted = format_file(file_name, settings)
+        if original != formatted:
+            found_unformatted = True
+            sys.stdout.writelines(
+                difflib.unified_diff(
+                    original.splitlines(keepends=True),
+                    formatted.splitlines(keepends=True),
+                    fromfile=file_name,
+                    tofile=f"{file_name} (formatted)",
+                )
+            )
+    return found_unformatted
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--local-settings",
+        metavar="FILE",
+        help="YAML file with settings for latexindent, passed as --local",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        nargs="+",
+        help="List of filenames to check",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_unformatted = search(sorted(args.files), args.local_settings)
+    if found_unformatted:
+        sys.exit("Found files which are not formatted with latexindent")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,116 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    blank_spans,
+    check_projects,
+    iter_commands,
+    mask_non_prose,
+    read_project,
+    report,
+)
+
+# Macro name and the literal it replaces
+MACROS = {
+    "ie": re.compile(r"\bi\.\s?e\.(?:,|(?=\W))"),
+    "eg": re.compile(r"\be\.\s?g\.(?:,|(?=\W))"),
+    "etal": re.compile(r"\bet\s+al\.(?=\W|$)"),
+}
+
+DEFINING_COMMANDS = [
+    "newcommand",
+    "renewcommand",
+    "providecommand",
+    "DeclareRobustCommand",
+]
+RE_DEF = re.compile(r"\\def\s*\\(\w+)[^\{]*\{[^\{\}]*\}")
+
+
+def defined_macros(text: str) -> t.Tuple[t.Set[str], t.List[t.Tuple[int, int]]]:
+    """Return the defined abbreviation macros and the spans of all definitions"""
+    macros: t.Set[str] = set()
+    spans: t.List[t.Tuple[int, int]] = []
+    for cmd in iter_commands(text, DEFINING_COMMANDS, nargs=2):
+        if cmd.args:
+            name = cmd.args[0].strip().lstrip("\\")
+            if name in MACROS:
+                macros.add(name)
+            spans.append((cmd.start, cmd.end))
+    for match in RE_DEF.finditer(text):
+        if match[1] in MACROS:
+            macros.add(match[1])
+        spans.append(match.span())
+    return macros, spans
+
+
+def normalize(literal: str) -> str:
+    """Describe the punctuation style of a literal, e.g., `i.e.,` or `i. e.`"""
+    return re.sub(r"\s+", " ", literal)
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    texts = read_project(files)
+
+    macros: t.Set[str] = set()
+    prose: t.List[t.Tuple[str, str, str]] = []
+    for file_name, text in texts:
+        defined, spans = defined_macros(text)
+        macros |= defined
+        prose.append((file_name, text, mask_non_prose(blank_spans(text, spans))))
+
+    found_error = False
+    for macro, regex in MACROS.items():
+        # Group all literal occurrences by their punctuation style
+        styles: t.Dict[str, t.List[t.Tuple[str, str, int]]] = dict()
+        for file_name, text, masked in prose:
+            for match in regex.finditer(masked):
+                location = (file_name, text, match.start())
+                styles.setdefault(normalize(match[0]), list()).append(location)
+
+        if macro in macros:
+            for literal, locations in styles.items():
+                for file_name, text, offset in locations:
+                    found_error = True
+                    message = f"Use \\{macro} instead of '{literal}'"
+                    report(file_name, text, offset, message)
+        elif len(styles) > 1:
+            # Suggest the most common style
+            common = max(styles, key=lambda style: len(styles[style]))
+            for literal, locations in styles.items():
+                if literal == common:
+                    continue
+                for file_name, text, offset in locations:
+                    found_error = True
+                    message = f"Use '{common}' instead of '{literal}'"
+                    report(file_name, text, offset, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files, args.root, search, "Found inconsistent latin abbreviations"
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
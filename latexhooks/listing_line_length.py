@@ -0,0 +1,99 @@
+#!/usr/bin/env python3
+import argparse
+import os
+import sys
+import typing as t
+
+from latexhooks.common import (
+    iter_commands,
+    iter_environments,
+    line_number,
+    mask_comments,
+    mask_source,
+)
+
+LISTINGS = ["lstlisting", "minted", "Verbatim"]
+
+
+def check_lines(file_name: str, first_line: int, code: str, max_width: int) -> bool:
+    found_long_line = False
+    for idx, line in enumerate(code.splitlines()):
+        width = len(line.rstrip().expandtabs())
+        if width > max_width:
+            found_long_line = True
+            print(
+                f"{file_name}:{first_line + idx} Listing line has {width} characters, "
+                f"the maximum is {max_width}"
+            )
+    return found_long_line
+
+
+def search(files: t.List[t.IO[str]], max_width: int) -> bool:
+    found_long_line = False
+    for f in files:
+        text = f.read()
+        # Commented out listings are not typeset, but the code itself may contain %
+        for env in iter_environments(mask_comments(text), LISTINGS):
+            # Skip the remainder of the \begin line, which contains the options
+            body_start = text.find("\n", env.body_start, env.body_end)
+            if body_start == -1:
+                continue
+            code = text[body_start + 1 : env.body_end]
+            first_line = line_number(text, body_start + 1)
+            found_long_line |= check_lines(f.name, first_line, code, max_width)
+
+        # Listings from external files are resolved relative to the including file
+        directory = os.path.dirname(f.name)
+        masked = mask_source(text)
+        included = [
+            cmd.args[-1]
+            for cmd in iter_commands(masked, ["lstinputlisting"])
+            if cmd.args
+        ] + [
+            cmd.args[-1]
+            for cmd in iter_commands(masked, ["inputminted"], nargs=2)
+            if len(cmd.args) == 2
+        ]
+        for path in included:
+            path = os.path.join(directory, path.strip())
+            try:
+                with open(path, encoding="utf-8") as listing:
+                    code = listing.read()
+            except OSError as err:
+                print(f"{f.name}: Cannot read listing {path}: {err}")
+                found_long_line = True
+                continue
+            found_long_line |= check_lines(path, 1, code, max_width)
+    return found_long_line
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-width",
+        type=int,
+        default=80,
+        help="Maximum number of characters per line in a code listing",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_long_line = search(args.files, args.max_width)
+    if found_long_line:
+        sys.exit("Found code listings with overlong lines")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,78 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import iter_environments, mask_source, report, table_columns
+
+MULTIPAGE_ENVIRONMENTS = ["longtable", "xltabular"]
+
+RE_BLOCK_END = re.compile(r"\\(endfirsthead|endhead|endfoot|endlastfoot)(?![a-zA-Z@])")
+RE_BOOKTABS_RULE = re.compile(r"\\(toprule|midrule|bottomrule)(?![a-zA-Z@])")
+
+
+def check_table(text: str, start: int, end: int) -> t.List[str]:
+    """Return all problems with the header and footer blocks of a multi-page table"""
+    # Content of each block, keyed by the command ending it
+    blocks: t.Dict[str, str] = dict()
+    block_start = start
+    for match in RE_BLOCK_END.finditer(text, start, end):
+        blocks[match[1]] = text[block_start : match.start()]
+        block_start = match.end()
+    booktabs = RE_BOOKTABS_RULE.search(text, start, end) is not None
+
+    problems: t.List[str] = []
+    if "endhead" not in blocks:
+        problems.append("has no \\endhead, continuation pages lose the header")
+    elif booktabs:
+        rules = {m[1] for m in RE_BOOKTABS_RULE.finditer(blocks["endhead"])}
+        for rule in ["toprule", "midrule"]:
+            if rule not in rules:
+                problems.append(f"repeats the header without \\{rule}")
+
+    if "endfoot" not in blocks and "endlastfoot" not in blocks:
+        problems.append("has no \\endfoot, continuation pages end without a rule")
+    elif booktabs:
+        last_foot = blocks.get("endlastfoot", blocks.get("endfoot", ""))
+        if "\\bottomrule" not in last_foot:
+            problems.append("ends without \\bottomrule in the last footer")
+    return problems
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        for env in iter_environments(text, MULTIPAGE_ENVIRONMENTS):
+            _, body_start = table_columns(text, env)
+            for problem in check_table(text, body_start, env.body_end):
+                found_error = True
+                report(f.name, text, env.start, f"{env.name} {problem}")
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files)
+    if found_error:
+        sys.exit("Found multi-page tables without proper headers or footers")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,119 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    VERBATIM_ENVIRONMENTS,
+    Definition,
+    add_root_argument,
+    blank_spans,
+    check_projects,
+    iter_definitions,
+    iter_environments,
+    parse_group,
+    read_project,
+    report,
+)
+
+
+def required_arguments(definition: Definition) -> int:
+    """Number of mandatory arguments, the optional first argument does not count"""
+    if definition.default is not None:
+        return definition.nargs - 1
+    return definition.nargs
+
+
+def count_arguments(text: str, pos: int, optional: bool, wanted: int) -> int:
+    """Count the brace groups following a macro call at `pos`, up to `wanted`"""
+    if optional:
+        group = parse_group(text, pos, "[", "]")
+        if group is not None:
+            pos = group[1] + 1
+    count = 0
+    while count < wanted:
+        group = parse_group(text, pos)
+        if group is None:
+            break
+        count += 1
+        pos = group[1] + 1
+    return count
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    texts = read_project(files)
+    masked: t.List[t.Tuple[str, str]] = []
+    for file_name, text in texts:
+        verbatim = [
+            (env.start, env.end)
+            for env in iter_environments(text, VERBATIM_ENVIRONMENTS)
+        ]
+        masked.append((file_name, blank_spans(text, verbatim)))
+
+    definitions: t.Dict[str, t.List[Definition]] = dict()
+    # Everything before the body of a definition, where the macro name is not a call
+    headers: t.Dict[str, t.List[t.Tuple[int, int]]] = dict()
+    for file_name, text in masked:
+        for definition in iter_definitions(text):
+            definitions.setdefault(definition.name, list()).append(definition)
+            body_start = definition.end - len(definition.body) - 1
+            headers.setdefault(file_name, list()).append((definition.start, body_start))
+
+    # Macros with mandatory arguments, whether they have an optional one, and the count
+    macros = {
+        name: (
+            any(d.default is not None for d in defs),
+            min(required_arguments(d) for d in defs),
+        )
+        for name, defs in definitions.items()
+    }
+    macros = {name: macro for name, macro in macros.items() if macro[1] > 0}
+    if not macros:
+        return False
+    pattern = re.compile(
+        r"\\(" + "|".join(re.escape(name) for name in macros) + r")(?![a-zA-Z@])"
+    )
+
+    found_error = False
+    for file_name, text in masked:
+        for match in pattern.finditer(text):
+            if any(
+                start <= match.start() < end
+                for start, end in headers.get(file_name, [])
+            ):
+                continue
+            optional, wanted = macros[match[1]]
+            count = count_arguments(text, match.end(), optional, wanted)
+            if count < wanted:
+                found_error = True
+                plural = "argument" if wanted == 1 else "arguments"
+                message = f"{match[0]} takes {wanted} {plural}, but got {count}"
+                report(file_name, text, match.start(), message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files, args.root, search, "Found macro calls with too few arguments"
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,74 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    check_projects,
+    iter_commands,
+    line_number,
+    read_project,
+)
+
+# Commands typesetting an upright identifier in math mode
+FONT_COMMANDS = ["mathrm", "text", "operatorname"]
+
+# Only single words are identifiers, unlike "\text{for all }"
+RE_IDENTIFIER = re.compile(r"[^\W_]+")
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    texts = read_project(files)
+
+    # Identifier mapped to the font commands and all places using them
+    identifiers: t.Dict[str, t.Dict[str, t.List[t.Tuple[str, int]]]] = dict()
+    for file_name, text in texts:
+        for cmd in iter_commands(text, FONT_COMMANDS):
+            if not cmd.args:
+                continue
+            identifier = cmd.args[0].strip()
+            if not RE_IDENTIFIER.fullmatch(identifier):
+                continue
+            # \operatorname* only changes the placement of limits
+            variant = f"\\{cmd.name}"
+            location = (file_name, line_number(text, cmd.start))
+            variants = identifiers.setdefault(identifier, dict())
+            variants.setdefault(variant, list()).append(location)
+
+    found_error = False
+    for identifier, variants in identifiers.items():
+        if len(variants) > 1:
+            found_error = True
+            print(f"Found different math fonts for '{identifier}', define a macro")
+            for variant, locations in variants.items():
+                print(f"  {variant}{{{identifier}}}")
+                for file_name, line in locations:
+                    print(f"    {file_name}:{line}")
+            print()
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(args.files, args.root, search, "Found inconsistent math fonts")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
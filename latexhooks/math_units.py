@@ -0,0 +1,77 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    MATH_ENVIRONMENTS,
+    RE_INLINE_MATH,
+    UNITS,
+    add_root_argument,
+    check_projects,
+    iter_environments,
+    read_project,
+    report,
+)
+
+# A number followed by a unit, like "5ms", "10\,GB", or "3\,\mathrm{kHz}"
+RE_MATH_UNIT = re.compile(
+    r"(?<![\w.\\])\d+(?:[.,]\d+)?"
+    r"(?P<space>\s*(?:\\[,;:! ]|~)?\s*)"
+    r"(?P<font>\\(?:mathrm|text|textrm|mathup)\s*\{\s*)?"
+    r"(?P<unit>" + "|".join(UNITS) + r")(?![a-zA-Z])"
+)
+
+
+def math_spans(text: str) -> t.List[t.Tuple[int, int]]:
+    """Return the spans of inline math and math environments"""
+    spans = [m.span() for m in RE_INLINE_MATH.finditer(text)]
+    spans += [
+        (env.body_start, env.body_end)
+        for env in iter_environments(text, MATH_ENVIRONMENTS)
+    ]
+    return spans
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    texts = read_project(files)
+
+    found_error = False
+    for file_name, text in texts:
+        for start, end in sorted(set(math_spans(text))):
+            for match in RE_MATH_UNIT.finditer(text, start, end):
+                # "2m" is more likely a product with the variable m than meters
+                explicit = match["font"] or match["space"].strip()
+                if len(match["unit"]) == 1 and not explicit:
+                    continue
+                found_error = True
+                message = f"Unit {match['unit']} written manually in math, use \\qty"
+                report(file_name, text, match.start(), message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files, args.root, search, "Found manually written units in math"
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
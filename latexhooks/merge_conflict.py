@@ -0,0 +1,78 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import VERBATIM_ENVIRONMENTS, iter_environments
+
+RE_CONFLICT_START = re.compile(r"^<<<<<<<(?: |$)")
+RE_CONFLICT_BASE = re.compile(r"^\|\|\|\|\|\|\|(?: |$)")
+RE_CONFLICT_SEPARATOR = re.compile(r"^=======$")
+RE_CONFLICT_END = re.compile(r"^>>>>>>>(?: |$)")
+
+
+def verbatim_lines(text: str) -> t.Set[int]:
+    """Return the 0-based numbers of all lines inside of verbatim environments"""
+    lines: t.Set[int] = set()
+    for env in iter_environments(text, VERBATIM_ENVIRONMENTS):
+        first = text.count("\n", 0, env.body_start)
+        last = text.count("\n", 0, env.body_end)
+        lines |= set(range(first, last + 1))
+    return lines
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    found_marker = False
+    for f in files:
+        text = f.read()
+        # BibTeX has no verbatim environments
+        skipped = set() if f.name.endswith(".bib") else verbatim_lines(text)
+
+        in_conflict = False
+        for line_number, line in enumerate(text.splitlines()):
+            if line_number in skipped:
+                continue
+            marker = None
+            if RE_CONFLICT_START.match(line):
+                in_conflict = True
+                marker = "<<<<<<<"
+            elif RE_CONFLICT_END.match(line):
+                in_conflict = False
+                marker = ">>>>>>>"
+            # The other markers are only unambiguous within a conflict
+            elif in_conflict and RE_CONFLICT_BASE.match(line):
+                marker = "|||||||"
+            elif in_conflict and RE_CONFLICT_SEPARATOR.match(line):
+                marker = "======="
+
+            if marker is not None:
+                found_marker = True
+                print(f"{f.name}:{line_number + 1} Merge conflict marker {marker}")
+    return found_marker
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_marker = search(args.files)
+    if found_marker:
+        sys.exit("Found merge conflict markers")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
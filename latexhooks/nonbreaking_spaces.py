@@ -0,0 +1,165 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import UNITS, mask_non_prose, mask_source, report
+
+CHECKS = ["references", "units", "superfluous"]
+
+# Names which are followed by a number from `\ref`, abbreviations are matched as is
+REFERENCE_NAMES = [
+    "Algorithm",
+    "Appendix",
+    "Chapter",
+    "Corollary",
+    "Definition",
+    "Equation",
+    "Figure",
+    "Lemma",
+    "Line",
+    "Listing",
+    "Page",
+    "Part",
+    "Section",
+    "Table",
+    "Theorem",
+    "Alg.",
+    "Chap.",
+    "Def.",
+    "Eq.",
+    "Fig.",
+    "Figs.",
+    "Sec.",
+    "Tab.",
+]
+# Whitespace which may be replaced by a tie, i.e., no paragraph break
+WHITESPACE = r"(?:[ \t]*\n[ \t]*|[ \t]+)"
+LONG_UNITS = sorted((unit for unit in UNITS if len(unit) > 1), key=len, reverse=True)
+# Single letters are often words or names, like in "2 A students" or "3 B-trees", so
+# they only count as units if no word follows
+SHORT_UNITS = [unit for unit in UNITS if len(unit) == 1]
+RE_NUMBER_UNIT = re.compile(
+    rf"(?<![\w.,])\d+(?:[.,]\d+)?(?P<space>{WHITESPACE})"
+    rf"(?P<unit>(?:{'|'.join(LONG_UNITS)})(?!\w)"
+    rf"|(?:{'|'.join(SHORT_UNITS)})(?![\w-])(?!\s*[^\W\d_]))"
+)
+# The second word is a lookahead, such that chains like "a~b~c" find both ties
+RE_TIE = re.compile(r"\b(?P<first>[a-z]+)~(?=(?P<second>[a-z]+)\b)")
+# Ties between lowercase words which are customary
+ALLOWED_TIES = [("et", "al")]
+
+
+def reference_pattern(names: t.List[str]) -> t.Pattern[str]:
+    words = [re.escape(n) + ("" if n.endswith(".") else "s?") for n in names]
+    return re.compile(
+        rf"(?<![\w\\])(?i:(?P<name>{'|'.join(words)}))(?P<space>{WHITESPACE})"
+        r"\\(?:ref|pageref|eqref|vref)\b"
+    )
+
+
+def in_prose(text: str, prose: str, offset: int) -> bool:
+    return prose[offset] == text[offset]
+
+
+def find_ties(
+    text: str, checks: t.List[str], names: t.List[str]
+) -> t.List[t.Tuple[int, int, str, str]]:
+    """Return the spans of all misused spaces with their replacement and a message"""
+    prose = mask_non_prose(text)
+    findings: t.List[t.Tuple[int, int, str, str]] = []
+    if "references" in checks:
+        for match in reference_pattern(names).finditer(mask_source(text)):
+            if in_prose(text, prose, match.start()):
+                start, end = match.span("space")
+                message = f"Missing ~ between '{match['name']}' and the reference"
+                findings.append((start, end, "~", message))
+    if "units" in checks:
+        for match in RE_NUMBER_UNIT.finditer(text):
+            if in_prose(text, prose, match.start()) and in_prose(
+                text, prose, match.start("unit")
+            ):
+                start, end = match.span("space")
+                message = f"Missing ~ between the number and the unit {match['unit']}"
+                findings.append((start, end, "~", message))
+    if "superfluous" in checks:
+        for match in RE_TIE.finditer(text):
+            first, second = match["first"], match["second"]
+            # Ties after single letters are customary in some languages, e.g., Czech
+            if len(first) == 1 or (first, second) in ALLOWED_TIES:
+                continue
+            if in_prose(text, prose, match.start()) and in_prose(
+                text, prose, match.end("second") - 1
+            ):
+                start = match.start() + len(first)
+                message = f"Superfluous ~ between '{first}' and '{second}'"
+                findings.append((start, start + 1, " ", message))
+    return sorted(findings)
+
+
+def search(
+    files: t.List[str], checks: t.List[str], names: t.List[str], fix: bool
+) -> bool:
+    found_error = False
+    for file_name in files:
+        with open(file_name, encoding="utf-8") as f:
+            text = f.read()
+        findings = find_ties(text, checks, names)
+        for start, _, _, message in findings:
+            found_error = True
+            report(file_name, text, start, message)
+
+        if fix and findings:
+            # Replace from the back, such that the offsets stay valid
+            for start, end, replacement, _ in reversed(findings):
+                text = text[:start] + replacement + text[end:]
+            with open(file_name, "w", encoding="utf-8") as f:
+                f.write(text)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--disable",
+        action="append",
+        choices=CHECKS,
+        default=list(),
+        help="Check which is not performed. Can be given multiple times.",
+    )
+    parser.add_argument(
+        "--name",
+        action="append",
+        metavar="NAME",
+        default=list(),
+        help="Additional name before a \\ref which needs a ~, e.g., Claim",
+    )
+    parser.add_argument(
+        "--fix",
+        action="store_true",
+        help="Insert the missing ~ and replace the superfluous ones in place",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    checks = [check for check in CHECKS if check not in args.disable]
+    names = REFERENCE_NAMES + args.name
+    found_error = search(sorted(args.files), checks, names, args.fix)
+    if found_error:
+        sys.exit("Found misused or missing non-breaking spaces")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,53 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import mask_non_prose, report
+
+# A range of two numbers separated by a single hyphen, but no dates like 2020-01-31
+RE_NUMBER_RANGE = re.compile(r"(?<![\w.,-])\d+(?:[.,]\d+)?-\d+(?:[.,]\d+)?(?![\w-])")
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    found_range = False
+    for f in files:
+        text = f.read()
+        for match in RE_NUMBER_RANGE.finditer(mask_non_prose(text)):
+            found_range = True
+            start, end = match[0].split("-")
+            report(
+                f.name,
+                text,
+                match.start(),
+                f"Number range '{match[0]}' uses a hyphen, "
+                f"use '{start}--{end}' or '\\numrange{{{start}}}{{{end}}}'",
+            )
+    return found_range
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_range = search(args.files)
+    if found_range:
+        sys.exit("Found number ranges written with a hyphen")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
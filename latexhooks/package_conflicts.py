@@ -0,0 +1,132 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    ENGINE_PACKAGES,
+    iter_packages,
+    line_number,
+    magic_program,
+    mask_source,
+    split_list,
+)
+
+# Packages which cannot or should not be loaded together
+CONFLICTS = [
+    ("subfigure", "subcaption"),
+    ("subfig", "subcaption"),
+    ("subfigure", "subfig"),
+    ("enumitem", "paralist"),
+    ("enumitem", "enumerate"),
+    ("natbib", "biblatex"),
+    ("cite", "biblatex"),
+    ("caption2", "caption"),
+    ("fontspec", "inputenc"),
+    ("epsfig", "graphicx"),
+]
+
+RE_COMMENT = re.compile(r"(?<!\\)(?:\\\\)*%([^\n]*)")
+
+
+def search(files: t.List[t.IO[str]], conflicts: t.List[t.Tuple[str, str]]) -> bool:
+    # Package name to all places where it is loaded and the options used there
+    packages: t.Dict[str, t.List[t.Tuple[str, int, t.List[str]]]] = dict()
+    # The same for packages loaded in commented-out lines
+    commented: t.Dict[str, t.List[t.Tuple[str, int, t.List[str]]]] = dict()
+    # Engine selected via a magic comment and where it is selected
+    program: t.Optional[t.Tuple[str, str]] = None
+    for f in files:
+        raw = f.read()
+        selected = magic_program(raw)
+        if program is None and selected is not None:
+            program = (selected, f.name)
+        text = mask_source(raw)
+        for cmd, package in iter_packages(text):
+            location = (f.name, line_number(text, cmd.start), split_list(cmd.optional))
+            packages.setdefault(package, list()).append(location)
+        for comment in RE_COMMENT.finditer(raw):
+            for cmd, package in iter_packages(comment[1]):
+                line = line_number(raw, comment.start())
+                location = (f.name, line, split_list(cmd.optional))
+                commented.setdefault(package, list()).append(location)
+
+    found_conflict = False
+    for package, locations in packages.items():
+        options = {tuple(sorted(opts)) for _, _, opts in locations}
+        if len(options) > 1:
+            found_conflict = True
+            print(f"Package {package} is loaded multiple times with different options")
+            for file_name, line, opts in locations:
+                print(f"  {file_name}:{line} [{','.join(opts)}]")
+            print()
+
+    # Collaborators uncommenting different lines end up with different documents
+    for package, locations in commented.items():
+        active = {tuple(sorted(opts)) for _, _, opts in packages.get(package, [])}
+        differing = [loc for loc in locations if tuple(sorted(loc[2])) not in active]
+        if active and differing:
+            found_conflict = True
+            print(f"Package {package} is also loaded with other options in a comment")
+            for file_name, line, opts in packages[package]:
+                print(f"  {file_name}:{line} [{','.join(opts)}]")
+            for file_name, line, opts in differing:
+                print(f"  {file_name}:{line} [{','.join(opts)}] (commented out)")
+            print()
+
+    for first, second in conflicts:
+        if first in packages and second in packages:
+            found_conflict = True
+            print(f"Packages {first} and {second} conflict with each other")
+            for package in (first, second):
+                for file_name, line, _ in packages[package]:
+                    print(f"  {file_name}:{line} {package}")
+            print()
+
+    for package, engines in ENGINE_PACKAGES.items():
+        if program is not None and package in packages and program[0] not in engines:
+            found_conflict = True
+            print(
+                f"Package {package} requires {' or '.join(engines)}, "
+                f"but {program[1]} selects {program[0]}"
+            )
+            for file_name, line, _ in packages[package]:
+                print(f"  {file_name}:{line} {package}")
+            print()
+    return found_conflict
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--conflict",
+        action="append",
+        metavar=("package1,package2"),
+        type=lambda x: tuple(x.split(",", 1)),
+        help="Additional pair of packages which must not be loaded together",
+        default=list(),
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    files = sorted(args.files, key=lambda f: f.name)
+    found_conflict = search(files, CONFLICTS + args.conflict)
+    if found_conflict:
+        sys.exit("Found conflicting packages")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,68 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    check_projects,
+    document_body,
+    is_local,
+    read_masked,
+    report,
+)
+
+# \setlength{\parindent}{0pt}, \addtolength\parskip{1ex}, or \parindent=0pt
+RE_PARAGRAPH_LENGTH = re.compile(
+    r"\\(?:setlength|addtolength)\s*\{?\s*\\(parindent|parskip)(?![a-zA-Z@])"
+    r"|\\(parindent|parskip)(?=\s*=?\s*[-+\d.\\])"
+)
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    found_error = False
+    for file_name, (start, _) in sorted(document_body(files).items()):
+        text = read_masked(file_name)
+        for match in RE_PARAGRAPH_LENGTH.finditer(text, start):
+            # Changes inside a group or environment do not leak into the following text
+            if is_local(text, match.start()):
+                continue
+            found_error = True
+            length = match[1] or match[2]
+            report(
+                file_name,
+                text,
+                match.start(),
+                f"\\{length} changed in the document body, set it in the preamble",
+            )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        search,
+        "Found paragraph length changes in the document body",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
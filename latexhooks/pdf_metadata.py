@@ -0,0 +1,142 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    blank_spans,
+    check_projects,
+    iter_commands,
+    iter_packages,
+    read_project,
+    report,
+)
+
+# Commands in metadata which break the PDF strings, but not `\&` or `\%`
+RE_METADATA_COMMAND = re.compile(r"\\[a-zA-Z@]+")
+
+METADATA_KEYS = ["pdftitle", "pdfauthor", "pdfsubject", "pdfkeywords", "pdfcreator"]
+
+
+def iter_keyvals(text: str) -> t.Iterator[t.Tuple[str, str, int]]:
+    """Split a key-value list into key, value, and offset of the value"""
+    depth = 0
+    start = 0
+    for idx, c in enumerate(text + ","):
+        if c == "{":
+            depth += 1
+        elif c == "}":
+            depth -= 1
+        elif c == "," and depth == 0:
+            key, _, value = text[start:idx].partition("=")
+            if key.strip():
+                offset = start + len(key) + 1
+                value_start = offset + len(value) - len(value.lstrip())
+                yield key.strip(), value.strip(), value_start
+            start = idx + 1
+
+
+def link_style(keys: t.Dict[str, str]) -> str:
+    """Return how hyperref renders links given the options"""
+    if keys.get("hidelinks", "false") != "false":
+        return "hidelinks"
+    if keys.get("colorlinks", "false") != "false":
+        return "colorlinks"
+    return "boxes"
+
+
+def search(files: t.List[t.IO[str]], required: t.List[str], links: str) -> bool:
+    texts = read_project(files)
+
+    # Location of the first \usepackage{hyperref}
+    hyperref: t.Optional[t.Tuple[str, str, int]] = None
+    # Options of hyperref and \hypersetup, with the value "true" for flags
+    keys: t.Dict[str, str] = dict()
+    found_error = False
+    for file_name, text in texts:
+        for cmd, package in iter_packages(text):
+            if package != "hyperref":
+                continue
+            if hyperref is None:
+                hyperref = (file_name, text, cmd.start)
+            for option in cmd.optional:
+                for key, value, _ in iter_keyvals(option):
+                    keys[key] = value or "true"
+
+        for cmd in iter_commands(text, ["hypersetup"]):
+            if not cmd.args:
+                continue
+            for key, value, offset in iter_keyvals(cmd.args[0]):
+                keys[key] = value or "true"
+                spans = [
+                    (c.start, c.end)
+                    for c in iter_commands(value, ["texorpdfstring"], nargs=2)
+                ]
+                match = RE_METADATA_COMMAND.search(blank_spans(value, spans))
+                if key in METADATA_KEYS and match:
+                    found_error = True
+                    message = (
+                        f"Command {match[0]} in {key} breaks the PDF metadata, "
+                        "use \\texorpdfstring or plain text"
+                    )
+                    offset = cmd.arg_offsets[0] + offset + match.start()
+                    report(file_name, text, offset, message)
+
+    if hyperref is None:
+        return found_error
+
+    file_name, text, offset = hyperref
+    for key in required:
+        if not keys.get(key, "").strip("{} "):
+            found_error = True
+            report(file_name, text, offset, f"Set {key} via \\hypersetup")
+    style = link_style(keys)
+    if links != "any" and style != links:
+        found_error = True
+        report(file_name, text, offset, f"Links use {style}, but {links} is required")
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--require",
+        action="append",
+        metavar="KEY",
+        help="Metadata key which must be set. Can be given multiple times.",
+        default=list(),
+    )
+    parser.add_argument(
+        "--links",
+        choices=["any", "hidelinks", "colorlinks", "boxes"],
+        default="any",
+        help="Required appearance of links",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    require = args.require or ["pdftitle", "pdfauthor"]
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, require, args.links),
+        "Found incomplete or broken PDF metadata",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
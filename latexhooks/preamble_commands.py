@@ -0,0 +1,56 @@
+#!/usr/bin/env python3
+import argparse
+import sys
+import typing as t
+
+from latexhooks.common import document_body, is_root, iter_commands, read_masked, report
+
+PREAMBLE_COMMANDS = ["usepackage", "RequirePackage", "documentclass"]
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    body = document_body(files)
+
+    found_error = False
+    for file_name, (_, parent) in sorted(body.items()):
+        if parent == file_name:
+            continue
+        text = read_masked(file_name)
+        # Documents using the subfiles package have their own preamble
+        if is_root(text):
+            continue
+        for cmd in iter_commands(text, PREAMBLE_COMMANDS):
+            found_error = True
+            report(
+                file_name,
+                text,
+                cmd.start,
+                f"\\{cmd.name} in a file included into the document body of {parent}",
+            )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files)
+    if found_error:
+        sys.exit("Found preamble commands outside of the preamble")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
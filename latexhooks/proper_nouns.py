@@ -0,0 +1,82 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    blank_spans,
+    iter_commands,
+    mask_non_prose,
+    read_wordlist,
+    report,
+)
+
+# Commands containing code, which keeps its own capitalization
+CODE_COMMANDS = ["texttt", "lstinline", "verb", "mintinline", "code"]
+
+
+def search(files: t.List[t.IO[str]], nouns: t.List[str]) -> bool:
+    regexes = [
+        (noun, re.compile(r"(?<![\w-])" + re.escape(noun) + r"(?![\w-])", re.I))
+        for noun in nouns
+    ]
+
+    found_error = False
+    for f in files:
+        text = f.read()
+        code = [(cmd.start, cmd.end) for cmd in iter_commands(text, CODE_COMMANDS)]
+        prose = mask_non_prose(blank_spans(text, code))
+        for noun, regex in regexes:
+            for match in regex.finditer(prose):
+                if match[0] != noun:
+                    found_error = True
+                    message = f"Use '{noun}' instead of '{match[0]}'"
+                    report(f.name, text, match.start(), message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--noun",
+        action="append",
+        metavar="NOUN",
+        help="Proper noun with its correct capitalization",
+        default=list(),
+    )
+    parser.add_argument(
+        "--nouns-file",
+        action="append",
+        metavar="FILE",
+        help="File with proper nouns, one per line. Lines starting with # are ignored.",
+        default=list(),
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    nouns = list(args.noun)
+    for path in args.nouns_file:
+        nouns += read_wordlist(path)
+    if len(nouns) == 0:
+        sys.exit("No proper nouns specified. See --help for how to use them.")
+
+    found_error = search(args.files, nouns)
+    if found_error:
+        sys.exit("Found proper nouns with wrong capitalization")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,149 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    blank_spans,
+    check_projects,
+    count_sentences,
+    iter_commands,
+    mask_non_prose,
+    mask_source,
+    read_project,
+    report,
+    split_sections,
+)
+
+RE_WORD = re.compile(r"[^\W\d_]+(?:['’-][^\W\d_]+)*")
+RE_VOWEL_GROUP = re.compile(r"[aeiouy]+")
+
+SECTIONING_COMMANDS = ["chapter", "section"]
+# Headings are not sentences and would be counted as part of the following one
+HEADING_COMMANDS = [
+    "part",
+    "chapter",
+    "section",
+    "subsection",
+    "subsubsection",
+    "paragraph",
+    "subparagraph",
+]
+
+
+def syllables(word: str) -> int:
+    """Estimate the number of syllables of an English word"""
+    word = word.lower()
+    if word.endswith("e") and not word.endswith("le"):
+        word = word[:-1]
+    return max(1, len(RE_VOWEL_GROUP.findall(word)))
+
+
+def metrics(prose: str) -> t.Optional[t.Tuple[float, float]]:
+    """Return the average sentence length and the Flesch-Kincaid grade level"""
+    words = RE_WORD.findall(prose)
+    if not words:
+        return None
+    sentences = max(1, count_sentences(prose))
+    words_per_sentence = len(words) / sentences
+    syllables_per_word = sum(syllables(w) for w in words) / len(words)
+    grade = 0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59
+    return words_per_sentence, grade
+
+
+def mask_headings(text: str) -> str:
+    """Replace the sectioning commands and their titles with spaces"""
+    headings = iter_commands(mask_source(text), HEADING_COMMANDS)
+    return blank_spans(text, [(cmd.start, cmd.end) for cmd in headings])
+
+
+def search(
+    files: t.List[t.IO[str]],
+    max_sentence_length: float,
+    max_grade: float,
+    show_metrics: bool,
+) -> bool:
+    texts = read_project(files)
+    prose = {name: mask_non_prose(mask_headings(text)) for name, text in texts}
+
+    found_error = False
+    for section in split_sections(texts, SECTIONING_COMMANDS):
+        result = metrics(prose[section.file_name][section.start : section.end])
+        if result is None:
+            continue
+        words_per_sentence, grade = result
+        title = section.title or "(no section)"
+        if show_metrics:
+            print(
+                f"{title}: {words_per_sentence:.1f} words per sentence, "
+                f"Flesch-Kincaid grade {grade:.1f}"
+            )
+        if words_per_sentence > max_sentence_length:
+            found_error = True
+            report(
+                section.file_name,
+                section.text,
+                section.start,
+                f"Sentences in '{title}' have {words_per_sentence:.1f} words on average, "
+                f"the maximum is {max_sentence_length}",
+            )
+        if grade > max_grade:
+            found_error = True
+            report(
+                section.file_name,
+                section.text,
+                section.start,
+                f"'{title}' has a Flesch-Kincaid grade of {grade:.1f}, "
+                f"the maximum is {max_grade}",
+            )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-sentence-length",
+        type=float,
+        default=25,
+        help="Maximum average number of words per sentence in a section",
+    )
+    parser.add_argument(
+        "--max-grade",
+        type=float,
+        default=16,
+        help="Maximum Flesch-Kincaid grade level of a section",
+    )
+    parser.add_argument(
+        "--report",
+        action="store_true",
+        help="Print the metrics of all sections, not only of those exceeding a maximum",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(
+            project, args.max_sentence_length, args.max_grade, args.report
+        ),
+        "Found sections which are hard to read",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,100 @@
+#!/usr/bin/env python3
+import argparse
+import sys
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    check_projects,
+    iter_commands,
+    iter_environments,
+    line_number,
+    read_project,
+)
+
+SECTIONING_COMMANDS = ["part", "chapter", "section", "subsection", "subsubsection"]
+
+
+def find_element(
+    element: str, texts: t.List[t.Tuple[str, str]]
+) -> t.List[t.Tuple[str, int]]:
+    """
+    Return all locations of an element.
+
+    Elements starting with a backslash are commands, `chapter:Title` is a sectioning
+    command with the given title, and everything else is an environment.
+    """
+    locations: t.List[t.Tuple[str, int]] = []
+    for file_name, text in texts:
+        if element.startswith("\\"):
+            offsets = [cmd.start for cmd in iter_commands(text, [element[1:]], nargs=0)]
+        elif element.startswith("chapter:"):
+            title = element[len("chapter:") :].strip().lower()
+            offsets = [
+                cmd.start
+                for cmd in iter_commands(text, SECTIONING_COMMANDS)
+                if cmd.args and cmd.args[0].strip().lower() == title
+            ]
+        else:
+            offsets = [env.start for env in iter_environments(text, [element])]
+        locations += [(file_name, line_number(text, offset)) for offset in offsets]
+    return locations
+
+
+def search(files: t.List[t.IO[str]], elements: t.List[str]) -> bool:
+    texts = read_project(files)
+
+    found_error = False
+    for element in elements:
+        locations = find_element(element, texts)
+        if not locations:
+            found_error = True
+            print(f"Missing required element {element}")
+        elif len(locations) > 1:
+            found_error = True
+            print(f"Required element {element} exists multiple times")
+            for file_name, line in locations:
+                print(f"  {file_name}:{line}")
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--require",
+        action="append",
+        metavar="ELEMENT",
+        help="Element which must exist exactly once. "
+        "Either a command like \\title, an environment like abstract, "
+        "or a chapter with a given title like chapter:Declaration.",
+        default=list(),
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    if len(args.require) == 0:
+        sys.exit("No elements specified. See --help for how to use them.")
+
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, args.require),
+        "Found missing or duplicated required elements",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
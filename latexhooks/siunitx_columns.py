@@ -0,0 +1,78 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    TABULAR_ENVIRONMENTS,
+    iter_environments,
+    iter_table_cells,
+    mask_source,
+    parse_group,
+    report,
+    table_columns,
+)
+
+# Numbers as understood by siunitx, e.g., `-1.5(2)e3` or `< 12.3 \pm 0.4`
+RE_NUMBER = re.compile(
+    r"^(?:[<>]|\\(?:approx|sim|le|ge|leq|geq|ll|gg)(?![a-zA-Z@]))?\s*"
+    r"[+-]?\s*(?:\d+(?:[.,]\d*)?|[.,]\d+)(?:\(\d+\))?"
+    r"(?:\s*\\(?:pm|mp)\s*(?:\d+(?:[.,]\d*)?|[.,]\d+))?"
+    r"(?:\s*[eEdD][+-]?\d+)?$"
+)
+
+
+def is_valid_cell(content: str) -> bool:
+    """Check that a cell in an S column contains a number or text protected by braces"""
+    if not content or content.startswith("\\multicolumn") or RE_NUMBER.match(content):
+        return True
+    group = parse_group(content, 0)
+    return group is not None and group[1] == len(content) - 1
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        for env in iter_environments(text, TABULAR_ENVIRONMENTS):
+            columns, body_start = table_columns(text, env)
+            for cell in iter_table_cells(text, body_start, env.body_end):
+                if cell.column >= len(columns) or columns[cell.column] != "S":
+                    continue
+                if not is_valid_cell(cell.content):
+                    found_error = True
+                    report(
+                        f.name,
+                        text,
+                        cell.start,
+                        f"Cell '{cell.content}' in an S column is not a number, "
+                        "wrap text in braces",
+                    )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files)
+    if found_error:
+        sys.exit("Found invalid cells in siunitx S columns")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,117 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    NON_PROSE_ENVIRONMENTS,
+    RE_INLINE_MATH,
+    iter_environments,
+    line_number,
+    mask_source,
+    verbatim_spans,
+)
+
+# Environments where spaces are used for alignment
+ALIGNED_ENVIRONMENTS = [
+    "tabular",
+    "tabular*",
+    "tabularx",
+    "tabulary",
+    "longtable",
+    "array",
+]
+
+# Multiple spaces between two non-space characters, but not after an escaped space `\ `
+RE_DOUBLE_SPACE = re.compile(r"(?<=[^\s\\])[ \t]{2,}(?=\S)")
+# Spaces before punctuation or a footnote, but not before a decimal like `.5`
+RE_SPACE_BEFORE_PUNCTUATION = re.compile(
+    r"(?<=[\w\}])[ \t]+(?=[,.;:](?![\d.])|\\footnote\b)"
+)
+
+
+def find_spacing(text: str) -> t.List[t.Tuple[int, int, str]]:
+    """Return the spans of all bad spacing together with their replacement"""
+    masked = mask_source(text)
+    skipped = [
+        (env.start, env.end)
+        for env in iter_environments(
+            masked, NON_PROSE_ENVIRONMENTS + ALIGNED_ENVIRONMENTS
+        )
+    ]
+    skipped += [m.span() for m in RE_INLINE_MATH.finditer(masked)]
+    # The masked inline verbatim looks like spaces, but must be kept as is
+    skipped += verbatim_spans(text)
+
+    def is_skipped(match: t.Match[str]) -> bool:
+        return any(start <= match.start() < end for start, end in skipped)
+
+    findings = [
+        (m.start(), m.end(), " ")
+        for m in RE_DOUBLE_SPACE.finditer(masked)
+        if not is_skipped(m)
+    ]
+    findings += [
+        (m.start(), m.end(), "")
+        for m in RE_SPACE_BEFORE_PUNCTUATION.finditer(masked)
+        if not is_skipped(m)
+    ]
+
+    # Spaces before punctuation can also be double spaces, only keep one of them
+    result: t.List[t.Tuple[int, int, str]] = []
+    for finding in sorted(findings, key=lambda f: (f[0], f[2])):
+        if result and finding[0] < result[-1][1]:
+            continue
+        result.append(finding)
+    return result
+
+
+def search(files: t.List[str], fix: bool) -> bool:
+    found_error = False
+    for file_name in files:
+        with open(file_name, encoding="utf-8") as f:
+            text = f.read()
+        findings = find_spacing(text)
+        for start, _, replacement in findings:
+            found_error = True
+            message = "Double space" if replacement else "Space before punctuation"
+            print(f"{file_name}:{line_number(text, start)} {message}")
+
+        if fix and findings:
+            # Replace from the back, such that the offsets stay valid
+            for start, end, replacement in reversed(findings):
+                text = text[:start] + replacement + text[end:]
+            with open(file_name, "w", encoding="utf-8") as f:
+                f.write(text)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--fix",
+        action="store_true",
+        help="Remove the superfluous spaces in place",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(sorted(args.files), args.fix)
+    if found_error:
+        sys.exit("Found superfluous spaces")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,120 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import subprocess
+import sys
+import typing as t
+
+from latexhooks.common import (
+    line_number,
+    magic_comments,
+    mask_non_prose,
+    read_wordlist,
+)
+
+RE_WORD = re.compile(r"[^\W\d_]+(?:['’-][^\W\d_]+)*")
+
+
+def misspelled_words(words: t.Set[str], dictionaries: t.List[str]) -> t.Set[str]:
+    """Ask hunspell which of the words are unknown"""
+    try:
+        result = subprocess.run(
+            ["hunspell", "-i", "utf-8", "-d", ",".join(dictionaries), "-l"],
+            input="\n".join(sorted(words)),
+            capture_output=True,
+            encoding="utf-8",
+            check=True,
+        )
+    except FileNotFoundError:
+        sys.exit("hunspell is not installed or cannot be found in PATH")
+    except subprocess.CalledProcessError as err:
+        sys.exit(f"hunspell failed: {err.stderr.strip()}")
+    return set(result.stdout.split())
+
+
+def file_dictionaries(text: str, dictionaries: t.List[str]) -> t.Tuple[str, ...]:
+    """Use the language of a `% !TeX spellcheck = en_GB` magic comment, if present"""
+    language = magic_comments(text).get("spellcheck")
+    if language:
+        return (language.replace("-", "_"),)
+    return tuple(dictionaries)
+
+
+def search(
+    files: t.List[t.IO[str]], dictionaries: t.List[str], known_words: t.Set[str]
+) -> bool:
+    texts = [(f.name, f.read()) for f in files]
+    prose = [
+        (file_name, text, mask_non_prose(text), file_dictionaries(text, dictionaries))
+        for file_name, text in texts
+    ]
+
+    # Check all words in one go per dictionary, since starting hunspell is expensive
+    words: t.Dict[t.Tuple[str, ...], t.Set[str]] = dict()
+    for _, _, masked, dicts in prose:
+        words.setdefault(dicts, set()).update(
+            match[0] for match in RE_WORD.finditer(masked)
+        )
+    unknown = {
+        dicts: misspelled_words(candidates - known_words, list(dicts))
+        for dicts, candidates in words.items()
+        if candidates - known_words
+    }
+
+    found_unknown = False
+    for file_name, text, masked, dicts in prose:
+        for match in RE_WORD.finditer(masked):
+            if match[0] not in unknown.get(dicts, set()):
+                continue
+            found_unknown = True
+            line = line_number(text, match.start())
+            column = match.start() - (text.rfind("\n", 0, match.start()) + 1) + 1
+            print(f"{file_name}:{line}:{column} Unknown word '{match[0]}'")
+    return found_unknown
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--dictionary",
+        action="append",
+        metavar="DICT",
+        help="Hunspell dictionary to use, e.g., en_US. Can be given multiple times. "
+        "A `%% !TeX spellcheck` magic comment in a file takes precedence.",
+        default=list(),
+    )
+    parser.add_argument(
+        "--wordlist",
+        action="append",
+        metavar="FILE",
+        help="File with additional known words, one per line",
+        default=list(),
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    dictionaries = args.dictionary or ["en_US"]
+    known_words: t.Set[str] = set()
+    for path in args.wordlist:
+        known_words |= set(read_wordlist(path))
+
+    files = sorted(args.files, key=lambda f: f.name)
+    found_unknown = search(files, dictionaries, known_words)
+    if found_unknown:
+        sys.exit("Found unknown words")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,157 @@
+#!/usr/bin/env python3
+import argparse
+import sys
+import typing as t
+from dataclasses import dataclass
+
+from latexhooks.common import (
+    Environment,
+    iter_commands,
+    iter_environments,
+    mask_source,
+    parse_group,
+    report,
+)
+
+FLOATS = ["figure", "figure*", "table", "table*"]
+SUBFLOATS = ["subfigure", "subtable"]
+
+
+@dataclass
+class SubFloat:
+    """A sub-float, either an environment or a command like `\\subcaptionbox`"""
+
+    name: str
+    """ Name of the environment or the command with its backslash """
+    start: int
+    end: int
+    captions: t.List[str]
+    """ Content of the captions of the sub-float """
+    body: str
+    """ Text containing the labels of the sub-float """
+
+
+def iter_subfloats(text: str) -> t.Iterator[SubFloat]:
+    """Find the sub-floats of the subcaption and subfig packages"""
+    for env in iter_environments(text, SUBFLOATS):
+        body = text[env.body_start : env.body_end]
+        captions = [
+            "".join(cmd.args) for cmd in iter_commands(body, ["caption", "subcaption"])
+        ]
+        yield SubFloat(env.name, env.start, env.end, captions, body)
+    # \subcaptionbox{caption}[width][position]{content}
+    for cmd in iter_commands(text, ["subcaptionbox"]):
+        pos = cmd.end
+        for _ in range(2):
+            group = parse_group(text, pos, "[", "]")
+            if group is not None:
+                pos = group[1] + 1
+        content = parse_group(text, pos)
+        end = content[1] + 1 if content is not None else pos
+        captions = [arg for arg in cmd.args if arg.strip()]
+        body = text[cmd.start : end]
+        yield SubFloat("\\subcaptionbox", cmd.start, end, captions, body)
+    # \subfloat[list entry][caption]{content}
+    for cmd in iter_commands(text, ["subfloat"]):
+        captions = [caption for caption in cmd.optional[-1:] if caption.strip()]
+        body = text[cmd.start : cmd.end]
+        yield SubFloat("\\subfloat", cmd.start, cmd.end, captions, body)
+
+
+def check_float(
+    file_name: str,
+    text: str,
+    parent: Environment,
+    children: t.List[SubFloat],
+    sep: str,
+) -> bool:
+    found_error = False
+
+    # Captions and labels of the parent are those outside of any sub-float
+    parent_text = text[parent.body_start : parent.body_end]
+    for child in children:
+        start = child.start - parent.body_start
+        end = child.end - parent.body_start
+        parent_text = parent_text[:start] + " " * (end - start) + parent_text[end:]
+
+    parent_label = None
+    for cmd in iter_commands(parent_text, ["label"]):
+        if cmd.args:
+            parent_label = cmd.args[0].strip()
+            break
+    if not any(True for _ in iter_commands(parent_text, ["caption"])):
+        found_error = True
+        report(file_name, text, parent.start, f"Missing \\caption for {parent.name}")
+    if parent_label is None:
+        found_error = True
+        report(file_name, text, parent.start, f"Missing \\label for {parent.name}")
+
+    for child in children:
+        if not child.captions:
+            found_error = True
+            report(file_name, text, child.start, f"Missing \\caption for {child.name}")
+
+        labels = [
+            cmd.args[0].strip()
+            for cmd in iter_commands(child.body, ["label"])
+            if cmd.args
+        ]
+        if not labels:
+            found_error = True
+            report(file_name, text, child.start, f"Missing \\label for {child.name}")
+        elif parent_label is not None and not labels[0].startswith(parent_label + sep):
+            found_error = True
+            report(
+                file_name,
+                text,
+                child.start,
+                f"Label '{labels[0]}' of {child.name} should start with '{parent_label}{sep}'",
+            )
+    return found_error
+
+
+def search(files: t.List[t.IO[str]], sep: str) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        subfloats = sorted(iter_subfloats(text), key=lambda child: child.start)
+        for parent in iter_environments(text, FLOATS):
+            children = [
+                child
+                for child in subfloats
+                if parent.body_start <= child.start and child.end <= parent.body_end
+            ]
+            if children:
+                found_error |= check_float(f.name, text, parent, children, sep)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--separator",
+        default=":",
+        help="Sub-float labels must start with the parent label followed by SEPARATOR",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files, args.separator)
+    if found_error:
+        sys.exit("Found sub-floats with missing captions or labels")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,167 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    REF_COMMANDS,
+    add_root_argument,
+    check_projects,
+    is_root,
+    iter_commands,
+    iter_environments,
+    mask_non_prose,
+    read_project,
+    report,
+    split_list,
+)
+
+RE_APPENDIX_MENTION = re.compile(r"\b(?:appendix|appendices)\b", re.I)
+RE_SUPPLEMENT_MENTION = re.compile(r"\bsupplement(?:ary\s+materials?)?\b", re.I)
+
+
+def find_appendix(texts: t.List[t.Tuple[str, str]]) -> t.Optional[t.Tuple[int, int]]:
+    """Return the index of the file and the offset where the appendix starts"""
+    for idx, (_, text) in enumerate(texts):
+        starts = [cmd.start for cmd in iter_commands(text, ["appendix"], nargs=0)]
+        starts += [env.start for env in iter_environments(text, ["appendices"])]
+        if starts:
+            return idx, min(starts)
+    return None
+
+
+def appendix_headings(
+    texts: t.List[t.Tuple[str, str]], appendix: t.Tuple[int, int]
+) -> t.List[t.Tuple[int, int, str]]:
+    """Return the index of the file, the offset, and the title of each appendix part"""
+    headings: t.Dict[str, t.List[t.Tuple[int, int, str]]] = dict()
+    for idx, (_, text) in enumerate(texts):
+        for cmd in iter_commands(text, ["chapter", "section"]):
+            if cmd.args and (idx, cmd.start) >= appendix:
+                title = " ".join(cmd.args[0].split())
+                headings.setdefault(cmd.name, list()).append((idx, cmd.start, title))
+    return headings.get("chapter") or headings.get("section") or []
+
+
+def search(
+    files: t.List[t.IO[str]], prefixes: t.List[str], external_supplement: bool
+) -> bool:
+    texts = read_project(files)
+
+    # Without the root document we cannot say whether an appendix is missing
+    if not any(is_root(text) for _, text in texts):
+        return False
+
+    appendix = find_appendix(texts)
+
+    def in_appendix(idx: int, offset: int) -> bool:
+        return appendix is not None and (idx, offset) >= appendix
+
+    def is_appendix_label(label: str) -> bool:
+        return any(label.startswith(prefix + ":") for prefix in prefixes)
+
+    # Appendix labels and the index of the appendix part containing them
+    appendix_labels: t.Dict[str, int] = dict()
+    headings = appendix_headings(texts, appendix) if appendix is not None else []
+    # Mentions and references from the main matter: file, text, offset, description
+    mentions: t.List[t.Tuple[str, str, int, str]] = []
+    refs: t.List[t.Tuple[str, str, int, str]] = []
+    for idx, (file_name, text) in enumerate(texts):
+        for cmd in iter_commands(text, ["label"]):
+            if cmd.args and in_appendix(idx, cmd.start):
+                part = sum(1 for h in headings if h[:2] <= (idx, cmd.start)) - 1
+                appendix_labels[cmd.args[0].strip()] = part
+
+        prose = mask_non_prose(text)
+        for match in RE_APPENDIX_MENTION.finditer(prose):
+            if not in_appendix(idx, match.start()):
+                mentions.append((file_name, text, match.start(), match[0]))
+        if not external_supplement:
+            for match in RE_SUPPLEMENT_MENTION.finditer(prose):
+                if not in_appendix(idx, match.start()):
+                    mentions.append((file_name, text, match.start(), match[0]))
+
+        for cmd in iter_commands(text, REF_COMMANDS):
+            if not cmd.args or in_appendix(idx, cmd.start):
+                continue
+            for label in split_list([cmd.args[0]]):
+                refs.append((file_name, text, cmd.start, label))
+
+    found_error = False
+    if appendix is None:
+        for file_name, text, offset, mention in mentions:
+            found_error = True
+            message = f"Text mentions '{mention}', but the document has no appendix"
+            report(file_name, text, offset, message)
+        for file_name, text, offset, label in refs:
+            if is_appendix_label(label):
+                found_error = True
+                message = f"Reference to {label}, but the document has no appendix"
+                report(file_name, text, offset, message)
+        return found_error
+
+    referenced = {
+        appendix_labels[label] for *_, label in refs if label in appendix_labels
+    }
+    for file_name, text, offset, label in refs:
+        if is_appendix_label(label) and label not in appendix_labels:
+            found_error = True
+            message = f"Reference to {label}, but the appendix has no such label"
+            report(file_name, text, offset, message)
+
+    if len(headings) > 1:
+        for part, (idx, offset, title) in enumerate(headings):
+            if part not in referenced:
+                found_error = True
+                file_name, text = texts[idx]
+                message = f"Appendix '{title}' is never referenced from the main matter"
+                report(file_name, text, offset, message)
+    elif not mentions and not referenced:
+        found_error = True
+        file_name, text = texts[appendix[0]]
+        message = "The appendix is never referenced from the main matter"
+        report(file_name, text, appendix[1], message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--prefix",
+        action="append",
+        metavar="PREFIX",
+        help="Label prefix used for appendix labels. Can be given multiple times.",
+        default=list(),
+    )
+    parser.add_argument(
+        "--external-supplement",
+        action="store_true",
+        help="Supplementary material is a separate document outside of the project",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    prefixes = split_list(args.prefix) or ["app", "appendix", "supp"]
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, prefixes, args.external_supplement),
+        "Found problems with references to the appendix",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
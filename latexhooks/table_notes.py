@@ -0,0 +1,109 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    Environment,
+    TABULAR_ENVIRONMENTS,
+    UNITS,
+    iter_commands,
+    iter_environments,
+    iter_table_cells,
+    mask_source,
+    parse_group,
+    report,
+    table_columns,
+)
+
+# A cell consisting of a number with a unit
+RE_UNIT_CELL = re.compile(
+    r"^\s*-?\d+(?:[.,]\d+)?\s*(?:\\,|~|\\ )?\s*"
+    r"(?:" + "|".join(UNITS) + r"|\\%)\s*$"
+    r"|\\(?:SI|qty)\s*\{"
+)
+
+
+def check_notes(file_name: str, text: str, start: int, end: int) -> bool:
+    """Check that all \\tnote markers in a threeparttable have a note and vice versa"""
+    found_error = False
+    body = text[start:end]
+    markers = {
+        cmd.args[0].strip(): cmd.start
+        for cmd in iter_commands(body, ["tnote"])
+        if cmd.args
+    }
+    notes: t.Dict[str, int] = dict()
+    for env in iter_environments(body, ["tablenotes"]):
+        items = iter_commands(body[: env.body_end], ["item"], nargs=0)
+        for cmd in items:
+            marker = parse_group(body, cmd.end, "[", "]")
+            if cmd.start >= env.body_start and marker is not None:
+                notes[body[marker[0] : marker[1]].strip()] = cmd.start
+
+    for marker, offset in markers.items():
+        if marker not in notes:
+            found_error = True
+            report(file_name, text, start + offset, f"\\tnote{{{marker}}} has no note")
+    for note, offset in notes.items():
+        if note not in markers:
+            found_error = True
+            report(file_name, text, start + offset, f"Table note {note} is never used")
+    return found_error
+
+
+def check_units(file_name: str, text: str, env: Environment) -> bool:
+    """Flag cells which contain a unit instead of the column header"""
+    found_error = False
+    _, body_start = table_columns(text, env)
+    for cell in iter_table_cells(text, body_start, env.body_end):
+        # The first row is the header
+        if cell.row > 0 and RE_UNIT_CELL.search(cell.content):
+            found_error = True
+            message = f"Unit in cell '{cell.content}', move it into the header"
+            report(file_name, text, cell.start, message)
+    return found_error
+
+
+def search(files: t.List[t.IO[str]], header_units: bool) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        for env in iter_environments(text, ["threeparttable"]):
+            found_error |= check_notes(f.name, text, env.body_start, env.body_end)
+        if header_units:
+            for env in iter_environments(text, TABULAR_ENVIRONMENTS):
+                found_error |= check_units(f.name, text, env)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--header-units",
+        action="store_true",
+        help="Units must be placed in the column header instead of each cell",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files, args.header_units)
+    if found_error:
+        sys.exit("Found problems with table notes or units")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
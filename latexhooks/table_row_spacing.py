@@ -0,0 +1,64 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    TABULAR_ENVIRONMENTS,
+    iter_environments,
+    line_number,
+    mask_source,
+)
+
+RE_ROW_SPACING = re.compile(r"(?<!\\)(?:\\\\)*\\\\\s*\[([^\]]*)\]")
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    # Length mapped to all places where it is used
+    tweaks: t.Dict[str, t.List[t.Tuple[str, int]]] = dict()
+    for f in files:
+        text = mask_source(f.read())
+        for env in iter_environments(text, TABULAR_ENVIRONMENTS):
+            for match in RE_ROW_SPACING.finditer(text, env.body_start, env.body_end):
+                location = (f.name, line_number(text, match.start()))
+                tweaks.setdefault(match[1].strip(), list()).append(location)
+
+    if not tweaks:
+        return False
+    print(
+        "Manual row spacing in tables, use \\renewcommand{\\arraystretch} "
+        "or \\addlinespace from booktabs instead"
+    )
+    for length, locations in sorted(tweaks.items()):
+        print(f"  \\\\[{length}] is used {len(locations)} times")
+        for file_name, line in locations:
+            print(f"    {file_name}:{line}")
+    return True
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    files = sorted(args.files, key=lambda f: f.name)
+    found_error = search(files)
+    if found_error:
+        sys.exit("Found manual row spacing in tables")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,125 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    check_projects,
+    iter_commands,
+    mask_non_prose,
+    read_project,
+    read_wordlist,
+    report,
+)
+
+RE_QUOTED = re.compile(r"``[^`']*''|\"[^\"\n]*\"")
+
+STYLES = {
+    "emph": (["emph", "textit"], "\\emph{}"),
+    "quote": (["enquote"], "quotes"),
+}
+
+
+def highlighted_spans(text: str, style: str) -> t.List[t.Tuple[int, int]]:
+    """Return the spans of the text which introduce a term in the configured style"""
+    commands, _ = STYLES[style]
+    spans = [
+        (cmd.arg_offsets[0], cmd.end)
+        for cmd in iter_commands(text, commands)
+        if cmd.args
+    ]
+    if style == "quote":
+        spans += [m.span() for m in RE_QUOTED.finditer(text)]
+    return spans
+
+
+def search(files: t.List[t.IO[str]], terms: t.List[str], style: str) -> bool:
+    texts = read_project(files)
+    _, style_name = STYLES[style]
+
+    # Occurrences of each term in document order: file name, text, offset, highlighted
+    occurrences: t.Dict[str, t.List[t.Tuple[str, str, int, bool]]] = {
+        term: [] for term in terms
+    }
+    for file_name, text in texts:
+        prose = mask_non_prose(text)
+        spans = highlighted_spans(text, style)
+        for term in terms:
+            regex = r"(?<![\w-])" + re.escape(term) + r"(?![\w-])"
+            for match in re.finditer(regex, prose, re.I):
+                highlighted = any(start <= match.start() < end for start, end in spans)
+                occurrences[term].append((file_name, text, match.start(), highlighted))
+
+    found_error = False
+    for term, places in occurrences.items():
+        if not places:
+            continue
+        file_name, text, offset, highlighted = places[0]
+        if not highlighted:
+            found_error = True
+            message = f"First use of the term '{term}' should use {style_name}"
+            report(file_name, text, offset, message)
+        for file_name, text, offset, highlighted in places[1:]:
+            if highlighted:
+                found_error = True
+                message = f"Term '{term}' was already introduced, remove {style_name}"
+                report(file_name, text, offset, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--term",
+        action="append",
+        metavar="TERM",
+        help="Term which is introduced in the document",
+        default=list(),
+    )
+    parser.add_argument(
+        "--terms-file",
+        action="append",
+        metavar="FILE",
+        help="File with terms, one per line. Lines starting with # are ignored.",
+        default=list(),
+    )
+    parser.add_argument(
+        "--style",
+        choices=sorted(STYLES),
+        default="emph",
+        help="How new terms are introduced",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    terms = list(args.term)
+    for path in args.terms_file:
+        terms += read_wordlist(path)
+    if len(terms) == 0:
+        sys.exit("No terms specified. See --help for how to use them.")
+
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, terms, args.style),
+        "Found inconsistent introductions of terms",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
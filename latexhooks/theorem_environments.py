@@ -0,0 +1,112 @@
+#!/usr/bin/env python3
+import argparse
+import typing as t
+
+from latexhooks.common import (
+    THEOREM_PREFIXES,
+    add_root_argument,
+    check_projects,
+    is_root,
+    iter_commands,
+    iter_environments,
+    read_project,
+    report,
+)
+
+# Document classes which already declare the common theorem-like environments
+PREDEFINING_CLASSES = ["llncs", "svjour3", "svmult", "acmart"]
+
+
+def search(files: t.List[t.IO[str]], prefixes: t.Dict[str, str]) -> bool:
+    texts = read_project(files)
+
+    # Declared environments and where they are declared
+    declared: t.Dict[str, t.Tuple[str, str, int]] = dict()
+    used: t.Set[str] = set()
+    predefined = False
+    for file_name, text in texts:
+        for cmd in iter_commands(text, ["newtheorem", "declaretheorem"]):
+            if cmd.args:
+                declared.setdefault(cmd.args[0].strip(), (file_name, text, cmd.start))
+        for cmd in iter_commands(text, ["documentclass"]):
+            predefined |= bool(cmd.args) and cmd.args[0].strip() in PREDEFINING_CLASSES
+        used |= {env.name for env in iter_environments(text)}
+
+    found_error = False
+    for name, (file_name, text, offset) in declared.items():
+        if name not in used:
+            found_error = True
+            report(file_name, text, offset, f"Theorem environment {name} is never used")
+
+    # Undeclared environments are only detectable with the preamble
+    check_undeclared = any(is_root(text) for _, text in texts) and not predefined
+    for file_name, text in texts:
+        for env in iter_environments(text, list(prefixes)):
+            if check_undeclared and env.name not in declared:
+                found_error = True
+                message = f"Theorem environment {env.name} is used but never declared"
+                report(file_name, text, env.start, message)
+
+            # Only labels directly within the environment, not in nested equations
+            nested = [
+                (inner.start, inner.end)
+                for inner in iter_environments(text[: env.body_end])
+                if inner.start >= env.body_start
+            ]
+            body = text[: env.body_end]
+            for cmd in iter_commands(body, ["label"]):
+                if cmd.start < env.body_start or not cmd.args:
+                    continue
+                if any(start <= cmd.start < end for start, end in nested):
+                    continue
+                label = cmd.args[0].strip()
+                prefix = prefixes[env.name]
+                if not label.startswith(prefix + ":"):
+                    found_error = True
+                    report(
+                        file_name,
+                        text,
+                        cmd.start,
+                        f"Label {label} of {env.name} should start with {prefix}:",
+                    )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--prefix",
+        action="append",
+        metavar="ENV=PREFIX",
+        type=lambda x: tuple(x.split("=", 1)),
+        help="Label prefix for a theorem-like environment, e.g., theorem=thm",
+        default=list(),
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    prefixes = dict(THEOREM_PREFIXES)
+    prefixes.update(args.prefix)
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, prefixes),
+        "Found problems with theorem environments",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
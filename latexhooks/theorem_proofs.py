@@ -0,0 +1,149 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    REF_COMMANDS,
+    THEOREM_PREFIXES,
+    add_root_argument,
+    check_projects,
+    iter_commands,
+    iter_environments,
+    read_project,
+    report,
+)
+
+# Theorem-like environments which need a proof
+DEFAULT_THEOREMS = ["theorem", "lemma", "corollary", "proposition"]
+DEFAULT_PROOFS = ["proof", "proofsketch"]
+NO_PROOF_MARKER = "% no-proof"
+RE_NO_PROOF = re.compile(r"%\s*no-proof\b")
+
+RE_OPTION = re.compile(r"\s*\[([^\]]*)\]")
+RE_PRECEDING_END = re.compile(r"\\end\s*\{([^\{\}]+)\}\s*$")
+
+
+def base_name(name: str) -> str:
+    """Return the name of the environment without the star of unnumbered variants"""
+    return name.rstrip("*")
+
+
+def search(
+    files: t.List[t.IO[str]], theorems: t.List[str], proofs: t.List[str]
+) -> bool:
+    texts = read_project(files)
+    theorem_like = set(THEOREM_PREFIXES) | set(theorems)
+    for _, text in texts:
+        for cmd in iter_commands(text, ["newtheorem", "declaretheorem"]):
+            if cmd.args:
+                theorem_like.add(cmd.args[0].strip())
+
+    found_error = False
+    # Labels of theorems which are proven elsewhere, e.g., in the appendix
+    referenced: t.Set[str] = set()
+    for file_name, text in texts:
+        for env in iter_environments(text):
+            if base_name(env.name) not in proofs:
+                continue
+            option = RE_OPTION.match(text, env.body_start)
+            if option is not None:
+                refs = [
+                    label.strip()
+                    for cmd in iter_commands(option[1], REF_COMMANDS)
+                    if cmd.args
+                    for label in cmd.args[0].split(",")
+                ]
+                if refs:
+                    referenced.update(refs)
+                    continue
+            preceding = RE_PRECEDING_END.search(text[: env.start])
+            if preceding is None or base_name(preceding[1]) not in theorem_like:
+                found_error = True
+                report(
+                    file_name,
+                    text,
+                    env.start,
+                    "Proof does not follow a theorem-like environment, "
+                    "reference the theorem in the optional argument",
+                )
+
+    re_proof = re.compile(
+        rf"\\begin\s*\{{(?:{'|'.join(map(re.escape, proofs))})\*?\}}"
+    )
+    for file_name, text in texts:
+        # The texts of the project have masked comments, but the markers are comments
+        with open(file_name, encoding="utf-8") as f:
+            raw = f.read()
+        for env in iter_environments(text):
+            if base_name(env.name) not in theorems:
+                continue
+            labels = [
+                cmd.args[0].strip()
+                for cmd in iter_commands(text[env.body_start : env.body_end], ["label"])
+                if cmd.args
+            ]
+            if any(label in referenced for label in labels):
+                continue
+            # The marker may be in the theorem or in the comments after it
+            following = len(text) - len(text[env.end :].lstrip())
+            if RE_NO_PROOF.search(raw, env.start, following):
+                continue
+            if re_proof.match(text, following):
+                continue
+            found_error = True
+            report(
+                file_name,
+                text,
+                env.start,
+                f"{env.name} has no proof, add one or mark it with {NO_PROOF_MARKER}",
+            )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--theorem",
+        action="append",
+        metavar="ENV",
+        default=list(),
+        help="Environment which needs a proof, replaces the default "
+        f"{', '.join(DEFAULT_THEOREMS)}. Can be given multiple times.",
+    )
+    parser.add_argument(
+        "--proof",
+        action="append",
+        metavar="ENV",
+        default=list(),
+        help="Environment which is a proof, replaces the default "
+        f"{', '.join(DEFAULT_PROOFS)}. Can be given multiple times.",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    theorems = args.theorem or DEFAULT_THEOREMS
+    proofs = args.proof or DEFAULT_PROOFS
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, theorems, proofs),
+        "Found theorems without proofs or proofs without theorems",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
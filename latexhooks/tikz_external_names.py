@@ -0,0 +1,62 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import iter_commands, line_number, mask_source
+
+# Matches `figure name=...` inside of the options of `\tikzset` or `\tikzexternalize`
+RE_FIGURE_NAME = re.compile(r"figure\s+name\s*=\s*(?:\{([^\{\}]*)\}|([^,\]\}\s]+))")
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    names: t.Dict[str, t.List[t.Tuple[str, int]]] = dict()
+    for f in files:
+        text = mask_source(f.read())
+        for cmd in iter_commands(text, ["tikzsetnextfilename"]):
+            if cmd.args:
+                name = cmd.args[0].strip()
+                location = (f.name, line_number(text, cmd.start))
+                names.setdefault(name, list()).append(location)
+        for match in RE_FIGURE_NAME.finditer(text):
+            name = (match[1] or match[2]).strip()
+            location = (f.name, line_number(text, match.start()))
+            names.setdefault(name, list()).append(location)
+
+    found_duplicates = False
+    for name, locations in names.items():
+        if len(locations) > 1:
+            found_duplicates = True
+            print(f"Found multiple TikZ externalization names {name}")
+            for file_name, line in locations:
+                print(f"  {file_name}:{line}")
+            print()
+    return found_duplicates
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    files = sorted(args.files, key=lambda f: f.name)
+    found_duplicates = search(files)
+    if found_duplicates:
+        sys.exit("Found multiple TikZ figures using the same externalization name")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
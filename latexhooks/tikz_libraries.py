@@ -0,0 +1,179 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+from dataclasses import dataclass
+
+from latexhooks.common import iter_commands, iter_environments, read_project, report
+
+
+@dataclass
+class LibraryUsage:
+    """A pattern which can only be used if a TikZ or pgfplots library is loaded"""
+
+    library: str
+    """ Name of the library, as passed to `\\usetikzlibrary` """
+    regex: t.Pattern[str]
+    """ Matches the syntax which requires the library, reports the group `usage` """
+    pgfplots: bool = False
+    """ The library needs to be loaded with `\\usepgfplotslibrary` """
+
+
+ARROW_TIPS = r"(?:Stealth|Latex|Triangle|Kite|Straight Barb|Implies)"
+
+LIBRARIES = [
+    # Arrow tips after `>=`, `arrows=`, or the `-` of an arrow like `-{Latex}`
+    LibraryUsage(
+        "arrows.meta",
+        re.compile(
+            r"(?:>\s*=|\barrows\s*=\s*\{?\s*-?|(?<!-)-)\s*\{?\s*"
+            rf"(?P<usage>{ARROW_TIPS})(?!\w)"
+        ),
+    ),
+    # Arrow tips before the `-` of an arrow like `Latex[round]-`
+    LibraryUsage(
+        "arrows.meta",
+        re.compile(
+            rf"(?<![\\\w])(?P<usage>{ARROW_TIPS})"
+            r"(?=\s*(?:\[[^\]]*\])?\s*\}?\s*-(?!-))"
+        ),
+    ),
+    LibraryUsage(
+        "positioning",
+        re.compile(
+            r"\b(?:above|below|left|right)(?: (?:left|right))?\s*=[^,\]]*\bof\b"
+        ),
+    ),
+    LibraryUsage("calc", re.compile(r"\(\s*\$")),
+    LibraryUsage(
+        "decorations.pathreplacing", re.compile(r"decoration\s*=\s*\{?\s*brace")
+    ),
+    LibraryUsage("fit", re.compile(r"\bfit\s*=")),
+    LibraryUsage("backgrounds", re.compile(r"\bon background layer\b")),
+    LibraryUsage("patterns", re.compile(r"\bpattern\s*=")),
+    LibraryUsage("matrix", re.compile(r"\bmatrix of (?:math )?nodes\b")),
+    LibraryUsage("intersections", re.compile(r"\bname intersections\b")),
+    LibraryUsage("shadows", re.compile(r"\bdrop shadow\b")),
+    LibraryUsage(
+        "shapes.geometric",
+        re.compile(
+            r"\b(?:diamond|trapezium|regular polygon|isosceles triangle|cylinder)\b"
+        ),
+    ),
+    LibraryUsage("fillbetween", re.compile(r"\bfill between\b"), pgfplots=True),
+    LibraryUsage("groupplots", re.compile(r"\\begin\{groupplot\}"), pgfplots=True),
+]
+
+PICTURES = ["tikzpicture", "axis", "groupplot"]
+
+
+def loaded_libraries(text: str, command: str) -> t.Set[str]:
+    libraries: t.Set[str] = set()
+    for cmd in iter_commands(text, [command]):
+        if cmd.args:
+            libraries |= {lib.strip() for lib in cmd.args[0].split(",") if lib.strip()}
+    return libraries
+
+
+def uses_package(text: str, package: str) -> bool:
+    for cmd in iter_commands(text, ["usepackage", "RequirePackage"]):
+        if cmd.args and package in {p.strip() for p in cmd.args[0].split(",")}:
+            return True
+    return False
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    texts = read_project(files)
+
+    # The preamble might be split over multiple files, so collect all libraries first
+    tikz_libraries: t.Set[str] = set()
+    pgfplots_libraries: t.Set[str] = set()
+    has_tikz = False
+    has_pgfplots = False
+    has_compat = False
+    for _, text in texts:
+        tikz_libraries |= loaded_libraries(text, "usetikzlibrary")
+        pgfplots_libraries |= loaded_libraries(text, "usepgfplotslibrary")
+        has_tikz |= uses_package(text, "tikz")
+        has_pgfplots |= uses_package(text, "pgfplots")
+        has_compat |= any(
+            "compat" in cmd.args[0]
+            for cmd in iter_commands(text, ["pgfplotsset"])
+            if cmd.args
+        )
+        has_compat |= any(
+            "compat" in opt
+            for cmd in iter_commands(text, ["usepackage"])
+            if cmd.args and cmd.args[0].strip() == "pgfplots"
+            for opt in cmd.optional
+        )
+
+    # Without the preamble we cannot say which libraries are loaded
+    if not has_tikz and not has_pgfplots:
+        return False
+
+    found_missing = False
+    for file_name, text in texts:
+        for cmd in iter_commands(text, ["usepackage"]):
+            if cmd.args and cmd.args[0].strip() == "pgfplots" and not has_compat:
+                found_missing = True
+                report(
+                    file_name,
+                    text,
+                    cmd.start,
+                    "pgfplots is loaded without setting compat, use \\pgfplotsset{compat=...}",
+                )
+
+        reported: t.Set[t.Tuple[str, int]] = set()
+        for env in iter_environments(text, PICTURES):
+            body = text[env.start : env.end]
+            for usage in LIBRARIES:
+                loaded = pgfplots_libraries if usage.pgfplots else tikz_libraries
+                if usage.library in loaded:
+                    continue
+                for match in usage.regex.finditer(body):
+                    name = "usage" if "usage" in usage.regex.groupindex else 0
+                    offset = env.start + match.start(name)
+                    # Nested environments are visited multiple times
+                    if (usage.library, offset) in reported:
+                        continue
+                    reported.add((usage.library, offset))
+                    found_missing = True
+                    command = (
+                        "usepgfplotslibrary" if usage.pgfplots else "usetikzlibrary"
+                    )
+                    report(
+                        file_name,
+                        text,
+                        offset,
+                        f"'{match[name]}' requires \\{command}{{{usage.library}}}",
+                    )
+    return found_missing
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    files = sorted(args.files, key=lambda f: f.name)
+    found_missing = search(files)
+    if found_missing:
+        sys.exit("Found TikZ code using libraries which are not loaded")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
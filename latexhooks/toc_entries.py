@@ -0,0 +1,117 @@
+#!/usr/bin/env python3
+import argparse
+import sys
+import typing as t
+
+from latexhooks.common import Command, iter_commands, line_number, mask_source, report
+
+SECTIONING_COMMANDS = [
+    "part",
+    "chapter",
+    "section",
+    "subsection",
+    "subsubsection",
+    "paragraph",
+]
+
+# Maximum number of lines between a starred sectioning command and its ToC entry
+MAX_DISTANCE = 3
+
+
+def normalize(title: str) -> str:
+    return " ".join(title.split()).lower()
+
+
+def nearest_heading(
+    text: str, entry: Command, headings: t.List[Command]
+) -> t.Optional[Command]:
+    """Return the starred sectioning command closest to the ToC entry, if it is near"""
+    line = line_number(text, entry.start)
+    candidates = [
+        (abs(line_number(text, heading.start) - line), heading)
+        for heading in headings
+        if abs(line_number(text, heading.start) - line) <= MAX_DISTANCE
+    ]
+    if not candidates:
+        return None
+    return min(candidates, key=lambda candidate: candidate[0])[1]
+
+
+def search(files: t.List[t.IO[str]], required: t.List[str]) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        headings = [
+            cmd
+            for cmd in iter_commands(text, SECTIONING_COMMANDS)
+            if cmd.star and cmd.args
+        ]
+        entries = [
+            cmd
+            for cmd in iter_commands(text, ["addcontentsline"], nargs=3)
+            if len(cmd.args) == 3 and cmd.args[0].strip() == "toc"
+        ]
+
+        headings_with_entry: t.List[Command] = []
+        for entry in entries:
+            heading = nearest_heading(text, entry, headings)
+            if heading is None:
+                continue
+            headings_with_entry.append(heading)
+            level, title = entry.args[1].strip(), entry.args[2]
+            if normalize(title) != normalize(heading.args[0]):
+                found_error = True
+                message = (
+                    f"ToC entry '{title.strip()}' differs from the title"
+                    f" '{heading.args[0].strip()}'"
+                )
+                report(f.name, text, entry.start, message)
+            if level != heading.name:
+                found_error = True
+                message = f"ToC entry has level {level}, but the heading is "
+                report(f.name, text, entry.start, message + heading.name)
+
+        for heading in headings:
+            if heading in headings_with_entry:
+                continue
+            if normalize(heading.args[0]) in (normalize(title) for title in required):
+                found_error = True
+                message = (
+                    f"\\{heading.name}*{{{heading.args[0].strip()}}} needs"
+                    " an \\addcontentsline entry"
+                )
+                report(f.name, text, heading.start, message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--require-toc",
+        action="append",
+        metavar="TITLE",
+        help="Starred heading which needs a ToC entry. Can be given multiple times.",
+        default=list(),
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files, args.require_toc)
+    if found_error:
+        sys.exit("Found problems with table of contents entries")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,65 @@
+#!/usr/bin/env python3
+import argparse
+import fnmatch
+import re
+import sys
+import typing as t
+
+from latexhooks.common import current_branch, iter_commands, mask_source, report
+
+# Markup of the changes and soul packages
+CHANGE_COMMANDS = ["added", "deleted", "replaced", "highlight", "hl", "st"]
+# Markup added by latexdiff, like \DIFadd or \DIFdelbegin
+RE_LATEXDIFF = re.compile(r"\\DIF[a-zA-Z]*")
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        for cmd in iter_commands(text, CHANGE_COMMANDS, nargs=0):
+            found_error = True
+            report(f.name, text, cmd.start, f"Leftover change markup \\{cmd.name}")
+        for match in RE_LATEXDIFF.finditer(text):
+            found_error = True
+            report(f.name, text, match.start(), f"Leftover latexdiff markup {match[0]}")
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--allow-branch",
+        action="append",
+        metavar="PATTERN",
+        help="Glob pattern of branches which allow change markup, e.g., draft/*",
+        default=list(),
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    branch = current_branch()
+    if branch is not None and any(
+        fnmatch.fnmatchcase(branch, pattern) for pattern in args.allow_branch
+    ):
+        return
+
+    found_error = search(args.files)
+    if found_error:
+        sys.exit("Found leftover change tracking markup")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,71 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import blank_spans, iter_commands, mask_non_prose, report
+
+# Commands which already allow line breaks within their argument
+BREAKING_COMMANDS = ["url", "path", "href", "nolinkurl", "seqsplit", "texttt"]
+
+
+def suggestion(token: str) -> str:
+    if "://" in token or token.startswith("www."):
+        return "\\url"
+    if "/" in token or "\\" in token or "_" in token:
+        return "\\path"
+    return "\\seqsplit"
+
+
+def search(files: t.List[t.IO[str]], max_length: int) -> bool:
+    # A line can only be broken at spaces and after explicit hyphens
+    re_token = re.compile(r"[^\s-]{" + str(max_length + 1) + ",}")
+
+    found_error = False
+    for f in files:
+        text = f.read()
+        spans = [(cmd.start, cmd.end) for cmd in iter_commands(text, BREAKING_COMMANDS)]
+        prose = mask_non_prose(blank_spans(text, spans))
+        for match in re_token.finditer(prose):
+            found_error = True
+            report(
+                f.name,
+                text,
+                match.start(),
+                f"Unbreakable string '{match[0]}' with {len(match[0])} characters, "
+                f"use {suggestion(match[0])}",
+            )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-length",
+        type=int,
+        default=30,
+        help="Maximum number of characters without a possible line break",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files, args.max_length)
+    if found_error:
+        sys.exit("Found overlong unbreakable strings")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
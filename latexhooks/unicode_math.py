@@ -0,0 +1,179 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    MATH_ENVIRONMENTS,
+    RE_INLINE_MATH,
+    VERBATIM_ENVIRONMENTS,
+    add_root_argument,
+    blank_spans,
+    check_projects,
+    iter_environments,
+    iter_packages,
+    magic_program,
+    read_project,
+    report,
+)
+
+# Unicode math characters and the macros producing them
+UNICODE_MACROS = {
+    "α": "\\alpha",
+    "β": "\\beta",
+    "γ": "\\gamma",
+    "δ": "\\delta",
+    "ε": "\\varepsilon",
+    "ζ": "\\zeta",
+    "η": "\\eta",
+    "θ": "\\theta",
+    "κ": "\\kappa",
+    "λ": "\\lambda",
+    "μ": "\\mu",
+    "ν": "\\nu",
+    "ξ": "\\xi",
+    "π": "\\pi",
+    "ρ": "\\rho",
+    "σ": "\\sigma",
+    "τ": "\\tau",
+    "φ": "\\varphi",
+    "χ": "\\chi",
+    "ψ": "\\psi",
+    "ω": "\\omega",
+    "Γ": "\\Gamma",
+    "Δ": "\\Delta",
+    "Θ": "\\Theta",
+    "Λ": "\\Lambda",
+    "Π": "\\Pi",
+    "Σ": "\\Sigma",
+    "Φ": "\\Phi",
+    "Ψ": "\\Psi",
+    "Ω": "\\Omega",
+    "×": "\\times",
+    "·": "\\cdot",
+    "±": "\\pm",
+    "−": "-",
+    "≤": "\\leq",
+    "≥": "\\geq",
+    "≠": "\\neq",
+    "≈": "\\approx",
+    "≡": "\\equiv",
+    "∞": "\\infty",
+    "→": "\\to",
+    "←": "\\leftarrow",
+    "⇒": "\\Rightarrow",
+    "⇔": "\\Leftrightarrow",
+    "∈": "\\in",
+    "∉": "\\notin",
+    "⊂": "\\subset",
+    "⊆": "\\subseteq",
+    "∪": "\\cup",
+    "∩": "\\cap",
+    "∅": "\\emptyset",
+    "∀": "\\forall",
+    "∃": "\\exists",
+    "¬": "\\neg",
+    "∧": "\\land",
+    "∨": "\\lor",
+    "∑": "\\sum",
+    "∏": "\\prod",
+    "∫": "\\int",
+    "∂": "\\partial",
+    "∇": "\\nabla",
+    "ℝ": "\\mathbb{R}",
+    "ℕ": "\\mathbb{N}",
+    "ℤ": "\\mathbb{Z}",
+    "ℚ": "\\mathbb{Q}",
+    "ℂ": "\\mathbb{C}",
+}
+
+RE_UNICODE = re.compile("|".join(re.escape(c) for c in UNICODE_MACROS))
+RE_MACRO = re.compile(
+    "|".join(
+        re.escape(macro) + r"(?![a-zA-Z@])"
+        for macro in UNICODE_MACROS.values()
+        if re.fullmatch(r"\\[a-zA-Z]+", macro)
+    )
+)
+
+
+def is_unicode_project(texts: t.List[t.Tuple[str, str]], raw: t.List[str]) -> bool:
+    """Check whether the project uses an engine with Unicode math support"""
+    packages = {package for _, text in texts for _, package in iter_packages(text)}
+    programs = {magic_program(text) for text in raw}
+    return "unicode-math" in packages or bool(programs & {"lualatex", "xelatex"})
+
+
+def search(files: t.List[t.IO[str]], prefer_unicode: bool) -> bool:
+    texts = read_project(files)
+    raw: t.List[str] = []
+    for file_name, _ in texts:
+        with open(file_name, encoding="utf-8") as f:
+            raw.append(f.read())
+    unicode_project = is_unicode_project(texts, raw)
+    if unicode_project and not prefer_unicode:
+        return False
+
+    found_error = False
+    macro_to_unicode = {macro: char for char, macro in UNICODE_MACROS.items()}
+    for file_name, text in texts:
+        verbatim = [
+            (env.start, env.end)
+            for env in iter_environments(text, VERBATIM_ENVIRONMENTS)
+        ]
+        masked = blank_spans(text, verbatim)
+        if not unicode_project:
+            for match in RE_UNICODE.finditer(masked):
+                found_error = True
+                macro = UNICODE_MACROS[match[0]]
+                message = f"Character {match[0]} needs unicode-math, use {macro}"
+                report(file_name, text, match.start(), message)
+            continue
+
+        math = [
+            (env.start, env.end)
+            for env in iter_environments(masked, MATH_ENVIRONMENTS)
+        ]
+        math += [m.span() for m in RE_INLINE_MATH.finditer(masked)]
+        for match in RE_MACRO.finditer(masked):
+            if any(start <= match.start() < end for start, end in math):
+                found_error = True
+                char = macro_to_unicode[match[0]]
+                message = f"Use the Unicode character {char} instead of {match[0]}"
+                report(file_name, text, match.start(), message)
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--prefer-unicode",
+        action="store_true",
+        help="With unicode-math, prefer Unicode characters over macros in math",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, args.prefer_unicode),
+        "Found math characters violating the Unicode policy",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
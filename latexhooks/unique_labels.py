@@ -78,7 +78,7 @@ def search(files: t.List[t.IO[str]]) -> bool:
     return found_multiple_definitions
 
 
-def main() -> None:
+def build_parser() -> argparse.ArgumentParser:
     parser = argparse.ArgumentParser()
     parser.add_argument(
         "files",
@@ -87,13 +87,19 @@ def main() -> None:
         nargs="+",
         help="List of filenames to search in",
     )
-    args = parser.parse_args()
+    return parser
+
 
+def run(args: argparse.Namespace) -> None:
     files = sorted(args.files, key=lambda f: f.name)
     found_multiple_definitions = search(files)
     if found_multiple_definitions:
         sys.exit("Found multiple definitions of the same label")
 
 
+def main() -> None:
+    run(build_parser().parse_args())
+
+
 if __name__ == "__main__":
     main()
@@ -0,0 +1,88 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import (
+    is_root,
+    iter_commands,
+    mask_non_prose,
+    mask_source,
+    report,
+)
+
+RE_WORD = re.compile(r"[^\W\d_]+(?:['’-][^\W\d_]+)*")
+RE_END_DOCUMENT = re.compile(r"\\end\s*\{document\}")
+
+SECTIONING_COMMANDS = ["part", "chapter", "section", "subsection", "subsubsection"]
+
+
+def search(files: t.List[t.IO[str]], max_words: int) -> bool:
+    found_error = False
+    for f in files:
+        text = f.read()
+        masked = mask_source(text)
+        prose = mask_non_prose(text)
+
+        if is_root(masked):
+            match = RE_END_DOCUMENT.search(masked)
+            if match is not None:
+                words = RE_WORD.findall(prose, match.end())
+                if words:
+                    found_error = True
+                    word = RE_WORD.search(prose, match.end())
+                    offset = word.start() if word is not None else match.end()
+                    message = f"{len(words)} words after \\end{{document}} are ignored"
+                    report(f.name, text, offset, message)
+            continue
+
+        # Files without sectioning commands are snippets, like a table or the abstract
+        sections = iter_commands(masked, SECTIONING_COMMANDS, nargs=0)
+        first = next(sections, None)
+        if first is None:
+            continue
+        match = next(RE_WORD.finditer(prose, 0, first.start), None)
+        words = len(RE_WORD.findall(prose, 0, first.start))
+        if match is not None and words > max_words:
+            found_error = True
+            report(
+                f.name,
+                text,
+                match.start(),
+                f"{words} words before the first \\{first.name}, "
+                "move them into a section",
+            )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--max-words",
+        type=int,
+        default=10,
+        help="Maximum number of words before the first sectioning command of a file",
+    )
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files, args.max_words)
+    if found_error:
+        sys.exit("Found text outside of any section")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
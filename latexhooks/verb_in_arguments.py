@@ -0,0 +1,67 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import sys
+import typing as t
+
+from latexhooks.common import iter_commands, mask_source, report
+
+# Commands whose arguments are read before \verb can change the catcodes
+FRAGILE_COMMANDS = [
+    "footnote",
+    "caption",
+    "part",
+    "chapter",
+    "section",
+    "subsection",
+    "subsubsection",
+    "paragraph",
+    "subparagraph",
+    "title",
+]
+
+RE_VERB = re.compile(r"\\verb(?![a-zA-Z@])\*?")
+
+
+def search(files: t.List[t.IO[str]]) -> bool:
+    found_error = False
+    for f in files:
+        text = mask_source(f.read())
+        for cmd in iter_commands(text, FRAGILE_COMMANDS):
+            for arg, offset in zip(cmd.args, cmd.arg_offsets):
+                for match in RE_VERB.finditer(arg):
+                    found_error = True
+                    report(
+                        f.name,
+                        text,
+                        offset + match.start(),
+                        f"\\verb does not work in \\{cmd.name}, "
+                        "use \\texttt{\\detokenize{...}} instead",
+                    )
+    return found_error
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    found_error = search(args.files)
+    if found_error:
+        sys.exit("Found \\verb in arguments of other commands")
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
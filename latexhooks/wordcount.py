@@ -0,0 +1,119 @@
+#!/usr/bin/env python3
+import argparse
+import re
+import typing as t
+
+from latexhooks.common import (
+    add_root_argument,
+    blank_spans,
+    check_projects,
+    iter_commands,
+    iter_environments,
+    mask_non_prose,
+    read_project,
+    split_sections,
+)
+
+RE_WORD = re.compile(r"[^\W_]+(?:['’-][^\W_]+)*")
+
+SECTIONING_COMMANDS = ["chapter", "section"]
+
+
+def count_words(text: str) -> int:
+    return len(RE_WORD.findall(text))
+
+
+def mask_captions(text: str) -> str:
+    captions = iter_commands(text, ["caption", "subcaption"])
+    return blank_spans(text, [(cmd.start, cmd.end) for cmd in captions])
+
+
+def count_sections(
+    texts: t.List[t.Tuple[str, str]], exclude_captions: bool
+) -> t.Tuple[t.Dict[str, int], int]:
+    """Return the number of words per section title and in the abstract"""
+    if exclude_captions:
+        texts = [(file_name, mask_captions(text)) for file_name, text in texts]
+
+    abstract = sum(
+        count_words(mask_non_prose(text[env.body_start : env.body_end]))
+        for _, text in texts
+        for env in iter_environments(text, ["abstract"])
+    )
+    sections: t.Dict[str, int] = dict()
+    for section in split_sections(texts, SECTIONING_COMMANDS):
+        prose = mask_non_prose(section.text[section.start : section.end])
+        sections[section.title] = sections.get(section.title, 0) + count_words(prose)
+    return sections, abstract
+
+
+def search(
+    files: t.List[t.IO[str]], budgets: t.Dict[str, int], exclude_captions: bool
+) -> bool:
+    texts = read_project(files)
+    sections, abstract = count_sections(texts, exclude_captions)
+    total = sum(sections.values())
+
+    for title, count in sections.items():
+        print(f"{count:>7} {title or '(no section)'}")
+    print(f"{abstract:>7} (abstract)")
+    print(f"{total:>7} (total)")
+
+    counts = {"total": total, "abstract": abstract}
+    for title, count in sections.items():
+        counts[title.lower()] = counts.get(title.lower(), 0) + count
+
+    found_exceeded = False
+    for name, budget in budgets.items():
+        count = counts.get(name.lower())
+        if count is None:
+            found_exceeded = True
+            print(f"Budget for unknown section {name}")
+        elif count > budget:
+            found_exceeded = True
+            print(f"{name} has {count} words, but the budget is {budget}")
+    return found_exceeded
+
+
+def build_parser() -> argparse.ArgumentParser:
+    parser = argparse.ArgumentParser()
+    parser.add_argument(
+        "--budget",
+        action="append",
+        metavar=("name=words"),
+        type=lambda x: x.rsplit("=", 1),
+        help="Maximum number of words for a section title, 'abstract', or 'total'",
+        default=list(),
+    )
+    parser.add_argument(
+        "--exclude-captions",
+        action="store_true",
+        help="Do not count words in captions",
+    )
+    add_root_argument(parser)
+    parser.add_argument(
+        "files",
+        metavar="FILE",
+        type=lambda x: open(x, encoding="utf-8"),
+        nargs="+",
+        help="List of filenames to search in",
+    )
+    return parser
+
+
+def run(args: argparse.Namespace) -> None:
+    budgets = {name: int(words) for name, words in args.budget}
+    check_projects(
+        args.files,
+        args.root,
+        lambda project: search(project, budgets, args.exclude_captions),
+        "Found sections exceeding their word budget",
+    )
+
+
+def main() -> None:
+    run(build_parser().parse_args())
+
+
+if __name__ == "__main__":
+    main()
@@ -15,10 +15,83 @@ setup(
         "Programming Language :: Python :: 3.11",
     ],
     packages=find_packages(),
+    install_requires=["PyYAML"],
     entry_points={
         "console_scripts": [
             "consistent_spelling = latexhooks.consistent_spelling:main",
             "unique_labels = latexhooks.unique_labels:main",
+            "subcaption_labels = latexhooks.subcaption_labels:main",
+            "tikz_external_names = latexhooks.tikz_external_names:main",
+            "tikz_libraries = latexhooks.tikz_libraries:main",
+            "listing_line_length = latexhooks.listing_line_length:main",
+            "external_references = latexhooks.external_references:main",
+            "language_consistency = latexhooks.language_consistency:main",
+            "heading_length = latexhooks.heading_length:main",
+            "spellcheck = latexhooks.spellcheck:main",
+            "chktex_wrapper = latexhooks.chktex:main",
+            "latexindent_check = latexhooks.latexindent_check:main",
+            "latex_merge_conflict = latexhooks.merge_conflict:main",
+            "bibliography_wiring = latexhooks.bibliography_wiring:main",
+            "package_conflicts = latexhooks.package_conflicts:main",
+            "color_usage = latexhooks.color_usage:main",
+            "number_ranges = latexhooks.number_ranges:main",
+            "latex_spacing = latexhooks.spacing:main",
+            "latin_abbreviations = latexhooks.latin_abbreviations:main",
+            "ensuremath = latexhooks.ensuremath:main",
+            "required_elements = latexhooks.required_elements:main",
+            "wordcount = latexhooks.wordcount:main",
+            "readability = latexhooks.readability:main",
+            "citation_density = latexhooks.citation_density:main",
+            "image_files = latexhooks.image_files:main",
+            "proper_nouns = latexhooks.proper_nouns:main",
+            "footnote_length = latexhooks.footnote_length:main",
+            "table_notes = latexhooks.table_notes:main",
+            "supplementary_references = latexhooks.supplementary_references:main",
+            "date_freshness = latexhooks.date_freshness:main",
+            "preamble_commands = latexhooks.preamble_commands:main",
+            "eof_hygiene = latexhooks.eof_hygiene:main",
+            "siunitx_columns = latexhooks.siunitx_columns:main",
+            "unsectioned_text = latexhooks.unsectioned_text:main",
+            "label_bib_collisions = latexhooks.label_bib_collisions:main",
+            "class_profiles = latexhooks.class_profiles:main",
+            "latex-hooks = latexhooks.cli:main",
+            "environment_spacing = latexhooks.environment_spacing:main",
+            "hyphenation_hacks = latexhooks.hyphenation_hacks:main",
+            "theorem_environments = latexhooks.theorem_environments:main",
+            "term_introductions = latexhooks.term_introductions:main",
+            "abstract_keywords = latexhooks.abstract_keywords:main",
+            "table_row_spacing = latexhooks.table_row_spacing:main",
+            "commented_floats = latexhooks.commented_floats:main",
+            "beamer_frames = latexhooks.beamer_frames:main",
+            "float_centering = latexhooks.float_centering:main",
+            "track_changes = latexhooks.track_changes:main",
+            "label_after_caption = latexhooks.label_after_caption:main",
+            "duplicate_titles = latexhooks.duplicate_titles:main",
+            "unicode_math = latexhooks.unicode_math:main",
+            "verb_in_arguments = latexhooks.verb_in_arguments:main",
+            "unbreakable_strings = latexhooks.unbreakable_strings:main",
+            "colon_capitalization = latexhooks.colon_capitalization:main",
+            "font_encoding = latexhooks.font_encoding:main",
+            "check_encoding = latexhooks.check_encoding:main",
+            "pdf_metadata = latexhooks.pdf_metadata:main",
+            "macro_arguments = latexhooks.macro_arguments:main",
+            "longtable_headers = latexhooks.longtable_headers:main",
+            "bibliography_keys = latexhooks.bibliography_keys:main",
+            "paragraph_lengths = latexhooks.paragraph_lengths:main",
+            "math_fonts = latexhooks.math_fonts:main",
+            "citation_nouns = latexhooks.citation_nouns:main",
+            "math_units = latexhooks.math_units:main",
+            "float_widths = latexhooks.float_widths:main",
+            "footnote_marks = latexhooks.footnote_marks:main",
+            "toc_entries = latexhooks.toc_entries:main",
+            "citation_backends = latexhooks.citation_backends:main",
+            "duplicate_preambles = latexhooks.duplicate_preambles:main",
+            "nonbreaking_spaces = latexhooks.nonbreaking_spaces:main",
+            "theorem_proofs = latexhooks.theorem_proofs:main",
+            "counter_resets = latexhooks.counter_resets:main",
+            "final_document = latexhooks.final_document:main",
+            "caption_repetition = latexhooks.caption_repetition:main",
+            "caption_content = latexhooks.caption_content:main",
         ]
     },
 )
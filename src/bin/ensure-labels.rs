@@ -1,151 +1,211 @@
+use latexhooks_core::rules::{junit_report, text_report, Diagnostic};
+use latexhooks_core::{
+    apply_edits, fix, label_edits, label_insertions, mask_comments, mask_verbatim,
+    offset_to_line_number, reference_edits, rename_conflicts, renames, run_rules, section_slugs,
+    Config, ConflictKind, Disambiguation, Finding, FindingKind, Rename, SlugCase, SlugStyle,
+};
 use once_cell::sync::Lazy;
-use regex::{Captures, Regex};
-use slug::slugify;
+use regex::Regex;
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
 use std::path::{Path, PathBuf};
 
 type Error = Box<dyn std::error::Error + 'static>;
 
-static RE_SECTIONS: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(
-        r#"(?mx) # Enable multiline and ignore whitespace mode
-
-        # Match whitespace but no newline
-        # https://stackoverflow.com/questions/3469080/match-whitespace-but-not-newlines
-        ^[^\S\n]* # Eat leading whitespace
-
-        \\(?P<section_type>(?:sub|subsub)?section)\*?\ *
-        (?:
-            \{
-                # Section content
-                (?P<section_content>
-                    (?:
-                    [^\{\}]* |
-                    # Parse single nested {} blocks
-                    (?:\{[^\{\}]*\})* |
-                    # Parse double nested {} blocks
-                    (?:\{ [^\{\}]*
-                        (?:\{[^\{\}]*\} [^\{\}]*)*
-                    \})*
-                    )+
-                )
-            \}
-            [^\S\n]* # Eat trailing spaces
-            (?P<comment>%[^\n]*)? # Eat optional comment
-            (?:$\n^)? # Optional linebreak
-
-            (?:
-                [^\S\n]* # Eat leading whitespace
-                \\label\{
-                    # Label content
-                    (?P<label>.*)
-                \}$
-            )?
-        |
-            (?P<unparsable_section>.+$)?
-        )
-        "#,
-    )
-    .unwrap()
-});
-
-/// Match a LaTeX Command with 1 or 2 required arquments.
-static RE_LATEX_COMMAND: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(
-        r#"(?x) # Ignore whitespace mode
-        # Parse \ ident [*] {
-        \\\w+ \*? \{
-            (?P<first_arg>
-            [^\{\}]*
-            (?:\{[^\{\}]*\} [^\{\}]*)*
-            )
-        \}
-        # Optional second argument to LaTeX command
-        (?:\{
-            [^\{\}]*
-            (?:\{[^\{\}]*\} [^\{\}]*)*
-        \})?
-        "#,
-    )
-    .unwrap()
-});
-
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-struct Capture<'a> {
-    offset: usize,
-
-    /// String matching the section command, e.g., "subsection"
-    section_type: Option<&'a str>,
-    /// String matching the content of the section command
-    section_content: Option<&'a str>,
-    /// Optional comment on the same line as the section command
-    comment: Option<&'a str>,
-    ///
-    label: Option<&'a str>,
-    unparsable_section: Option<&'a str>,
-}
+/// Match the commands including other files, like `\input{chapters/intro}`
+static RE_INPUT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\\(?:input|include|subfile)\s*\{([^{}]*)\}").unwrap());
 
-impl<'a> From<regex::Captures<'a>> for Capture<'a> {
-    fn from(capture: regex::Captures<'a>) -> Self {
-        Self {
-            offset: capture
-                .get(0)
-                .expect("A capture group 0 always exists as the full match.")
-                .start(),
-            section_type: capture.name("section_type").map(|m| m.as_str()),
-            section_content: capture.name("section_content").map(|m| m.as_str()),
-            comment: capture.name("comment").map(|m| m.as_str()),
-            label: capture.name("label").map(|m| m.as_str()),
-            unparsable_section: capture.name("unparsable_section").map(|m| m.as_str()),
-        }
-    }
-}
+/// Built-in section commands, whose prefixes can be changed with `--prefix`
+const SECTION_TYPES: &[&str] = &[
+    "section",
+    "subsection",
+    "subsubsection",
+    "paragraph",
+    "subparagraph",
+];
 
 #[derive(Clone, Debug, clap::Parser)]
 struct CliArgs {
     files: Vec<PathBuf>,
     #[arg(short, long)]
     ignore_label_content: bool,
+    /// Label prefix of a section type, e.g., `subsection=subsec`. Can be given multiple times.
+    #[arg(long, value_name = "TYPE=PREFIX", value_parser = parse_prefix)]
+    prefix: Vec<(String, String)>,
+    /// User-defined sectioning command and its label prefix, e.g., `paperSection=psec`.
+    /// Can be given multiple times.
+    #[arg(long, value_name = "NAME=PREFIX", value_parser = parse_section_command)]
+    section_command: Vec<(String, String)>,
+    /// Regex which the labels of a section type must match instead of the exact slug, e.g.,
+    /// `section=^sec:[a-z0-9-]+$`. Can be given multiple times.
+    #[arg(long, value_name = "TYPE=REGEX", value_parser = parse_label_pattern)]
+    label_pattern: Vec<(String, Regex)>,
+    /// Separator between the words of lower case labels
+    #[arg(long, default_value = "-", value_parser = ["-", "_", ".", ":"])]
+    slug_separator: String,
+    /// Letter case of the words in labels
+    #[arg(long, value_enum, default_value_t = Case::Lower)]
+    slug_case: Case,
+    /// Check `\paragraph` and `\subparagraph` with the prefixes `par` and `spar` too
+    #[arg(long)]
+    paragraphs: bool,
+    /// Derive labels from the short title of `\section[short]{long}` instead of the long one
+    #[arg(long)]
+    short_title: bool,
+    /// How sections with the same title as an earlier section, also in an earlier file, get
+    /// a unique label
+    #[arg(long, value_enum, default_value_t = Disambiguate::Number)]
+    disambiguate: Disambiguate,
+    /// Word which is left out of labels, e.g., `the`. Can be given multiple times.
+    #[arg(long, value_name = "WORD", value_parser = parse_stop_word)]
+    stop_word: Vec<String>,
+    /// Report `skip-label` comments which do not suppress any finding
+    #[arg(long)]
+    check_suppressions: bool,
+    /// Root document, whose files are checked together for colliding labels in document
+    /// order. Can be given multiple times. Without a root, every file only disambiguates
+    /// its own sections.
+    #[arg(long, value_name = "FILE")]
+    root: Vec<PathBuf>,
+    /// Insert missing labels and rename wrong labels in place, updating all references to
+    /// them in the files. Labels with references which cannot be updated are kept.
+    #[arg(long)]
+    fix: bool,
+    /// Read one JSON document `{"filename": ..., "content": ...}` per line from stdin and
+    /// write the diagnostics for each as one line of JSON to stdout
+    #[arg(long, conflicts_with = "files")]
+    stdin_batch: bool,
+    /// Only report these rules, e.g., `missing-label`. Can be comma separated or given
+    /// multiple times.
+    #[arg(long, value_name = "RULES", value_delimiter = ',', value_parser = FindingKind::RULES)]
+    only: Vec<String>,
+    /// Do not report these rules. Can be comma separated or given multiple times.
+    #[arg(long, value_name = "RULES", value_delimiter = ',', value_parser = FindingKind::RULES)]
+    skip: Vec<String>,
+    /// Output format of the findings
+    #[arg(long, value_enum, default_value_t = Format::Text, conflicts_with_all = ["fix", "stdin_batch"])]
+    format: Format,
 }
 
-enum FileStatus {
-    FoundLabelMismatch,
-    AllLabelsMatch,
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum Case {
+    /// `sec:hello-world`, or `sec:hello_world` with `--slug-separator=_`
+    Lower,
+    /// `sec:helloWorld`
+    Camel,
+    /// `sec:HelloWorld`
+    Pascal,
 }
 
-fn slugify_label(section_type: &str, content: String) -> String {
-    let prefix = match section_type {
-        "section" => "sec",
-        "subsection" => "ssec",
-        "subsubsection" => "sssec",
-        _ => "unknwn",
-    };
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum Disambiguate {
+    /// `ssec:evaluation-2`
+    Number,
+    /// `ssec:method-evaluation`, with the title of the parent section
+    Parent,
+}
 
-    // Remove embedded LaTeX commands in the content part.
-    // Iterate until we reach a fixpoint
-    let mut new_content = content;
-    let mut content = String::new();
-    while content != new_content {
-        content = new_content;
-        new_content = RE_LATEX_COMMAND
-            .replace_all(&content, |capture: &Captures| -> String {
-                capture.name("first_arg").unwrap().as_str().to_string()
-            })
-            .to_string();
-    }
-    content = new_content;
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum Format {
+    /// One `file:line message` line per finding
+    Text,
+    /// JUnit XML with one test case per file, for CI test reports
+    Junit,
+}
 
-    format!("{}:{}", prefix, slugify(content))
+/// Counts of `skip-label` comments seen during a run
+#[derive(Copy, Clone, Default, Debug)]
+struct SuppressionStats {
+    /// All `skip-label` comments on section commands
+    total: usize,
+    /// `skip-label` comments on sections which would not be reported anyway
+    stale: usize,
 }
 
 fn main() {
     let cli_args: CliArgs = clap::Parser::parse();
+    for (section_type, _) in &cli_args.label_pattern {
+        let is_custom = cli_args
+            .section_command
+            .iter()
+            .any(|(name, _)| name == section_type);
+        if !SECTION_TYPES.contains(&section_type.as_str()) && !is_custom {
+            <CliArgs as clap::CommandFactory>::command()
+                .error(
+                    clap::error::ErrorKind::InvalidValue,
+                    format!("unknown section type '{}' in --label-pattern", section_type),
+                )
+                .exit();
+        }
+    }
+    let mut prefixes = cli_args.section_command.clone();
+    prefixes.extend(cli_args.prefix.iter().cloned());
+    let config = Config {
+        ignore_label_content: cli_args.ignore_label_content,
+        prefixes,
+        slug_style: SlugStyle {
+            separator: cli_args.slug_separator.clone(),
+            case: match cli_args.slug_case {
+                Case::Lower => SlugCase::Lower,
+                Case::Camel => SlugCase::Camel,
+                Case::Pascal => SlugCase::Pascal,
+            },
+            stop_words: cli_args.stop_word.clone(),
+        },
+        use_short_title: cli_args.short_title,
+        check_paragraphs: cli_args.paragraphs,
+        section_commands: cli_args
+            .section_command
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect(),
+        label_patterns: cli_args.label_pattern.clone(),
+        disambiguation: match cli_args.disambiguate {
+            Disambiguate::Number => Disambiguation::Number,
+            Disambiguate::Parent => Disambiguation::Parent,
+        },
+        known_slugs: Vec::new(),
+        disabled_rules: FindingKind::RULES
+            .iter()
+            .filter(|rule| {
+                (!cli_args.only.is_empty() && !cli_args.only.iter().any(|only| only == *rule))
+                    || cli_args.skip.iter().any(|skip| skip == *rule)
+            })
+            .map(|rule| rule.to_string())
+            .collect(),
+    };
 
-    let mut has_error = false;
+    if cli_args.stdin_batch {
+        if let Err(err) = process_batch(&config, &cli_args) {
+            eprintln!("Error while reading the batch\n  {}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
 
-    for path in &cli_args.files {
-        match process_file(path, cli_args.ignore_label_content) {
-            Ok(FileStatus::FoundLabelMismatch) => has_error = true,
-            Ok(FileStatus::AllLabelsMatch) => {}
+    let mut has_error = false;
+    let mut stats = SuppressionStats::default();
+    let mut all_renames = Vec::new();
+    let mut results = Vec::new();
+
+    let file_configs = file_configs(&cli_args.files, &config, &cli_args.root);
+    for (path, config) in cli_args.files.iter().zip(&file_configs) {
+        match process_file(
+            path,
+            config,
+            cli_args.check_suppressions,
+            &mut stats,
+            &mut all_renames,
+        ) {
+            Ok(diagnostics) => {
+                has_error |= diagnostics.iter().any(|diagnostic| diagnostic.is_error);
+                if cli_args.format == Format::Text {
+                    print!("{}", text_report(&path.display().to_string(), &diagnostics));
+                }
+                results.push((path.clone(), diagnostics));
+            }
             Err(err) => {
                 has_error = true;
                 eprintln!("Error in file {}\n  {}", path.display(), err);
@@ -159,361 +219,435 @@ fn main() {
         }
     }
 
+    if cli_args.fix {
+        if let Err(err) = skip_conflicting_renames(&cli_args.files, &config, &mut all_renames) {
+            eprintln!("Error while indexing the references\n  {}", err);
+            std::process::exit(1);
+        }
+        for (path, config) in cli_args.files.iter().zip(&file_configs) {
+            if let Err(err) = fix_file(path, config, &all_renames) {
+                has_error = true;
+                eprintln!("Error in file {}\n  {}", path.display(), err);
+            }
+        }
+    }
+
+    if cli_args.format == Format::Junit {
+        print!(
+            "{}",
+            junit_report(
+                "ensure-labels",
+                "Labels do not match the sections",
+                &FindingKind::RULES,
+                &results
+            )
+        );
+    } else if cli_args.check_suppressions && stats.total > 0 {
+        println!(
+            "Found {} skip-label suppressions, {} of them stale",
+            stats.total, stats.stale
+        );
+    }
+
     if has_error {
         std::process::exit(1);
     }
 }
 
-fn process_file(file: &Path, ignore_label_content: bool) -> Result<FileStatus, Error> {
-    let mut found_mismatch = false;
-    let text = std::fs::read_to_string(file)?;
-
-    RE_SECTIONS.captures_iter(&text).for_each(|capture| {
-        let capture: Capture = capture.into();
-        let line_number = offset_to_line_number(&text, capture.offset);
-
-        if let Some(_unparsable_section) = capture.unparsable_section {
-            println!("{}:{} Unprocessable Section", file.display(), line_number,);
-        } else {
-            let section_type = capture
-                .section_type
-                .expect("A section_type must exist if the regex is parsable.");
-            let section_content = capture
-                .section_content
-                .expect("A section_type must exist if the regex is parsable.");
-            let slug = slugify_label(section_type, section_content.to_string());
-
-            match capture.label {
-                None => {
-                    found_mismatch = true;
-                    println!(
-                        "{}:{} Missing Label, use \\label{{{}}}",
-                        file.display(),
-                        line_number,
-                        slug
-                    );
-                }
-                Some(label) => {
-                    if label != slug
-                        && !ignore_label_content
-                        && !capture
-                            .comment
-                            .map(|cmt| cmt.contains("skip-label"))
-                            .unwrap_or(false)
-                    {
-                        let line_number = offset_to_line_number(&text, capture.offset);
-                        found_mismatch = true;
-                        println!(
-                            "{}:{} Wrong Label '{}', use \\label{{{}}}",
-                            file.display(),
-                            line_number,
-                            label,
-                            slug
-                        );
-                    }
-                }
-            }
-        }
-    });
+fn parse_prefix(value: &str) -> Result<(String, String), String> {
+    let (section_type, prefix) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected TYPE=PREFIX, got '{}'", value))?;
+    if !SECTION_TYPES.contains(&section_type) {
+        return Err(format!("unknown section type '{}'", section_type));
+    }
+    check_prefix(prefix)?;
+    Ok((section_type.to_string(), prefix.to_string()))
+}
 
-    if found_mismatch {
-        Ok(FileStatus::FoundLabelMismatch)
-    } else {
-        Ok(FileStatus::AllLabelsMatch)
+fn parse_section_command(value: &str) -> Result<(String, String), String> {
+    let (name, prefix) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=PREFIX, got '{}'", value))?;
+    let name = name.strip_prefix('\\').unwrap_or(name);
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(format!("invalid command name '{}'", name));
+    }
+    if SECTION_TYPES.contains(&name) {
+        return Err(format!("'{}' is built in, use --prefix instead", name));
     }
+    check_prefix(prefix)?;
+    Ok((name.to_string(), prefix.to_string()))
 }
 
-#[cfg(test)]
-mod test_regex {
-    use super::*;
-    use pretty_assertions::assert_eq;
+fn parse_label_pattern(value: &str) -> Result<(String, Regex), String> {
+    let (section_type, pattern) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected TYPE=REGEX, got '{}'", value))?;
+    // The section type may also be a user-defined command, which is checked in `main`
+    let section_type = section_type.strip_prefix('\\').unwrap_or(section_type);
+    let pattern = Regex::new(pattern).map_err(|err| err.to_string())?;
+    Ok((section_type.to_string(), pattern))
+}
 
-    /// Parse a lone section
-    #[test]
-    fn only_section() {
-        let text = r##"\section{Hello World}"##;
-        let captures: Capture = RE_SECTIONS
-            .captures(text)
-            .expect("Regex needs to match")
-            .into();
-        let expected = Capture {
-            offset: 0,
-            section_type: Some("section"),
-            section_content: Some("Hello World"),
-            comment: None,
-            label: None,
-            unparsable_section: None,
-        };
-        assert_eq!(captures, expected);
+fn check_prefix(prefix: &str) -> Result<(), String> {
+    if prefix.is_empty() || prefix.contains(['{', '}', '\\', ',']) {
+        return Err(format!("invalid label prefix '{}'", prefix));
     }
+    Ok(())
+}
 
-    /// Parse a section with comment
-    #[test]
-    fn only_section_with_comment() {
-        let text = r##"\section{Hello World} % Comment"##;
-        let captures: Capture = RE_SECTIONS
-            .captures(text)
-            .expect("Regex needs to match")
-            .into();
-        let expected = Capture {
-            offset: 0,
-            section_type: Some("section"),
-            section_content: Some("Hello World"),
-            comment: Some("% Comment"),
-            label: None,
-            unparsable_section: None,
-        };
-        assert_eq!(captures, expected);
+fn parse_stop_word(value: &str) -> Result<String, String> {
+    // Stop words are compared to the words of the slug, which are lower case ASCII
+    if value.is_empty() || !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("invalid stop word '{}'", value));
     }
+    Ok(value.to_ascii_lowercase())
+}
 
-    #[test]
-    fn section_and_label() {
-        let text = r##"\section{Hello World}
-\label{Label-ABC}"##;
-        let captures: Capture = RE_SECTIONS
-            .captures(text)
-            .expect("Regex needs to match")
-            .into();
-        let expected = Capture {
-            offset: 0,
-            section_type: Some("section"),
-            section_content: Some("Hello World"),
-            comment: None,
-            label: Some("Label-ABC"),
-            unparsable_section: None,
-        };
-        assert_eq!(captures, expected);
+fn process_file(
+    file: &Path,
+    config: &Config,
+    check_suppressions: bool,
+    stats: &mut SuppressionStats,
+    all_renames: &mut Vec<(PathBuf, Rename)>,
+) -> Result<Vec<Diagnostic>, Error> {
+    let text = std::fs::read_to_string(file)?;
+    let findings = run_rules(&text, config);
+
+    let diagnostics = diagnostics(&text, &findings, check_suppressions, stats);
+    all_renames.extend(
+        renames(&text, &findings, config)
+            .into_iter()
+            .map(|rename| (file.to_path_buf(), rename)),
+    );
+    Ok(diagnostics)
+}
+
+/// Turn the findings into the messages shown to the user
+fn diagnostics(
+    text: &str,
+    findings: &[Finding],
+    check_suppressions: bool,
+    stats: &mut SuppressionStats,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for finding in findings {
+        match &finding.kind {
+            FindingKind::Suppressed { .. } => {
+                stats.total += 1;
+                continue;
+            }
+            FindingKind::StaleSuppression => {
+                stats.total += 1;
+                stats.stale += 1;
+                if !check_suppressions {
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        diagnostics.push(Diagnostic {
+            rule: finding.kind.rule().to_string(),
+            line: offset_to_line_number(text, finding.offset),
+            message: finding.kind.message(),
+            is_error: finding.kind.is_error(),
+        });
     }
+    diagnostics
+}
 
-    /// Parse a section and comment and label
-    #[test]
-    fn section_with_comment_and_label() {
-        let text = r##"\section{Hello World} % Another Comment
-\label{Here}"##;
-        let captures: Capture = RE_SECTIONS
-            .captures(text)
-            .expect("Regex needs to match")
-            .into();
-        let expected = Capture {
-            offset: 0,
-            section_type: Some("section"),
-            section_content: Some("Hello World"),
-            comment: Some("% Another Comment"),
-            label: Some("Here"),
-            unparsable_section: None,
-        };
-        assert_eq!(captures, expected);
+/// Check the documents of the `--stdin-batch` protocol until stdin is closed
+///
+/// Each output line contains the `filename`, the list of `diagnostics` with `line`,
+/// `message`, and `error`, and with `--fix` the fixed `content`. Malformed input lines
+/// are answered with an `error` message instead.
+fn process_batch(config: &Config, cli_args: &CliArgs) -> Result<(), Error> {
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    let mut stdout = stdout.lock();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        writeln!(stdout, "{}", batch_answer(&line, config, cli_args))?;
+        // Tool servers wait for the answer before sending the next document
+        stdout.flush()?;
     }
+    Ok(())
+}
 
-    /// Put section and label on the same line
-    #[test]
-    fn section_and_label_same_line() {
-        let text = r##"\section{Hello World} \label{Label-123}"##;
-        let captures: Capture = RE_SECTIONS
-            .captures(text)
-            .expect("Regex needs to match")
-            .into();
-        let expected = Capture {
-            offset: 0,
-            section_type: Some("section"),
-            section_content: Some("Hello World"),
-            comment: None,
-            label: Some("Label-123"),
-            unparsable_section: None,
-        };
-        assert_eq!(captures, expected);
+/// Return the JSON answer to a line of the `--stdin-batch` protocol
+fn batch_answer(line: &str, config: &Config, cli_args: &CliArgs) -> String {
+    let document: serde_json::Value = match serde_json::from_str(line) {
+        Ok(document) => document,
+        Err(err) => return json!({ "error": err.to_string() }).to_string(),
+    };
+    let (filename, content) = match (
+        document.get("filename").and_then(serde_json::Value::as_str),
+        document.get("content").and_then(serde_json::Value::as_str),
+    ) {
+        (Some(filename), Some(content)) => (filename, content),
+        _ => {
+            return json!({ "error": "Expected the string fields filename and content" })
+                .to_string()
+        }
+    };
+    let mut stats = SuppressionStats::default();
+    let findings = run_rules(content, config);
+    let diagnostics: Vec<_> =
+        diagnostics(content, &findings, cli_args.check_suppressions, &mut stats)
+            .into_iter()
+            .map(|diagnostic| {
+                json!({
+                    "line": diagnostic.line,
+                    "message": diagnostic.message,
+                    "error": diagnostic.is_error,
+                })
+            })
+            .collect();
+    let mut answer = json!({ "filename": filename, "diagnostics": diagnostics });
+    if cli_args.fix {
+        answer["content"] = json!(fix(content, config));
     }
+    answer.to_string()
+}
 
-    /// Check for `\section*`
-    #[test]
-    fn section_star_and_label() {
-        let text = r##"
-
-\section*{Hello World}
-\label{Label-ABC}"##;
-        let captures: Capture = RE_SECTIONS
-            .captures(text)
-            .expect("Regex needs to match")
-            .into();
-        let expected = Capture {
-            offset: 2,
-            section_type: Some("section"),
-            section_content: Some("Hello World"),
-            comment: None,
-            label: Some("Label-ABC"),
-            unparsable_section: None,
-        };
-        assert_eq!(captures, expected);
+/// Return the configuration of each file, in which the slugs of the earlier files of its
+/// document are taken
+///
+/// A document are the files included by a root in document order, which does not depend
+/// on the files passed on the command line or their order. Files are part of the document
+/// of the first root, sorted by name, which includes them. Files included by no root only
+/// disambiguate their own sections.
+fn file_configs(files: &[PathBuf], config: &Config, roots: &[PathBuf]) -> Vec<Config> {
+    let mut roots: Vec<PathBuf> = roots
+        .iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .collect();
+    roots.sort();
+    let mut known_slugs: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    for root in &roots {
+        let mut document_slugs = Vec::new();
+        for path in document_files(root) {
+            if known_slugs.contains_key(&path) {
+                continue;
+            }
+            let file_config = Config {
+                known_slugs: document_slugs.clone(),
+                ..config.clone()
+            };
+            // Unreadable files are skipped, their errors are reported when checking them
+            if let Ok(text) = std::fs::read_to_string(&path) {
+                document_slugs.extend(
+                    section_slugs(&text, &file_config)
+                        .into_iter()
+                        .map(|(_, slug)| slug),
+                );
+            }
+            known_slugs.insert(path, file_config.known_slugs);
+        }
     }
+    files
+        .iter()
+        .map(|path| Config {
+            known_slugs: path
+                .canonicalize()
+                .ok()
+                .and_then(|path| known_slugs.remove(&path))
+                .unwrap_or_default(),
+            ..config.clone()
+        })
+        .collect()
+}
 
-    /// Check parsing a single latex command in section
-    #[test]
-    fn section_with_nested_command() {
-        let text = r##"\section{\textbf{bold}}"##;
-        let captures: Capture = RE_SECTIONS
-            .captures(text)
-            .expect("Regex needs to match")
-            .into();
-        let expected = Capture {
-            offset: 0,
-            section_type: Some("section"),
-            section_content: Some("\\textbf{bold}"),
-            comment: None,
-            label: None,
-            unparsable_section: None,
-        };
-        assert_eq!(captures, expected);
+/// Return the root and all files it includes, recursively, in document order
+///
+/// Files included via `\input`, `\include`, and `\subfile` are resolved relative to the
+/// directory of the root, as LaTeX does. Missing files are skipped.
+fn document_files(root: &Path) -> Vec<PathBuf> {
+    fn visit(path: PathBuf, directory: &Path, files: &mut Vec<PathBuf>) {
+        if files.contains(&path) {
+            return;
+        }
+        let text = std::fs::read_to_string(&path).unwrap_or_default();
+        files.push(path);
+        for capture in RE_INPUT.captures_iter(&mask_comments(&mask_verbatim(&text))) {
+            let included = directory.join(capture[1].trim());
+            let mut with_extension = included.clone().into_os_string();
+            with_extension.push(".tex");
+            let with_extension = PathBuf::from(with_extension);
+            let resolved = if included.is_file() {
+                included.canonicalize()
+            } else {
+                with_extension.canonicalize()
+            };
+            match resolved {
+                Ok(resolved) if resolved.is_file() => visit(resolved, directory, files),
+                _ => {}
+            }
+        }
     }
 
-    /// Check parsing multiple nested latex commands in section
-    #[test]
-    fn section_with_double_nested_command_and_label() {
-        let text = r##"\subsubsection{Formalization of \texorpdfstring{\acs{knn}}{k-NN}}
-\label{sssec:formalization-of-knn}"##;
-        let captures: Capture = RE_SECTIONS
-            .captures(text)
-            .expect("Regex needs to match")
-            .into();
-        let expected = Capture {
-            offset: 0,
-            section_type: Some("subsubsection"),
-            section_content: Some(r"Formalization of \texorpdfstring{\acs{knn}}{k-NN}"),
-            comment: None,
-            label: Some("sssec:formalization-of-knn"),
-            unparsable_section: None,
-        };
-        assert_eq!(captures, expected);
+    let mut files = Vec::new();
+    let directory = root.parent().unwrap_or(Path::new("."));
+    visit(root.to_path_buf(), directory, &mut files);
+    files
+}
+
+/// Remove the renames which would leave references in any of the `files` dangling
+fn skip_conflicting_renames(
+    files: &[PathBuf],
+    config: &Config,
+    all_renames: &mut Vec<(PathBuf, Rename)>,
+) -> Result<(), Error> {
+    let texts = files
+        .iter()
+        .map(std::fs::read_to_string)
+        .collect::<Result<Vec<_>, _>>()?;
+    let text_refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+    let renames: Vec<Rename> = all_renames
+        .iter()
+        .map(|(_, rename)| rename.clone())
+        .collect();
+
+    let conflicts = rename_conflicts(&text_refs, &renames, config);
+    for conflict in &conflicts {
+        match conflict.kind {
+            ConflictKind::UnrewritableReference { text, offset } => println!(
+                "{}:{} Not renaming label '{}', the reference in the section title cannot be updated",
+                files[text].display(),
+                offset_to_line_number(&texts[text], offset),
+                conflict.label
+            ),
+            ConflictKind::DuplicateLabel => {
+                for (path, rename) in all_renames.iter() {
+                    let idx = match files.iter().position(|file| file == path) {
+                        Some(idx) if rename.old == conflict.label => idx,
+                        _ => continue,
+                    };
+                    println!(
+                        "{}:{} Not renaming label '{}', it is defined more than once",
+                        path.display(),
+                        offset_to_line_number(&texts[idx], rename.offset),
+                        conflict.label
+                    );
+                }
+            }
+        }
     }
+    all_renames.retain(|(_, rename)| {
+        conflicts
+            .iter()
+            .all(|conflict| conflict.label != rename.old)
+    });
+    Ok(())
+}
 
-    /// Check using a subsection
-    #[test]
-    fn only_subsection() {
-        let text = r##"\subsection{SubSec}"##;
-        let captures: Capture = RE_SECTIONS.captures(text).unwrap().into();
-        let expected = Capture {
-            offset: 0,
-            section_type: Some("subsection"),
-            section_content: Some("SubSec"),
-            comment: None,
-            label: None,
-            unparsable_section: None,
-        };
-        assert_eq!(captures, expected);
+/// Insert missing labels, rename the labels defined in `file`, and update all references
+/// to renamed labels
+fn fix_file(file: &Path, config: &Config, all_renames: &[(PathBuf, Rename)]) -> Result<(), Error> {
+    let text = std::fs::read_to_string(file)?;
+    let own_renames: Vec<Rename> = all_renames
+        .iter()
+        .filter(|(path, _)| path == file)
+        .map(|(_, rename)| rename.clone())
+        .collect();
+    let renames: Vec<Rename> = all_renames
+        .iter()
+        .map(|(_, rename)| rename.clone())
+        .collect();
+
+    let mut edits = Vec::new();
+    for edit in label_insertions(&text, &run_rules(&text, config), config) {
+        println!(
+            "{}:{} Inserted {}",
+            file.display(),
+            offset_to_line_number(&text, edit.start),
+            edit.content.trim(),
+        );
+        edits.push(edit);
+    }
+    for rename in &own_renames {
+        for edit in label_edits(&text, std::slice::from_ref(rename), config) {
+            println!(
+                "{}:{} Renamed label '{}' to '{}'",
+                file.display(),
+                offset_to_line_number(&text, edit.start),
+                rename.old,
+                rename.new
+            );
+            edits.push(edit);
+        }
+    }
+    for edit in reference_edits(&text, &renames, config) {
+        println!(
+            "{}:{} Updated reference to renamed label",
+            file.display(),
+            offset_to_line_number(&text, edit.start),
+        );
+        edits.push(edit);
     }
 
-    /// Test if we can handle things outside of our current regex
-    #[test]
-    fn unsupported_section_content() {
-        let text = r##"\subsection{A{B{C{D{EE}D}C}B}A}"##;
-        let captures: Capture = RE_SECTIONS.captures(text).unwrap().into();
-        let expected = Capture {
-            offset: 0,
-            section_type: Some("subsection"),
-            section_content: None,
-            comment: None,
-            label: None,
-            unparsable_section: Some("{A{B{C{D{EE}D}C}B}A}"),
-        };
-        assert_eq!(captures, expected);
+    if edits.is_empty() {
+        return Ok(());
     }
+    std::fs::write(file, apply_edits(&text, edits))?;
+    Ok(())
 }
 
 #[cfg(test)]
-mod test_slugify_label {
+mod test_batch {
     use super::*;
+    use pretty_assertions::assert_eq;
 
-    #[test]
-    fn simple_ascii() {
-        assert_eq!(slugify_label("section", "Word".to_string()), "sec:word");
-        assert_eq!(
-            slugify_label("section", "Hello World".to_string()),
-            "sec:hello-world"
-        );
-        assert_eq!(
-            slugify_label("subsubsection", "Many Many words here".to_string()),
-            "sssec:many-many-words-here"
-        );
+    fn answer(line: &str, args: &[&str]) -> String {
+        let cli_args: CliArgs =
+            clap::Parser::parse_from(["ensure-labels", "--stdin-batch"].iter().chain(args));
+        batch_answer(line, &Config::default(), &cli_args)
     }
 
     #[test]
-    fn nested_commands() {
+    fn documents() {
+        let line = r#"{"filename": "intro.tex", "content": "\\section{Intro}\n"}"#;
         assert_eq!(
-            slugify_label("section", r"\texttt{Abc}".to_string()),
-            "sec:abc"
+            answer(line, &[]),
+            r#"{"filename":"intro.tex","diagnostics":[{"line":1,"message":"Missing Label, use \\label{sec:intro}","error":true}]}"#
         );
         assert_eq!(
-            slugify_label("subsection", r"Something \emph{very} important".to_string()),
-            "ssec:something-very-important"
+            answer(line, &["--fix"]),
+            r#"{"filename":"intro.tex","diagnostics":[{"line":1,"message":"Missing Label, use \\label{sec:intro}","error":true}],"content":"\\section{Intro}\n\\label{sec:intro}\n"}"#
         );
-    }
-
-    #[test]
-    fn commands_with_star() {
+        let line = r#"{"filename": "\u00fc\ud83d\ude00.tex", "content": ""}"#;
         assert_eq!(
-            slugify_label("section", r"Unused abbreviation \ac*{Abc}".to_string()),
-            "sec:unused-abbreviation-abc"
+            answer(line, &[]),
+            r#"{"filename":"ü😀.tex","diagnostics":[]}"#
         );
     }
 
     #[test]
-    fn double_nested_commands() {
+    fn errors() {
         assert_eq!(
-            slugify_label(
-                "subsubsection",
-                r"Formalization of \texorpdfstring{\acs{knn}}{k-NN}".to_string()
-            ),
-            "sssec:formalization-of-knn"
+            answer(r#"{"filename": "a.tex"}"#, &[]),
+            r#"{"error":"Expected the string fields filename and content"}"#
         );
-    }
-}
-
-fn offset_to_line_number(text: &str, offset: usize) -> u32 {
-    if offset > text.len() {
-        panic!("ERROR");
-    }
-
-    let mut line_number = 1;
-    for (idx, c) in text.char_indices() {
-        if idx >= offset {
-            return line_number;
-        }
-
-        if c == '\n' {
-            line_number += 1;
+        assert_eq!(
+            answer(r#"{"filename": "a.tex", "content": 1}"#, &[]),
+            r#"{"error":"Expected the string fields filename and content"}"#
+        );
+        for line in [
+            "{",
+            "[1, 2",
+            r#"{"content": 01}"#,
+            r#"{"content": 1-2}"#,
+            r#"{"content": "\x"}"#,
+            r#""\ud800""#,
+        ] {
+            assert!(answer(line, &[]).starts_with(r#"{"error":"#), "{}", line);
         }
-    }
-
-    panic!("This shouldn't happen as we check offset before");
-}
-
-#[cfg(test)]
-mod test_offset_to_line_number {
-    use super::*;
-
-    #[test]
-    fn simple_ascii() {
-        let text = r#"Hello
-Nice
-World
-"#;
-        assert_eq!(offset_to_line_number(text, 0), 1);
-        assert_eq!(offset_to_line_number(text, 1), 1);
-        assert_eq!(offset_to_line_number(text, 2), 1);
-        assert_eq!(offset_to_line_number(text, 3), 1);
-        assert_eq!(offset_to_line_number(text, 4), 1);
-        assert_eq!(offset_to_line_number(text, 5), 1);
-
-        assert_eq!(offset_to_line_number(text, 6), 2);
-        assert_eq!(offset_to_line_number(text, 7), 2);
-        assert_eq!(offset_to_line_number(text, 8), 2);
-        assert_eq!(offset_to_line_number(text, 9), 2);
-        assert_eq!(offset_to_line_number(text, 10), 2);
-
-        assert_eq!(offset_to_line_number(text, 11), 3);
-        assert_eq!(offset_to_line_number(text, 12), 3);
-        assert_eq!(offset_to_line_number(text, 13), 3);
-        assert_eq!(offset_to_line_number(text, 14), 3);
-        assert_eq!(offset_to_line_number(text, 15), 3);
-        assert_eq!(offset_to_line_number(text, 16), 3);
+        // Deeply nested documents are rejected instead of overflowing the stack
+        let nested = "[".repeat(100_000) + &"]".repeat(100_000);
+        assert!(answer(&nested, &[]).starts_with(r#"{"error":"recursion limit"#));
     }
 }
@@ -0,0 +1,1886 @@
+//! Rules of the `ensure-labels` hook, usable without the command line interface.
+//!
+//! [`run_rules`] finds all problems in a LaTeX text and [`fix`] inserts missing labels
+//! and rewrites wrong labels together with the references to them.
+
+pub mod rules;
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use slug::slugify;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// Pattern of a section command with an optional label on the next line
+///
+/// `SECTION_COMMANDS` is replaced by the alternatives of the command names.
+const SECTIONS_PATTERN: &str = r#"(?mx) # Enable multiline and ignore whitespace mode
+
+        # Match whitespace but no newline
+        # https://stackoverflow.com/questions/3469080/match-whitespace-but-not-newlines
+        ^[^\S\n]* # Eat leading whitespace
+
+        \\(?P<section_type>SECTION_COMMANDS)\*?\ *
+        # Optional short title for the table of contents and the page headers
+        (?:
+            \[
+                (?P<short_title>(?:[^\[\]\{\}\n] | \{[^\{\}\n]*\})*)
+            \]\ *
+        )?
+        (?:
+            \{
+                # Section content
+                (?P<section_content>
+                    (?:
+                    [^\{\}]* |
+                    # Parse single nested {} blocks
+                    (?:\{[^\{\}]*\})* |
+                    # Parse double nested {} blocks
+                    (?:\{ [^\{\}]*
+                        (?:\{[^\{\}]*\} [^\{\}]*)*
+                    \})*
+                    )+
+                )
+            \}
+            [^\S\n]* # Eat trailing spaces
+            (?P<comment>%[^\n]*)? # Eat optional comment
+            (?:$\n^)? # Optional linebreak
+
+            (?:
+                [^\S\n]* # Eat leading whitespace
+                \\label\{
+                (?:
+                    # Run-in headings like `\paragraph` continue with text after the label
+                    (?P<run_in_label>[^\{\}\n]*)
+                    \}[^\S\n]*
+                    (?P<run_in_text>\S[^\n]*)$
+                |
+                    # Label content
+                    (?P<label>.*)
+                    \}$
+                )
+            )?
+        |
+            (?P<unparsable_section>.+$)?
+        )
+        "#;
+
+static RE_SECTIONS: Lazy<Regex> = Lazy::new(|| sections_regex(&[]));
+
+/// Headings below `\subsubsection`, which are only checked if enabled
+const PARAGRAPH_COMMANDS: &[&str] = &["paragraph", "subparagraph"];
+
+/// Build the regex matching the built-in and the custom section commands
+fn sections_regex(commands: &[String]) -> Regex {
+    // Longer names first, such that `\paperSectionTitle` is not matched as `\paperSection`
+    let mut commands: Vec<&String> = commands.iter().collect();
+    commands.sort_by_key(|command| std::cmp::Reverse(command.len()));
+    let mut names: Vec<String> = commands
+        .into_iter()
+        .map(|command| format!(r"{}\b", regex::escape(command)))
+        .collect();
+    names.push("(?:sub|subsub)?section".to_string());
+    Regex::new(&SECTIONS_PATTERN.replace("SECTION_COMMANDS", &names.join("|"))).unwrap()
+}
+
+/// Match a LaTeX Command with 1 or 2 required arquments.
+static RE_LATEX_COMMAND: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?x) # Ignore whitespace mode
+        # Parse \ ident [*] {
+        \\\w+ \*? \{
+            (?P<first_arg>
+            [^\{\}]*
+            (?:\{[^\{\}]*\} [^\{\}]*)*
+            )
+        \}
+        # Optional second argument to LaTeX command
+        (?:\{
+            [^\{\}]*
+            (?:\{[^\{\}]*\} [^\{\}]*)*
+        \})?
+        "#,
+    )
+    .unwrap()
+});
+
+/// Environments whose content is not LaTeX, like code listings or commented-out text
+///
+/// The Python hooks skip the same environments.
+pub const VERBATIM_ENVIRONMENTS: &[&str] =
+    &["verbatim", "Verbatim", "lstlisting", "minted", "comment"];
+
+/// Match the beginning of a verbatim-like environment at the start of the text
+static RE_VERBATIM_BEGIN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"^\\begin\s*\{{(?P<name>(?:{})\*?)\}}",
+        VERBATIM_ENVIRONMENTS.join("|")
+    ))
+    .unwrap()
+});
+
+/// Match an inline verbatim command up to its delimiter, e.g., `\verb|x|` or `\mintinline{c}{x}`
+static RE_INLINE_VERBATIM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\\(?:verb\*?|lstinline(?:\[[^\]\n]*\])?|mintinline(?:\[[^\]\n]*\])?\{[^\{\}\n]*\})",
+    )
+    .unwrap()
+});
+
+/// Match a reference to one or multiple labels, e.g., `\cref{sec:a,sec:b}`.
+static RE_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?x) # Ignore whitespace mode
+        \\(?:ref|eqref|pageref|autoref|nameref|vref|cref|Cref|cpageref|Cpageref|labelcref|namecref|nameCref)
+        \*? \{
+            (?P<labels>[^\{\}]*)
+        \}
+        "#,
+    )
+    .unwrap()
+});
+
+/// Match the definition of a label, e.g., `\label{sec:a}`.
+static RE_LABEL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\\label\{(?P<label>[^\{\}\n]*)\}").unwrap());
+
+/// Match a `% latex-hooks: off` or `% latex-hooks: on` directive with optional rule names
+static RE_DIRECTIVE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)(?:^|[^\\])%[ \t]*latex-hooks:[ \t]*(?P<state>off|on)\b(?P<rules>[ \t]*[\w-]+(?:[ \t]*,[ \t]*[\w-]+)*)?",
+    )
+    .unwrap()
+});
+
+/// Names of the hook in directives, which disable all of its rules
+const HOOK_NAMES: &[&str] = &["ensure-labels", "ensure-labels-for-sections"];
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) struct Capture<'a> {
+    offset: usize,
+
+    /// String matching the section command, e.g., "subsection"
+    section_type: Option<&'a str>,
+    /// String matching the optional short title of the section command
+    short_title: Option<&'a str>,
+    /// String matching the content of the section command
+    section_content: Option<&'a str>,
+    /// Optional comment on the same line as the section command
+    comment: Option<&'a str>,
+    /// Content of the label following the section command
+    label: Option<&'a str>,
+    unparsable_section: Option<&'a str>,
+}
+
+impl<'a> From<regex::Captures<'a>> for Capture<'a> {
+    fn from(capture: regex::Captures<'a>) -> Self {
+        // Only paragraphs may continue with text after their label
+        let is_run_in = capture
+            .name("section_type")
+            .is_some_and(|m| PARAGRAPH_COMMANDS.contains(&m.as_str()));
+        Self {
+            offset: capture
+                .get(0)
+                .expect("A capture group 0 always exists as the full match.")
+                .start(),
+            section_type: capture.name("section_type").map(|m| m.as_str()),
+            short_title: capture.name("short_title").map(|m| m.as_str()),
+            section_content: capture.name("section_content").map(|m| m.as_str()),
+            comment: capture.name("comment").map(|m| m.as_str()),
+            label: capture
+                .name("label")
+                .or_else(|| capture.name("run_in_label").filter(|_| is_run_in))
+                .map(|m| m.as_str()),
+            unparsable_section: capture.name("unparsable_section").map(|m| m.as_str()),
+        }
+    }
+}
+
+/// Options changing which findings [`run_rules`] reports
+#[derive(Clone, Default, Debug)]
+pub struct Config {
+    /// Only check that a label exists, but not its value
+    pub ignore_label_content: bool,
+    /// Label prefixes of section types, overriding [`default_prefix`]
+    pub prefixes: Vec<(String, String)>,
+    /// How the words of the section title are turned into the label
+    pub slug_style: SlugStyle,
+    /// Slugify the short title `\section[short]{long}` instead of the long one
+    pub use_short_title: bool,
+    /// Names of user-defined commands which are checked like `\section`, their label
+    /// prefixes are taken from `prefixes`
+    pub section_commands: Vec<String>,
+    /// Check `\paragraph` and `\subparagraph` too
+    pub check_paragraphs: bool,
+    /// Regexes which the labels of a section type must match, instead of being the slug
+    pub label_patterns: Vec<(String, Regex)>,
+    /// How sections with the same slug as an earlier section get a unique one
+    pub disambiguation: Disambiguation,
+    /// Slugs of the sections in files checked before this one, which are already taken
+    pub known_slugs: Vec<String>,
+    /// Names of rules which are not reported, as if disabled by `% latex-hooks: off <rule>`
+    /// for the whole text
+    pub disabled_rules: Vec<String>,
+}
+
+/// How a section whose slug collides with an earlier section gets a unique slug
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Disambiguation {
+    /// Append a number, e.g., `ssec:evaluation-2`
+    #[default]
+    Number,
+    /// Prepend the title of the parent section, e.g., `ssec:method-evaluation`, and append
+    /// a number if this is not unique either
+    Parent,
+}
+
+impl Config {
+    /// Return the label prefix of the section type, e.g., "sec" for "section"
+    pub fn prefix<'a>(&'a self, section_type: &str) -> &'a str {
+        self.prefixes
+            .iter()
+            .rev()
+            .find(|(name, _)| name == section_type)
+            .map(|(_, prefix)| prefix.as_str())
+            .unwrap_or_else(|| default_prefix(section_type))
+    }
+
+    /// Return the regex which labels of the section type must match, if any
+    pub fn label_pattern(&self, section_type: &str) -> Option<&Regex> {
+        self.label_patterns
+            .iter()
+            .rev()
+            .find(|(name, _)| name == section_type)
+            .map(|(_, pattern)| pattern)
+    }
+
+    /// Return the regex matching all section commands of this configuration
+    fn sections_regex(&self) -> &'static Regex {
+        // The regexes are kept for the whole run, as only a few configurations are used
+        static CUSTOM_SECTIONS: Lazy<Mutex<HashMap<Vec<String>, &'static Regex>>> =
+            Lazy::new(Default::default);
+
+        let mut commands = self.section_commands.clone();
+        if self.check_paragraphs {
+            commands.extend(PARAGRAPH_COMMANDS.iter().map(|name| name.to_string()));
+        }
+        if commands.is_empty() {
+            return &RE_SECTIONS;
+        }
+        let mut custom_sections = CUSTOM_SECTIONS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        if let Some(regex) = custom_sections.get(&commands) {
+            return regex;
+        }
+        let regex = Box::leak(Box::new(sections_regex(&commands)));
+        custom_sections.insert(commands, regex);
+        regex
+    }
+
+    /// Return the expected label of a section, e.g., "sec:hello-world"
+    pub fn slug(&self, section_type: &str, content: String) -> String {
+        slugify_with_style(self.prefix(section_type), content, &self.slug_style)
+    }
+}
+
+/// Return the level of the section type in the document hierarchy, 1 for `\section`
+///
+/// User-defined section commands are on the level of `\section`.
+fn section_level(section_type: &str) -> usize {
+    match section_type {
+        "subsection" => 2,
+        "subsubsection" => 3,
+        "paragraph" => 4,
+        "subparagraph" => 5,
+        _ => 1,
+    }
+}
+
+/// Letter case of the words in a slug
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum SlugCase {
+    /// All words in lower case, joined by the separator, e.g., `hello-world`
+    #[default]
+    Lower,
+    /// The first word in lower case and all others capitalized, e.g., `helloWorld`
+    Camel,
+    /// All words capitalized, e.g., `HelloWorld`
+    Pascal,
+}
+
+/// Options of the slug generation
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlugStyle {
+    /// Separator between the words of [`SlugCase::Lower`] slugs
+    pub separator: String,
+    pub case: SlugCase,
+    /// Lower case words which are removed, unless the title consists only of them
+    pub stop_words: Vec<String>,
+}
+
+impl Default for SlugStyle {
+    fn default() -> Self {
+        Self {
+            separator: "-".to_string(),
+            case: SlugCase::default(),
+            stop_words: Vec::new(),
+        }
+    }
+}
+
+/// A problem found in a LaTeX text
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Finding {
+    /// Offset of the section command
+    pub offset: usize,
+    pub kind: FindingKind,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FindingKind {
+    /// The section command could not be parsed
+    UnprocessableSection,
+    /// The section has no label, `slug` is the expected one
+    MissingLabel { slug: String },
+    /// The label of the section is not the expected `slug`
+    WrongLabel { label: String, slug: String },
+    /// The label of the section does not match the `pattern` of its section type
+    MalformedLabel {
+        label: String,
+        pattern: String,
+        slug: String,
+    },
+    /// A `skip-label` comment suppresses a wrong label
+    Suppressed { label: String, slug: String },
+    /// A `skip-label` comment which does not suppress anything
+    StaleSuppression,
+}
+
+impl FindingKind {
+    /// Message describing the finding to the user
+    pub fn message(&self) -> String {
+        match self {
+            FindingKind::UnprocessableSection => "Unprocessable Section".to_string(),
+            FindingKind::MissingLabel { slug } => {
+                format!("Missing Label, use \\label{{{}}}", slug)
+            }
+            FindingKind::WrongLabel { label, slug } => {
+                format!("Wrong Label '{}', use \\label{{{}}}", label, slug)
+            }
+            FindingKind::MalformedLabel { label, pattern, .. } => {
+                format!("Malformed Label '{}', it must match '{}'", label, pattern)
+            }
+            FindingKind::Suppressed { label, .. } => {
+                format!("Wrong Label '{}' is suppressed by skip-label", label)
+            }
+            FindingKind::StaleSuppression => {
+                "Stale skip-label comment, it does not suppress anything".to_string()
+            }
+        }
+    }
+
+    /// The finding makes the hook fail, unless it is suppressed
+    pub fn is_error(&self) -> bool {
+        !matches!(
+            self,
+            FindingKind::UnprocessableSection | FindingKind::Suppressed { .. }
+        )
+    }
+
+    /// Names of all rules returned by [`FindingKind::rule`]
+    pub const RULES: [&'static str; 3] = ["missing-label", "wrong-label", "unprocessable-section"];
+
+    /// Name of the rule, which `% latex-hooks: off <rule>` disables
+    pub fn rule(&self) -> &'static str {
+        match self {
+            FindingKind::UnprocessableSection => "unprocessable-section",
+            FindingKind::MissingLabel { .. } => "missing-label",
+            FindingKind::WrongLabel { .. }
+            | FindingKind::MalformedLabel { .. }
+            | FindingKind::Suppressed { .. }
+            | FindingKind::StaleSuppression => "wrong-label",
+        }
+    }
+}
+
+/// A wrong label which [`fix`] replaces by the slug
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rename {
+    /// Offset of the content of the `\label` command
+    pub offset: usize,
+    pub old: String,
+    pub new: String,
+}
+
+/// A reason why a wrong label must not be renamed
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Conflict {
+    /// The label which would be renamed
+    pub label: String,
+    pub kind: ConflictKind,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConflictKind {
+    /// The label is referenced in a section title of `texts[text]` at `offset`, where the
+    /// reference cannot be updated without changing the slug
+    UnrewritableReference { text: usize, offset: usize },
+    /// The label is defined more than once, so the references to it are ambiguous
+    DuplicateLabel,
+}
+
+/// Replacement of the text between `start` and `end` by `content`
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Edit {
+    pub start: usize,
+    pub end: usize,
+    pub content: String,
+}
+
+/// Region between a `% latex-hooks: off` and the next `% latex-hooks: on` directive
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisabledRegion {
+    pub start: usize,
+    pub end: usize,
+    /// The rule which is disabled, `None` disables all rules
+    pub rule: Option<String>,
+}
+
+/// Return the regions of `text` in which the directives disable checking
+///
+/// `% latex-hooks: off` disables all rules until `% latex-hooks: on`. A comma separated
+/// list of rule names, e.g., `% latex-hooks: off missing-label`, limits the directive to
+/// these rules. The name of the hook stands for all rules, rules of other hooks have no
+/// effect. Regions without an `on` directive extend to the end of the text.
+pub fn disabled_regions(text: &str) -> Vec<DisabledRegion> {
+    let mut regions = Vec::new();
+    let mut all_off: Option<usize> = None;
+    let mut rules_off: Vec<(String, usize)> = Vec::new();
+    for capture in RE_DIRECTIVE.captures_iter(&mask_verbatim(text)) {
+        let offset = capture.name("state").unwrap().start();
+        let is_off = &capture["state"] == "off";
+        let rules: Vec<&str> = capture
+            .name("rules")
+            .map_or("", |rules| rules.as_str())
+            .split(',')
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty())
+            .collect();
+        if rules.is_empty() || rules.iter().any(|rule| HOOK_NAMES.contains(rule)) {
+            if is_off {
+                all_off.get_or_insert(offset);
+            } else {
+                if let Some(start) = all_off.take() {
+                    regions.push(DisabledRegion {
+                        start,
+                        end: offset,
+                        rule: None,
+                    });
+                }
+                for (rule, start) in rules_off.drain(..) {
+                    regions.push(DisabledRegion {
+                        start,
+                        end: offset,
+                        rule: Some(rule),
+                    });
+                }
+            }
+            continue;
+        }
+        for rule in rules {
+            let open = rules_off.iter().position(|(name, _)| name == rule);
+            match (is_off, open) {
+                (true, None) => rules_off.push((rule.to_string(), offset)),
+                (false, Some(idx)) => {
+                    let (rule, start) = rules_off.remove(idx);
+                    regions.push(DisabledRegion {
+                        start,
+                        end: offset,
+                        rule: Some(rule),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+    if let Some(start) = all_off {
+        regions.push(DisabledRegion {
+            start,
+            end: text.len(),
+            rule: None,
+        });
+    }
+    for (rule, start) in rules_off {
+        regions.push(DisabledRegion {
+            start,
+            end: text.len(),
+            rule: Some(rule),
+        });
+    }
+    regions.sort_by_key(|region| region.start);
+    regions
+}
+
+fn is_disabled(regions: &[DisabledRegion], offset: usize, rule: &str) -> bool {
+    regions.iter().any(|region| {
+        region.start <= offset
+            && offset < region.end
+            && region.rule.as_deref().is_none_or(|name| name == rule)
+    })
+}
+
+/// Return the built-in label prefix of the section type
+pub fn default_prefix(section_type: &str) -> &'static str {
+    match section_type {
+        "section" => "sec",
+        "subsection" => "ssec",
+        "subsubsection" => "sssec",
+        "paragraph" => "par",
+        "subparagraph" => "spar",
+        _ => "unknwn",
+    }
+}
+
+pub fn slugify_label(section_type: &str, content: String) -> String {
+    slugify_with_prefix(default_prefix(section_type), content)
+}
+
+/// Slugify the section title and prepend the label prefix
+pub fn slugify_with_prefix(prefix: &str, content: String) -> String {
+    slugify_with_style(prefix, content, &SlugStyle::default())
+}
+
+/// Slugify the section title in the given style and prepend the label prefix
+pub fn slugify_with_style(prefix: &str, content: String, style: &SlugStyle) -> String {
+    // Remove embedded LaTeX commands in the content part.
+    // Iterate until we reach a fixpoint
+    let mut new_content = content;
+    let mut content = String::new();
+    while content != new_content {
+        content = new_content;
+        new_content = RE_LATEX_COMMAND
+            .replace_all(&content, |capture: &Captures| -> String {
+                capture.name("first_arg").unwrap().as_str().to_string()
+            })
+            .to_string();
+    }
+    content = new_content;
+
+    let slug = slugify(content);
+    let mut words: Vec<&str> = slug.split('-').filter(|word| !word.is_empty()).collect();
+    if words
+        .iter()
+        .any(|word| !style.stop_words.iter().any(|stop| stop == word))
+    {
+        words.retain(|word| !style.stop_words.iter().any(|stop| stop == word));
+    }
+    let slug = match style.case {
+        SlugCase::Lower => words.join(&style.separator),
+        SlugCase::Camel | SlugCase::Pascal => words
+            .iter()
+            .enumerate()
+            .map(|(idx, word)| {
+                if idx == 0 && style.case == SlugCase::Camel {
+                    word.to_string()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect(),
+    };
+    format!("{}:{}", prefix, slug)
+}
+
+/// Turn the first character of `word` into upper case
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn has_skip_label(comment: Option<&str>) -> bool {
+    comment
+        .map(|cmt| cmt.contains("skip-label"))
+        .unwrap_or(false)
+}
+
+/// Replace the content of verbatim-like environments and inline verbatim by spaces
+///
+/// The returned string has the same length as `text` and keeps all newlines, such that
+/// offsets and line numbers stay valid. Environments starting in a `%` comment are
+/// ignored, but the comments themselves are kept, as they carry the `skip-label` markers.
+pub fn mask_verbatim(text: &str) -> String {
+    let mut masked = text.as_bytes().to_vec();
+    let mut skip_until = 0;
+    let mut escaped = false;
+    for (pos, c) in text.char_indices() {
+        if pos < skip_until {
+            continue;
+        }
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => match verbatim_content(text, pos) {
+                Some((body_start, body_end, end)) => {
+                    for byte in &mut masked[body_start..body_end] {
+                        if *byte != b'\n' {
+                            *byte = b' ';
+                        }
+                    }
+                    skip_until = end;
+                }
+                None => escaped = true,
+            },
+            '%' => skip_until = text[pos..].find('\n').map_or(text.len(), |end| pos + end),
+            _ => {}
+        }
+    }
+    String::from_utf8(masked).expect("Only whole characters are replaced by spaces.")
+}
+
+/// Find the verbatim content of the environment or inline command starting at `pos`
+///
+/// Returns the start and end of the content and the position to continue behind it. The
+/// content of `\verb` ends at the next occurrence of its delimiter, the one of `\lstinline`
+/// and `\mintinline` may also be enclosed in braces. Inline content must end on its line.
+fn verbatim_content(text: &str, pos: usize) -> Option<(usize, usize, usize)> {
+    let rest = &text[pos..];
+    // Checking the prefix first avoids running the regexes for every command
+    if rest.starts_with("\\begin") {
+        let capture = RE_VERBATIM_BEGIN.captures(rest)?;
+        let body_start = pos + capture[0].len();
+        let end_tag = format!("\\end{{{}}}", &capture["name"]);
+        let body_end = text[body_start..]
+            .find(&end_tag)
+            .map_or(text.len(), |end| body_start + end);
+        return Some((body_start, body_end, body_end));
+    }
+    if !(rest.starts_with("\\verb")
+        || rest.starts_with("\\lstinline")
+        || rest.starts_with("\\mintinline"))
+    {
+        return None;
+    }
+    let command = RE_INLINE_VERBATIM.find(rest)?;
+    let delimiter = rest[command.end()..].chars().next()?;
+    if delimiter.is_alphabetic() || delimiter.is_whitespace() {
+        return None;
+    }
+    let closing = if delimiter == '{' && !rest.starts_with("\\verb") {
+        '}'
+    } else {
+        delimiter
+    };
+    let body_start = pos + command.end() + delimiter.len_utf8();
+    let body_end = body_start + text[body_start..].find(closing)?;
+    if text[body_start..body_end].contains('\n') {
+        return None;
+    }
+    Some((body_start, body_end, body_end + closing.len_utf8()))
+}
+
+/// Replace all `%` comments by spaces, keeping the newlines
+///
+/// Like [`mask_verbatim`], the returned string has the same length as `text`.
+pub fn mask_comments(text: &str) -> String {
+    let mut masked = text.as_bytes().to_vec();
+    let mut escaped = false;
+    let mut in_comment = false;
+    for (pos, byte) in text.bytes().enumerate() {
+        match byte {
+            b'\n' => {
+                in_comment = false;
+                escaped = false;
+                continue;
+            }
+            b'%' if !escaped => in_comment = true,
+            b'\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+        if in_comment {
+            masked[pos] = b' ';
+        }
+    }
+    String::from_utf8(masked).expect("Only whole characters are replaced by spaces.")
+}
+
+/// Return the offsets of all parsable sections in `text` and their expected slugs
+///
+/// The first section with a slug keeps it, later sections with the same slug and sections
+/// colliding with `config.known_slugs` are disambiguated as configured.
+pub fn section_slugs(text: &str, config: &Config) -> Vec<(usize, String)> {
+    let masked = mask_verbatim(text);
+    let mut sections = Vec::new();
+    for capture in config.sections_regex().captures_iter(&masked) {
+        let capture: Capture = capture.into();
+        if let (Some(section_type), Some(section_content), None) = (
+            capture.section_type,
+            capture.section_content,
+            capture.unparsable_section,
+        ) {
+            let title = match capture.short_title {
+                Some(short_title) if config.use_short_title => short_title,
+                _ => section_content,
+            };
+            let slug = config.slug(section_type, title.to_string());
+            sections.push((capture.offset, section_type, title, slug));
+        }
+    }
+
+    // Slugs which a disambiguated slug must not be equal to
+    let mut taken: HashSet<String> = config.known_slugs.iter().cloned().collect();
+    taken.extend(sections.iter().map(|(_, _, _, slug)| slug.clone()));
+    let mut used: HashSet<String> = config.known_slugs.iter().cloned().collect();
+    // Titles of the enclosing sections with their level
+    let mut parents: Vec<(usize, &str)> = Vec::new();
+    let mut slugs = Vec::new();
+    for (offset, section_type, title, slug) in sections {
+        let level = section_level(section_type);
+        parents.retain(|&(parent_level, _)| parent_level < level);
+        let parent = parents.last().map(|&(_, parent)| parent);
+        parents.push((level, title));
+        if used.insert(slug.clone()) {
+            slugs.push((offset, slug));
+            continue;
+        }
+
+        let base = match (config.disambiguation, parent) {
+            (Disambiguation::Parent, Some(parent)) => {
+                config.slug(section_type, format!("{} {}", parent, title))
+            }
+            _ => slug,
+        };
+        let mut slug = base.clone();
+        let mut number = 2;
+        while taken.contains(&slug) || used.contains(&slug) {
+            let separator = if config.slug_style.case == SlugCase::Lower && !base.ends_with(':') {
+                config.slug_style.separator.as_str()
+            } else {
+                ""
+            };
+            slug = format!("{}{}{}", base, separator, number);
+            number += 1;
+        }
+        used.insert(slug.clone());
+        slugs.push((offset, slug));
+    }
+    slugs
+}
+
+/// Check the labels of all sections in `text`
+pub fn run_rules(text: &str, config: &Config) -> Vec<Finding> {
+    let regions = disabled_regions(text);
+    let slugs: HashMap<usize, String> = section_slugs(text, config).into_iter().collect();
+    let text = &mask_verbatim(text);
+    let mut findings = Vec::new();
+    for capture in config.sections_regex().captures_iter(text) {
+        let capture: Capture = capture.into();
+        let offset = capture.offset;
+        let kind_disabled = |kind: &FindingKind| {
+            config.disabled_rules.iter().any(|rule| rule == kind.rule())
+                || is_disabled(&regions, offset, kind.rule())
+        };
+
+        // A section command without any content on the line is not parsable either
+        let section_type = match (capture.section_type, capture.section_content) {
+            (Some(section_type), Some(_)) if capture.unparsable_section.is_none() => section_type,
+            _ => {
+                if !kind_disabled(&FindingKind::UnprocessableSection) {
+                    findings.push(Finding {
+                        offset,
+                        kind: FindingKind::UnprocessableSection,
+                    });
+                }
+                continue;
+            }
+        };
+        let slug = slugs[&offset].clone();
+        let skip_label = has_skip_label(capture.comment);
+
+        let kind = match capture.label {
+            None => Some(FindingKind::MissingLabel { slug }),
+            Some(_) if config.ignore_label_content => None,
+            Some(label) => {
+                let pattern = config.label_pattern(section_type);
+                let is_valid = match pattern {
+                    Some(pattern) => pattern.is_match(label),
+                    None => label == slug,
+                };
+                let label = label.to_string();
+                match pattern {
+                    _ if is_valid => None,
+                    _ if skip_label => Some(FindingKind::Suppressed { label, slug }),
+                    Some(pattern) => Some(FindingKind::MalformedLabel {
+                        label,
+                        pattern: pattern.as_str().to_string(),
+                        slug,
+                    }),
+                    None => Some(FindingKind::WrongLabel { label, slug }),
+                }
+            }
+        };
+        // Within a disabled region the `skip-label` comment is not needed either
+        if kind_disabled(kind.as_ref().unwrap_or(&FindingKind::StaleSuppression)) {
+            continue;
+        }
+        // A suppression is stale if the label would not be reported without it
+        if skip_label && !matches!(kind, Some(FindingKind::Suppressed { .. })) {
+            findings.push(Finding {
+                offset,
+                kind: FindingKind::StaleSuppression,
+            });
+        }
+        if let Some(kind) = kind {
+            findings.push(Finding { offset, kind });
+        }
+    }
+    findings
+}
+
+/// Return the wrong and malformed labels in `findings` and their replacements
+///
+/// Labels containing braces or commands are skipped, as they cannot be rewritten safely.
+/// Sections referencing labels in their title are skipped too, as rewriting the
+/// references would change the slug. Malformed labels are only replaced if the slug
+/// matches the label pattern.
+pub fn renames(text: &str, findings: &[Finding], config: &Config) -> Vec<Rename> {
+    let sections = config.sections_regex();
+    let masked = mask_verbatim(text);
+    let mut renames = Vec::new();
+    for finding in findings {
+        let (label, slug) = match &finding.kind {
+            FindingKind::WrongLabel { label, slug } => (label, slug),
+            FindingKind::MalformedLabel {
+                label,
+                pattern,
+                slug,
+            } => {
+                let slug_matches = config
+                    .label_patterns
+                    .iter()
+                    .any(|(_, regex)| regex.as_str() == pattern && regex.is_match(slug));
+                if !slug_matches {
+                    continue;
+                }
+                (label, slug)
+            }
+            _ => continue,
+        };
+        let capture = match masked
+            .get(finding.offset..)
+            .and_then(|rest| sections.captures(rest))
+        {
+            Some(capture) => capture,
+            None => continue,
+        };
+        let title_has_reference = ["short_title", "section_content"].iter().any(|name| {
+            capture
+                .name(name)
+                .is_some_and(|content| RE_REFERENCE.is_match(content.as_str()))
+        });
+        if label.contains(['{', '}', '\\']) || title_has_reference {
+            continue;
+        }
+        if let Some(label) = capture
+            .name("label")
+            .or_else(|| capture.name("run_in_label"))
+        {
+            renames.push(Rename {
+                offset: finding.offset + label.start(),
+                old: label.as_str().to_string(),
+                new: slug.clone(),
+            });
+        }
+    }
+    renames
+}
+
+/// Return the spans of all section titles in `text`
+///
+/// Edits within a title would change the slug of the section, so the fixes skip them.
+fn title_spans(text: &str, config: &Config) -> Vec<(usize, usize)> {
+    config
+        .sections_regex()
+        .captures_iter(&mask_verbatim(text))
+        .flat_map(|capture| {
+            ["short_title", "section_content"]
+                .iter()
+                .filter_map(|name| capture.name(name))
+                .map(|title| (title.start(), title.end()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn in_title(titles: &[(usize, usize)], edit: &Edit) -> bool {
+    titles
+        .iter()
+        .any(|&(start, end)| edit.start < end && start < edit.end)
+}
+
+/// Replace the `\label` commands of the renamed labels defined in `text`
+pub fn label_edits(text: &str, renames: &[Rename], config: &Config) -> Vec<Edit> {
+    let titles = title_spans(text, config);
+    let mut edits = Vec::new();
+    for rename in renames {
+        let end = rename.offset + rename.old.len();
+        if text.get(rename.offset..end) != Some(rename.old.as_str()) {
+            continue;
+        }
+        let edit = Edit {
+            start: rename.offset,
+            end,
+            content: rename.new.clone(),
+        };
+        if !in_title(&titles, &edit) {
+            edits.push(edit);
+        }
+    }
+    edits
+}
+
+/// Update all references in `text` which point to renamed labels
+pub fn reference_edits(text: &str, renames: &[Rename], config: &Config) -> Vec<Edit> {
+    let titles = title_spans(text, config);
+    let mut edits = Vec::new();
+    for capture in RE_REFERENCE.captures_iter(&mask_verbatim(text)) {
+        let labels = capture
+            .name("labels")
+            .expect("The group labels always exists.");
+        let new_labels = rewrite_references(labels.as_str(), renames);
+        let edit = Edit {
+            start: labels.start(),
+            end: labels.end(),
+            content: new_labels,
+        };
+        if edit.content != labels.as_str() && !in_title(&titles, &edit) {
+            edits.push(edit);
+        }
+    }
+    edits
+}
+
+/// Insert the expected `\label` on the line after each section with a missing label
+///
+/// The label gets the indentation of the section command. Run-in paragraphs followed by
+/// text get the label directly after the title. Other sections followed by more text
+/// than a comment on the same line are skipped, as the label would not directly follow
+/// the section command.
+pub fn label_insertions(text: &str, findings: &[Finding], config: &Config) -> Vec<Edit> {
+    let sections = config.sections_regex();
+    let masked = mask_verbatim(text);
+    let mut edits = Vec::new();
+    for finding in findings {
+        let slug = match &finding.kind {
+            FindingKind::MissingLabel { slug } => slug,
+            _ => continue,
+        };
+        let capture = match masked
+            .get(finding.offset..)
+            .and_then(|rest| sections.captures(rest))
+        {
+            Some(capture) => capture,
+            None => continue,
+        };
+        let (section_type, content) = match (
+            capture.name("section_type"),
+            capture.name("section_content"),
+        ) {
+            (Some(section_type), Some(content)) => (section_type.as_str(), content),
+            _ => continue,
+        };
+        // Skip the closing brace of the title
+        let title_end = finding.offset + content.end() + 1;
+        let rest = masked.get(title_end..).unwrap_or("");
+        let line_end = title_end + rest.find('\n').unwrap_or(rest.len());
+        let trailing = masked[title_end..line_end].trim_start();
+        if !trailing.is_empty() && !trailing.starts_with('%') {
+            if PARAGRAPH_COMMANDS.contains(&section_type) {
+                edits.push(Edit {
+                    start: title_end,
+                    end: title_end,
+                    content: format!("\\label{{{}}}", slug),
+                });
+            }
+            continue;
+        }
+        let indent: String = text[finding.offset..]
+            .chars()
+            .take_while(|c| c.is_whitespace() && *c != '\n')
+            .collect();
+        edits.push(Edit {
+            start: line_end,
+            end: line_end,
+            content: format!("\n{}\\label{{{}}}", indent, slug),
+        });
+    }
+    edits
+}
+
+/// Find the renames which would leave references dangling
+///
+/// `texts` are all files which are fixed together, as their references are updated
+/// together. Renames of labels with a [`Conflict`] must be skipped.
+pub fn rename_conflicts(texts: &[&str], renames: &[Rename], config: &Config) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    let is_renamed = |label: &str| renames.iter().any(|rename| rename.old == label);
+    let masked: Vec<String> = texts.iter().map(|text| mask_verbatim(text)).collect();
+    let mut definitions: Vec<&str> = Vec::new();
+    for (idx, text) in masked.iter().enumerate() {
+        definitions.extend(
+            RE_LABEL
+                .captures_iter(text)
+                .filter_map(|capture| capture.name("label"))
+                .map(|label| label.as_str()),
+        );
+        for &(start, end) in &title_spans(text, config) {
+            for capture in RE_REFERENCE.captures_iter(&text[start..end]) {
+                let labels = capture
+                    .name("labels")
+                    .expect("The group labels always exists.");
+                for label in labels.as_str().split(',').map(str::trim) {
+                    if is_renamed(label) {
+                        conflicts.push(Conflict {
+                            label: label.to_string(),
+                            kind: ConflictKind::UnrewritableReference {
+                                text: idx,
+                                offset: start + labels.start(),
+                            },
+                        });
+                    }
+                }
+            }
+        }
+    }
+    for rename in renames {
+        let count = definitions
+            .iter()
+            .filter(|&&label| label == rename.old)
+            .count();
+        let already_reported = conflicts.iter().any(|conflict| {
+            conflict.label == rename.old && conflict.kind == ConflictKind::DuplicateLabel
+        });
+        if count > 1 && !already_reported {
+            conflicts.push(Conflict {
+                label: rename.old.clone(),
+                kind: ConflictKind::DuplicateLabel,
+            });
+        }
+    }
+    conflicts
+}
+
+/// Apply `edits` to `text`, an edit overlapping an earlier one is skipped
+pub fn apply_edits(text: &str, mut edits: Vec<Edit>) -> String {
+    edits.sort();
+    let mut end = 0;
+    edits.retain(|edit| {
+        let keep = edit.start >= end;
+        if keep {
+            end = edit.end;
+        }
+        keep
+    });
+    let mut new_text = text.to_string();
+    for edit in edits.into_iter().rev() {
+        new_text.replace_range(edit.start..edit.end, &edit.content);
+    }
+    new_text
+}
+
+/// Insert missing labels, rename all wrong labels in `text`, and update the references
+pub fn fix(text: &str, config: &Config) -> String {
+    apply_edits(text, fix_edits(text, config))
+}
+
+/// Return the edits with which [`fix`] changes `text`
+pub fn fix_edits(text: &str, config: &Config) -> Vec<Edit> {
+    let findings = run_rules(text, config);
+    let mut renames = renames(text, &findings, config);
+    let conflicts = rename_conflicts(&[text], &renames, config);
+    renames.retain(|rename| {
+        conflicts
+            .iter()
+            .all(|conflict| conflict.label != rename.old)
+    });
+    let mut edits = label_insertions(text, &findings, config);
+    edits.extend(label_edits(text, &renames, config));
+    edits.extend(reference_edits(text, &renames, config));
+    edits
+}
+
+/// Replace renamed labels in the comma separated argument of a reference command
+fn rewrite_references(labels: &str, renames: &[Rename]) -> String {
+    labels
+        .split(',')
+        .map(|label| {
+            let trimmed = label.trim();
+            match renames.iter().find(|rename| rename.old == trimmed) {
+                Some(rename) => label.replace(trimmed, &rename.new),
+                None => label.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub fn offset_to_line_number(text: &str, offset: usize) -> u32 {
+    if offset > text.len() {
+        panic!("ERROR");
+    }
+
+    let mut line_number = 1;
+    for (idx, c) in text.char_indices() {
+        if idx >= offset {
+            return line_number;
+        }
+
+        if c == '\n' {
+            line_number += 1;
+        }
+    }
+
+    // The offset is the end of the text
+    line_number
+}
+
+#[cfg(test)]
+mod test_regex {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Parse a lone section
+    #[test]
+    fn only_section() {
+        let text = r##"\section{Hello World}"##;
+        let captures: Capture = RE_SECTIONS
+            .captures(text)
+            .expect("Regex needs to match")
+            .into();
+        let expected = Capture {
+            offset: 0,
+            section_type: Some("section"),
+            short_title: None,
+            section_content: Some("Hello World"),
+            comment: None,
+            label: None,
+            unparsable_section: None,
+        };
+        assert_eq!(captures, expected);
+    }
+
+    /// Parse a section with comment
+    #[test]
+    fn only_section_with_comment() {
+        let text = r##"\section{Hello World} % Comment"##;
+        let captures: Capture = RE_SECTIONS
+            .captures(text)
+            .expect("Regex needs to match")
+            .into();
+        let expected = Capture {
+            offset: 0,
+            section_type: Some("section"),
+            short_title: None,
+            section_content: Some("Hello World"),
+            comment: Some("% Comment"),
+            label: None,
+            unparsable_section: None,
+        };
+        assert_eq!(captures, expected);
+    }
+
+    #[test]
+    fn section_and_label() {
+        let text = r##"\section{Hello World}
+\label{Label-ABC}"##;
+        let captures: Capture = RE_SECTIONS
+            .captures(text)
+            .expect("Regex needs to match")
+            .into();
+        let expected = Capture {
+            offset: 0,
+            section_type: Some("section"),
+            short_title: None,
+            section_content: Some("Hello World"),
+            comment: None,
+            label: Some("Label-ABC"),
+            unparsable_section: None,
+        };
+        assert_eq!(captures, expected);
+    }
+
+    /// Parse a section and comment and label
+    #[test]
+    fn section_with_comment_and_label() {
+        let text = r##"\section{Hello World} % Another Comment
+\label{Here}"##;
+        let captures: Capture = RE_SECTIONS
+            .captures(text)
+            .expect("Regex needs to match")
+            .into();
+        let expected = Capture {
+            offset: 0,
+            section_type: Some("section"),
+            short_title: None,
+            section_content: Some("Hello World"),
+            comment: Some("% Another Comment"),
+            label: Some("Here"),
+            unparsable_section: None,
+        };
+        assert_eq!(captures, expected);
+    }
+
+    /// Put section and label on the same line
+    #[test]
+    fn section_and_label_same_line() {
+        let text = r##"\section{Hello World} \label{Label-123}"##;
+        let captures: Capture = RE_SECTIONS
+            .captures(text)
+            .expect("Regex needs to match")
+            .into();
+        let expected = Capture {
+            offset: 0,
+            section_type: Some("section"),
+            short_title: None,
+            section_content: Some("Hello World"),
+            comment: None,
+            label: Some("Label-123"),
+            unparsable_section: None,
+        };
+        assert_eq!(captures, expected);
+    }
+
+    /// Check for `\section*`
+    #[test]
+    fn section_star_and_label() {
+        let text = r##"
+
+\section*{Hello World}
+\label{Label-ABC}"##;
+        let captures: Capture = RE_SECTIONS
+            .captures(text)
+            .expect("Regex needs to match")
+            .into();
+        let expected = Capture {
+            offset: 2,
+            section_type: Some("section"),
+            short_title: None,
+            section_content: Some("Hello World"),
+            comment: None,
+            label: Some("Label-ABC"),
+            unparsable_section: None,
+        };
+        assert_eq!(captures, expected);
+    }
+
+    /// Check parsing a single latex command in section
+    #[test]
+    fn section_with_nested_command() {
+        let text = r##"\section{\textbf{bold}}"##;
+        let captures: Capture = RE_SECTIONS
+            .captures(text)
+            .expect("Regex needs to match")
+            .into();
+        let expected = Capture {
+            offset: 0,
+            section_type: Some("section"),
+            short_title: None,
+            section_content: Some("\\textbf{bold}"),
+            comment: None,
+            label: None,
+            unparsable_section: None,
+        };
+        assert_eq!(captures, expected);
+    }
+
+    /// Check parsing multiple nested latex commands in section
+    #[test]
+    fn section_with_double_nested_command_and_label() {
+        let text = r##"\subsubsection{Formalization of \texorpdfstring{\acs{knn}}{k-NN}}
+\label{sssec:formalization-of-knn}"##;
+        let captures: Capture = RE_SECTIONS
+            .captures(text)
+            .expect("Regex needs to match")
+            .into();
+        let expected = Capture {
+            offset: 0,
+            section_type: Some("subsubsection"),
+            short_title: None,
+            section_content: Some(r"Formalization of \texorpdfstring{\acs{knn}}{k-NN}"),
+            comment: None,
+            label: Some("sssec:formalization-of-knn"),
+            unparsable_section: None,
+        };
+        assert_eq!(captures, expected);
+    }
+
+    /// Check using a subsection
+    #[test]
+    fn only_subsection() {
+        let text = r##"\subsection{SubSec}"##;
+        let captures: Capture = RE_SECTIONS.captures(text).unwrap().into();
+        let expected = Capture {
+            offset: 0,
+            section_type: Some("subsection"),
+            short_title: None,
+            section_content: Some("SubSec"),
+            comment: None,
+            label: None,
+            unparsable_section: None,
+        };
+        assert_eq!(captures, expected);
+    }
+
+    /// Parse the optional short title
+    #[test]
+    fn section_with_short_title() {
+        let text = r##"\section[Short {Title}]{A Much Longer Title}
+\label{sec:short-title}"##;
+        let captures: Capture = RE_SECTIONS.captures(text).unwrap().into();
+        let expected = Capture {
+            offset: 0,
+            section_type: Some("section"),
+            short_title: Some("Short {Title}"),
+            section_content: Some("A Much Longer Title"),
+            comment: None,
+            label: Some("sec:short-title"),
+            unparsable_section: None,
+        };
+        assert_eq!(captures, expected);
+    }
+
+    /// Test if we can handle things outside of our current regex
+    #[test]
+    fn unsupported_section_content() {
+        let text = r##"\subsection{A{B{C{D{EE}D}C}B}A}"##;
+        let captures: Capture = RE_SECTIONS.captures(text).unwrap().into();
+        let expected = Capture {
+            offset: 0,
+            section_type: Some("subsection"),
+            short_title: None,
+            section_content: None,
+            comment: None,
+            label: None,
+            unparsable_section: Some("{A{B{C{D{EE}D}C}B}A}"),
+        };
+        assert_eq!(captures, expected);
+    }
+}
+
+#[cfg(test)]
+mod test_mask_verbatim {
+    use super::*;
+
+    #[test]
+    fn environments() {
+        let text = "A\n\\begin{verbatim}\n\\section{ü}\n\\end{verbatim}\n\\begin{lstlisting*}[x]\ny\n\\end{lstlisting*}";
+        assert_eq!(
+            mask_verbatim(text),
+            "A\n\\begin{verbatim}\n            \n\\end{verbatim}\n\\begin{lstlisting*}   \n \n\\end{lstlisting*}"
+        );
+        // Unclosed environments extend to the end
+        assert_eq!(mask_verbatim("\\begin{comment}\nx"), "\\begin{comment}\n ");
+    }
+
+    #[test]
+    fn inline() {
+        let text =
+            "a \\verb|\\ref{x}| \\verb*+%ü+ \\lstinline[c]{a{b} \\mintinline{c}!x! % \\verb|y|";
+        assert_eq!(
+            mask_verbatim(text),
+            "a \\verb|       | \\verb*+   + \\lstinline[c]{   } \\mintinline{c}! ! % \\verb|y|"
+        );
+        // Unclosed or multi-line content and escaped commands are no verbatim
+        for text in [
+            "\\verb|x\ny|",
+            "\\verb|x",
+            "\\verbatim x",
+            "\\\\verb|x|",
+            "\\verb x x",
+        ] {
+            assert_eq!(mask_verbatim(text), text);
+        }
+    }
+
+    #[test]
+    fn comments_and_escapes() {
+        for text in [
+            "% \\begin{verbatim}\n\\section{A}",
+            "\\\\begin{verbatim} \\section{A}",
+            "\\begin{verbatimx}\n\\section{A}\n\\end{verbatimx}",
+        ] {
+            assert_eq!(mask_verbatim(text), text);
+        }
+    }
+
+    #[test]
+    fn sections_in_verbatim() {
+        let text = "\\begin{comment}\n\\section{Old}\n\\end{comment}\n\\begin{verbatim}\n\\section{Example}\n\\label{sec:x}\n\\end{verbatim}\n";
+        assert!(run_rules(text, &Config::default()).is_empty());
+        assert_eq!(fix(text, &Config::default()), text);
+    }
+}
+
+#[cfg(test)]
+mod test_disabled_regions {
+    use super::*;
+
+    fn region(start: usize, end: usize, rule: Option<&str>) -> DisabledRegion {
+        DisabledRegion {
+            start,
+            end,
+            rule: rule.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn directives() {
+        let text = "% latex-hooks: off\nA\n% latex-hooks: on\nB\n%latex-hooks:off missing-label, wrong-label\nC";
+        assert_eq!(
+            disabled_regions(text),
+            vec![
+                region(15, 36, None),
+                region(54, text.len(), Some("missing-label")),
+                region(54, text.len(), Some("wrong-label")),
+            ]
+        );
+        // Escaped percent signs and verbatim content are ignored
+        for text in [
+            "\\% latex-hooks: off\n",
+            "\\begin{verbatim}\n% latex-hooks: off\n\\end{verbatim}",
+        ] {
+            assert_eq!(disabled_regions(text), vec![]);
+        }
+    }
+
+    #[test]
+    fn sections_in_regions() {
+        let text = "% latex-hooks: off\n\\section{A}\n% latex-hooks: on\n\\section{B}\n% latex-hooks: off wrong-label\n\\section{C} % skip-label\n\\label{sec:x}\n\\section{D}\n";
+        let findings = run_rules(text, &Config::default());
+        assert_eq!(
+            findings.iter().map(|f| f.kind.rule()).collect::<Vec<_>>(),
+            vec!["missing-label", "missing-label"]
+        );
+        // Rules of other hooks have no effect
+        let other = "% latex-hooks: off spellcheck\n\\section{A}\n";
+        assert_eq!(run_rules(other, &Config::default()).len(), 1);
+        assert_eq!(
+            fix(text, &Config::default()),
+            text.replace("{B}\n", "{B}\n\\label{sec:b}\n")
+                .replace("{D}\n", "{D}\n\\label{sec:d}\n")
+        );
+    }
+
+    #[test]
+    fn disabled_rules() {
+        let text = "\\section{A}\n\\section{B}\n\\label{sec:x}\n";
+        let config = Config {
+            disabled_rules: vec!["missing-label".to_string()],
+            ..Config::default()
+        };
+        assert_eq!(
+            run_rules(text, &config)
+                .iter()
+                .map(|f| f.kind.rule())
+                .collect::<Vec<_>>(),
+            vec!["wrong-label"]
+        );
+        assert_eq!(
+            fix(text, &config),
+            "\\section{A}\n\\section{B}\n\\label{sec:b}\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_has_skip_label {
+    use super::*;
+
+    #[test]
+    fn comments() {
+        assert!(has_skip_label(Some("% skip-label")));
+        assert!(has_skip_label(Some("% keep old label, skip-label")));
+        assert!(!has_skip_label(Some("% Comment")));
+        assert!(!has_skip_label(None));
+    }
+}
+
+#[cfg(test)]
+mod test_rewrite_references {
+    use super::*;
+
+    fn rename(old: &str, new: &str) -> Rename {
+        Rename {
+            offset: 0,
+            old: old.to_string(),
+            new: new.to_string(),
+        }
+    }
+
+    #[test]
+    fn single_and_multiple_labels() {
+        let renames = [rename("sec:old", "sec:new")];
+        assert_eq!(rewrite_references("sec:old", &renames), "sec:new");
+        assert_eq!(
+            rewrite_references("sec:a, sec:old,sec:b", &renames),
+            "sec:a, sec:new,sec:b"
+        );
+        assert_eq!(rewrite_references("sec:older", &renames), "sec:older");
+    }
+}
+
+#[cfg(test)]
+mod test_fix {
+    use super::*;
+
+    #[test]
+    fn insert_missing_labels() {
+        let text = "\\section{Intro}\n  \\subsection{A \\emph{B}} % note\nText\n\\section{End}";
+        assert_eq!(
+            fix(text, &Config::default()),
+            "\\section{Intro}\n\\label{sec:intro}\n  \\subsection{A \\emph{B}} % note\n  \\label{ssec:a-b}\nText\n\\section{End}\n\\label{sec:end}"
+        );
+    }
+
+    #[test]
+    fn skip_text_after_section() {
+        let text = "\\section{Intro} text\n";
+        assert_eq!(fix(text, &Config::default()), text);
+    }
+
+    #[test]
+    fn rename_and_update_references() {
+        let text = "\\section{Intro}\n\\label{sec:old}\nSee \\cref{sec:old,sec:other}.\n";
+        assert_eq!(
+            fix(text, &Config::default()),
+            "\\section{Intro}\n\\label{sec:intro}\nSee \\cref{sec:intro,sec:other}.\n"
+        );
+    }
+
+    #[test]
+    fn keep_references_in_inline_verbatim() {
+        let text = "\\section{Intro}\n\\label{sec:old}\nSee \\ref{sec:old}, \\verb|\\ref{sec:old}|, and \\lstinline{\\cref{sec:old}}.\n";
+        assert_eq!(
+            fix(text, &Config::default()),
+            "\\section{Intro}\n\\label{sec:intro}\nSee \\ref{sec:intro}, \\verb|\\ref{sec:old}|, and \\lstinline{\\cref{sec:old}}.\n"
+        );
+    }
+
+    #[test]
+    fn custom_section_commands() {
+        let config = Config {
+            prefixes: vec![("paperSection".to_string(), "psec".to_string())],
+            section_commands: vec!["paperSection".to_string(), "paperSectionTitle".to_string()],
+            ..Config::default()
+        };
+        let text = "\\paperSection{Intro}\n\\label{sec:old}\n\\paperSectionTitle{Title}\n\\paperSectionx{X}\nSee \\ref{sec:old}.\n";
+        assert_eq!(
+            fix(text, &config),
+            "\\paperSection{Intro}\n\\label{psec:intro}\n\\paperSectionTitle{Title}\n\\label{unknwn:title}\n\\paperSectionx{X}\nSee \\ref{psec:intro}.\n"
+        );
+        // Without the configuration the commands are not sections
+        assert_eq!(fix(text, &Config::default()), text);
+    }
+
+    #[test]
+    fn paragraphs() {
+        let text = "\\paragraph{Setup}\n\\subparagraph{Details}\n";
+        assert_eq!(fix(text, &Config::default()), text);
+        let config = Config {
+            check_paragraphs: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            fix(text, &config),
+            "\\paragraph{Setup}\n\\label{par:setup}\n\\subparagraph{Details}\n\\label{spar:details}\n"
+        );
+    }
+
+    #[test]
+    fn run_in_paragraphs() {
+        let config = Config {
+            check_paragraphs: true,
+            ..Config::default()
+        };
+        let text = "\\paragraph{Setup}\\label{par:setup} The \\emph{first} step.\n\\subparagraph{Details}\\label{spar:details} % note\n";
+        assert_eq!(run_rules(text, &config), vec![]);
+        let text =
+            "\\paragraph{Setup}\\label{par:old} The \\emph{first} step.\n\\paragraph{Next} Text\n";
+        assert_eq!(
+            fix(text, &config),
+            "\\paragraph{Setup}\\label{par:setup} The \\emph{first} step.\n\\paragraph{Next}\\label{par:next} Text\n"
+        );
+        // Sections are still expected to have the label on its own
+        let text = "\\section{Setup}\\label{sec:setup} Text\n";
+        assert_eq!(
+            run_rules(text, &config),
+            vec![Finding {
+                offset: 0,
+                kind: FindingKind::MissingLabel {
+                    slug: "sec:setup".to_string()
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn short_titles() {
+        let text = "\\section[Short]{A Much Longer Title}\n";
+        assert_eq!(
+            fix(text, &Config::default()),
+            "\\section[Short]{A Much Longer Title}\n\\label{sec:a-much-longer-title}\n"
+        );
+        let config = Config {
+            use_short_title: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            fix(text, &config),
+            "\\section[Short]{A Much Longer Title}\n\\label{sec:short}\n"
+        );
+    }
+
+    #[test]
+    fn slug_collisions() {
+        let text = "\\section{Method}\n\\subsection{Evaluation}\n\\section{Results}\n\\subsection{Evaluation}\n\\subsection{Evaluation 2}\n";
+        let slugs = |config: &Config| -> Vec<String> {
+            section_slugs(text, config)
+                .into_iter()
+                .map(|(_, slug)| slug)
+                .collect()
+        };
+        assert_eq!(
+            slugs(&Config::default()),
+            [
+                "sec:method",
+                "ssec:evaluation",
+                "sec:results",
+                "ssec:evaluation-3",
+                "ssec:evaluation-2"
+            ]
+        );
+        let config = Config {
+            disambiguation: Disambiguation::Parent,
+            known_slugs: vec!["sec:method".to_string()],
+            ..Config::default()
+        };
+        assert_eq!(
+            slugs(&config),
+            [
+                "sec:method-2",
+                "ssec:evaluation",
+                "sec:results",
+                "ssec:results-evaluation",
+                "ssec:evaluation-2"
+            ]
+        );
+        let config = Config {
+            slug_style: SlugStyle {
+                case: SlugCase::Pascal,
+                ..SlugStyle::default()
+            },
+            ..Config::default()
+        };
+        assert_eq!(
+            fix("\\section{A}\n\\section{A}\n", &config),
+            "\\section{A}\n\\label{sec:A}\n\\section{A}\n\\label{sec:A2}\n"
+        );
+    }
+
+    #[test]
+    fn label_patterns() {
+        let config = Config {
+            label_patterns: vec![("section".to_string(), Regex::new("^sec:[a-z-]+$").unwrap())],
+            ..Config::default()
+        };
+        let text = "\\section{Intro}\n\\label{sec:overview}\n\\section{Details}\n\\label{Sec:Details}\n\\section{Part 2}\n\\label{Part2}\n\\subsection{Sub}\n\\label{ssec:x}\n";
+        let findings = run_rules(text, &config);
+        assert_eq!(
+            findings.iter().map(|f| f.kind.rule()).collect::<Vec<_>>(),
+            vec!["wrong-label", "wrong-label", "wrong-label"]
+        );
+        assert!(matches!(
+            findings[0].kind,
+            FindingKind::MalformedLabel { .. }
+        ));
+        // The slug `sec:part-2` does not match the pattern, so the label is kept
+        assert_eq!(
+            fix(text, &config),
+            "\\section{Intro}\n\\label{sec:overview}\n\\section{Details}\n\\label{sec:details}\n\\section{Part 2}\n\\label{Part2}\n\\subsection{Sub}\n\\label{ssec:sub}\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_rename_conflicts {
+    use super::*;
+
+    fn rename(old: &str, new: &str) -> Rename {
+        Rename {
+            offset: 0,
+            old: old.to_string(),
+            new: new.to_string(),
+        }
+    }
+
+    #[test]
+    fn reference_in_title() {
+        let text = "\\section{A}\n\\label{sec:x}\n\\section{B \\ref{sec:x}}\n";
+        assert_eq!(
+            rename_conflicts(&[text], &[rename("sec:x", "sec:a")], &Config::default()),
+            vec![Conflict {
+                label: "sec:x".to_string(),
+                kind: ConflictKind::UnrewritableReference {
+                    text: 0,
+                    offset: 42
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn duplicate_label_across_texts() {
+        let first = "\\section{A}\n\\label{sec:x}\n";
+        let second = "\\section{B}\n\\label{sec:x}\n\\section{C}\n\\label{sec:y}\n";
+        let renames = [rename("sec:x", "sec:a"), rename("sec:y", "sec:c")];
+        assert_eq!(
+            rename_conflicts(&[first, second], &renames, &Config::default()),
+            vec![Conflict {
+                label: "sec:x".to_string(),
+                kind: ConflictKind::DuplicateLabel,
+            }]
+        );
+        assert!(rename_conflicts(&[first], &renames, &Config::default()).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_slugify_label {
+    use super::*;
+
+    #[test]
+    fn simple_ascii() {
+        assert_eq!(slugify_label("section", "Word".to_string()), "sec:word");
+        assert_eq!(
+            slugify_label("section", "Hello World".to_string()),
+            "sec:hello-world"
+        );
+        assert_eq!(
+            slugify_label("subsubsection", "Many Many words here".to_string()),
+            "sssec:many-many-words-here"
+        );
+    }
+
+    #[test]
+    fn nested_commands() {
+        assert_eq!(
+            slugify_label("section", r"\texttt{Abc}".to_string()),
+            "sec:abc"
+        );
+        assert_eq!(
+            slugify_label("subsection", r"Something \emph{very} important".to_string()),
+            "ssec:something-very-important"
+        );
+    }
+
+    #[test]
+    fn commands_with_star() {
+        assert_eq!(
+            slugify_label("section", r"Unused abbreviation \ac*{Abc}".to_string()),
+            "sec:unused-abbreviation-abc"
+        );
+    }
+
+    #[test]
+    fn custom_prefixes() {
+        let config = Config {
+            prefixes: vec![("subsection".to_string(), "subsec".to_string())],
+            ..Config::default()
+        };
+        assert_eq!(config.prefix("section"), "sec");
+        assert_eq!(config.prefix("subsection"), "subsec");
+        assert_eq!(
+            slugify_with_prefix(config.prefix("subsection"), "Hello World".to_string()),
+            "subsec:hello-world"
+        );
+    }
+
+    #[test]
+    fn slug_styles() {
+        let mut config = Config::default();
+        let title = || r"The Design of \emph{Fast} Caches".to_string();
+        assert_eq!(
+            config.slug("section", title()),
+            "sec:the-design-of-fast-caches"
+        );
+
+        config.slug_style.separator = "_".to_string();
+        assert_eq!(
+            config.slug("section", title()),
+            "sec:the_design_of_fast_caches"
+        );
+
+        config.slug_style.stop_words = vec!["a".to_string(), "the".to_string(), "of".to_string()];
+        assert_eq!(config.slug("section", title()), "sec:design_fast_caches");
+        // Titles consisting only of stop words are kept
+        assert_eq!(config.slug("section", "The A".to_string()), "sec:the_a");
+
+        config.slug_style.case = SlugCase::Camel;
+        assert_eq!(config.slug("section", title()), "sec:designFastCaches");
+        config.slug_style.case = SlugCase::Pascal;
+        assert_eq!(config.slug("section", title()), "sec:DesignFastCaches");
+        assert_eq!(
+            config.slug("section", "My Heading 2".to_string()),
+            "sec:MyHeading2"
+        );
+    }
+
+    #[test]
+    fn double_nested_commands() {
+        assert_eq!(
+            slugify_label(
+                "subsubsection",
+                r"Formalization of \texorpdfstring{\acs{knn}}{k-NN}".to_string()
+            ),
+            "sssec:formalization-of-knn"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_offset_to_line_number {
+    use super::*;
+
+    #[test]
+    fn simple_ascii() {
+        let text = r#"Hello
+Nice
+World
+"#;
+        assert_eq!(offset_to_line_number(text, 0), 1);
+        assert_eq!(offset_to_line_number(text, 1), 1);
+        assert_eq!(offset_to_line_number(text, 2), 1);
+        assert_eq!(offset_to_line_number(text, 3), 1);
+        assert_eq!(offset_to_line_number(text, 4), 1);
+        assert_eq!(offset_to_line_number(text, 5), 1);
+
+        assert_eq!(offset_to_line_number(text, 6), 2);
+        assert_eq!(offset_to_line_number(text, 7), 2);
+        assert_eq!(offset_to_line_number(text, 8), 2);
+        assert_eq!(offset_to_line_number(text, 9), 2);
+        assert_eq!(offset_to_line_number(text, 10), 2);
+
+        assert_eq!(offset_to_line_number(text, 11), 3);
+        assert_eq!(offset_to_line_number(text, 12), 3);
+        assert_eq!(offset_to_line_number(text, 13), 3);
+        assert_eq!(offset_to_line_number(text, 14), 3);
+        assert_eq!(offset_to_line_number(text, 15), 3);
+        assert_eq!(offset_to_line_number(text, 16), 3);
+        assert_eq!(offset_to_line_number(text, 17), 4);
+    }
+}
@@ -0,0 +1,436 @@
+//! Traits for custom rules, which are checked and fixed like the built-in ones.
+//!
+//! A [`Rule`] finds problems in a [`Document`], whose masked text hides comments and
+//! verbatim content. A [`FixableRule`] can also return the [`Edit`]s removing them. A
+//! [`RuleSet`] runs all registered rules, honors the `% latex-hooks: off` directives with
+//! the [`Rule::name`], and turns the results into [`Diagnostic`]s for the output formats.
+//!
+//! ```
+//! use latexhooks_core::rules::{Document, Rule, RuleSet, Violation};
+//!
+//! /// Flag the `\bf` command, which is deprecated in LaTeX2e
+//! struct NoBf;
+//!
+//! impl Rule for NoBf {
+//!     fn name(&self) -> &str {
+//!         "no-bf"
+//!     }
+//!
+//!     fn check(&self, document: &Document) -> Vec<Violation> {
+//!         document
+//!             .masked
+//!             .match_indices("\\bf ")
+//!             .map(|(offset, _)| Violation::error(offset, "Use \\textbf instead of \\bf"))
+//!             .collect()
+//!     }
+//! }
+//!
+//! let rules = RuleSet::new().with_rule(NoBf);
+//! let diagnostics = rules.check("{\\bf bold}\n% {\\bf old}\n");
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].line, 1);
+//! ```
+
+use crate::{
+    apply_edits, disabled_regions, fix_edits, is_disabled, mask_comments, mask_verbatim,
+    offset_to_line_number, run_rules, Config, DisabledRegion, Edit, FindingKind,
+};
+use std::path::PathBuf;
+
+/// A LaTeX text as seen by the rules
+pub struct Document<'a> {
+    pub text: &'a str,
+    /// The text with comments and the content of verbatim-like environments replaced by
+    /// spaces, such that offsets are the same as in `text`
+    pub masked: String,
+    regions: Vec<DisabledRegion>,
+}
+
+impl<'a> Document<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Document {
+            text,
+            masked: mask_comments(&mask_verbatim(text)),
+            regions: disabled_regions(text),
+        }
+    }
+}
+
+/// A problem found by a [`Rule`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+    /// Offset in the text of the [`Document`]
+    pub offset: usize,
+    pub message: String,
+    /// The violation makes the hook fail
+    pub is_error: bool,
+}
+
+impl Violation {
+    pub fn error(offset: usize, message: impl Into<String>) -> Self {
+        Violation {
+            offset,
+            message: message.into(),
+            is_error: true,
+        }
+    }
+
+    pub fn warning(offset: usize, message: impl Into<String>) -> Self {
+        Violation {
+            offset,
+            message: message.into(),
+            is_error: false,
+        }
+    }
+}
+
+/// A message about a single line of a file
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// Name of the rule reporting the diagnostic
+    pub rule: String,
+    pub line: u32,
+    pub message: String,
+    /// The diagnostic makes the hook fail
+    pub is_error: bool,
+}
+
+/// A check of LaTeX documents
+pub trait Rule {
+    /// Name of the rule, which `% latex-hooks: off <name>` disables
+    fn name(&self) -> &str;
+
+    /// Return all problems in the document
+    fn check(&self, document: &Document) -> Vec<Violation>;
+}
+
+/// A [`Rule`] whose problems can be fixed automatically
+pub trait FixableRule: Rule {
+    /// Return the edits fixing the problems in the document
+    ///
+    /// Overlapping edits of all rules are skipped by [`apply_edits`], so applying the
+    /// fixes again must be able to pick them up.
+    fn fix(&self, document: &Document) -> Vec<Edit>;
+}
+
+enum Registered {
+    Rule(Box<dyn Rule>),
+    Fixable(Box<dyn FixableRule>),
+}
+
+impl Registered {
+    fn rule(&self) -> &dyn Rule {
+        match self {
+            Registered::Rule(rule) => rule.as_ref(),
+            Registered::Fixable(rule) => rule.as_ref(),
+        }
+    }
+}
+
+/// The rules which are checked together
+#[derive(Default)]
+pub struct RuleSet {
+    rules: Vec<Registered>,
+}
+
+impl RuleSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.rules.push(Registered::Rule(Box::new(rule)));
+        self
+    }
+
+    pub fn with_fixable_rule(mut self, rule: impl FixableRule + 'static) -> Self {
+        self.rules.push(Registered::Fixable(Box::new(rule)));
+        self
+    }
+
+    /// Check `text` with all rules, the diagnostics are sorted by line
+    pub fn check(&self, text: &str) -> Vec<Diagnostic> {
+        let document = Document::new(text);
+        let mut violations: Vec<(&str, Violation)> = Vec::new();
+        for registered in &self.rules {
+            let rule = registered.rule();
+            violations.extend(
+                rule.check(&document)
+                    .into_iter()
+                    .filter(|violation| {
+                        !is_disabled(&document.regions, violation.offset, rule.name())
+                    })
+                    .map(|violation| (rule.name(), violation)),
+            );
+        }
+        violations.sort_by_key(|(_, violation)| violation.offset);
+        violations
+            .into_iter()
+            .map(|(rule, violation)| Diagnostic {
+                rule: rule.to_string(),
+                line: offset_to_line_number(text, violation.offset),
+                message: violation.message,
+                is_error: violation.is_error,
+            })
+            .collect()
+    }
+
+    /// Names of all rules, in the order in which they were added
+    pub fn names(&self) -> Vec<&str> {
+        self.rules
+            .iter()
+            .map(|registered| registered.rule().name())
+            .collect()
+    }
+
+    /// Apply the fixes of all fixable rules to `text`
+    pub fn fix(&self, text: &str) -> String {
+        let document = Document::new(text);
+        let mut edits = Vec::new();
+        for registered in &self.rules {
+            if let Registered::Fixable(rule) = registered {
+                edits.extend(
+                    rule.fix(&document)
+                        .into_iter()
+                        .filter(|edit| !is_disabled(&document.regions, edit.start, rule.name())),
+                );
+            }
+        }
+        apply_edits(text, edits)
+    }
+}
+
+/// The checks of `ensure-labels` as a [`FixableRule`]
+///
+/// `skip-label` suppressions are not reported.
+pub struct EnsureLabels(pub Config);
+
+impl Rule for EnsureLabels {
+    fn name(&self) -> &str {
+        "ensure-labels"
+    }
+
+    fn check(&self, document: &Document) -> Vec<Violation> {
+        run_rules(document.text, &self.0)
+            .into_iter()
+            .filter(|finding| {
+                !matches!(
+                    finding.kind,
+                    FindingKind::Suppressed { .. } | FindingKind::StaleSuppression
+                )
+            })
+            .map(|finding| Violation {
+                offset: finding.offset,
+                message: finding.kind.message(),
+                is_error: finding.kind.is_error(),
+            })
+            .collect()
+    }
+}
+
+impl FixableRule for EnsureLabels {
+    fn fix(&self, document: &Document) -> Vec<Edit> {
+        fix_edits(document.text, &self.0)
+    }
+}
+
+/// Render `file:line message` lines for the diagnostics of a file
+pub fn text_report(path: &str, diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|diagnostic| format!("{}:{} {}\n", path, diagnostic.line, diagnostic.message))
+        .collect()
+}
+
+/// Escape the special characters of XML in attributes and text
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Render the diagnostics as JUnit test suites, one per rule with one test case per file
+///
+/// The suites are named `<name>.<rule>` for the `rules` and for any other rule with
+/// diagnostics. A file whose diagnostics of a rule contain an error fails the test case
+/// with `failure` as the message and these diagnostics as the failure text.
+pub fn junit_report(
+    name: &str,
+    failure: &str,
+    rules: &[&str],
+    results: &[(PathBuf, Vec<Diagnostic>)],
+) -> String {
+    let mut all_rules: Vec<&str> = rules.to_vec();
+    for (_, diagnostics) in results {
+        for diagnostic in diagnostics {
+            if !all_rules.contains(&diagnostic.rule.as_str()) {
+                all_rules.push(&diagnostic.rule);
+            }
+        }
+    }
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+    for rule in all_rules {
+        let suite = xml_escape(&format!("{}.{}", name, rule));
+        let cases: Vec<(String, Vec<Diagnostic>)> = results
+            .iter()
+            .map(|(path, diagnostics)| {
+                let diagnostics = diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.rule == rule)
+                    .cloned()
+                    .collect();
+                (path.display().to_string(), diagnostics)
+            })
+            .collect();
+        let failures = cases
+            .iter()
+            .filter(|(_, diagnostics)| diagnostics.iter().any(|diagnostic| diagnostic.is_error))
+            .count();
+        out.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            suite,
+            cases.len(),
+            failures
+        ));
+        for (path, diagnostics) in cases {
+            out.push_str(&format!(
+                "    <testcase classname=\"{}\" name=\"{}\">\n",
+                suite,
+                xml_escape(&path)
+            ));
+            if diagnostics.iter().any(|diagnostic| diagnostic.is_error) {
+                out.push_str(&format!(
+                    "      <failure message=\"{}\">",
+                    xml_escape(failure)
+                ));
+                out.push_str(&xml_escape(&text_report(&path, &diagnostics)));
+                out.push_str("</failure>\n");
+            }
+            out.push_str("    </testcase>\n");
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+#[cfg(test)]
+mod test_rules {
+    use super::*;
+
+    /// Replace `\bf` by `\bfseries`
+    struct Bfseries;
+
+    impl Rule for Bfseries {
+        fn name(&self) -> &str {
+            "bfseries"
+        }
+
+        fn check(&self, document: &Document) -> Vec<Violation> {
+            document
+                .masked
+                .match_indices("\\bf ")
+                .map(|(offset, _)| Violation::warning(offset, "Use \\bfseries"))
+                .collect()
+        }
+    }
+
+    impl FixableRule for Bfseries {
+        fn fix(&self, document: &Document) -> Vec<Edit> {
+            self.check(document)
+                .into_iter()
+                .map(|violation| Edit {
+                    start: violation.offset,
+                    end: violation.offset + 3,
+                    content: "\\bfseries".to_string(),
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn check_and_fix() {
+        let rules = RuleSet::new()
+            .with_fixable_rule(Bfseries)
+            .with_fixable_rule(EnsureLabels(Config::default()));
+        let text = "\\section{A}\n{\\bf x} % {\\bf y}\n% latex-hooks: off bfseries\n{\\bf z}\n";
+        assert_eq!(
+            rules.check(text),
+            vec![
+                Diagnostic {
+                    rule: "ensure-labels".to_string(),
+                    line: 1,
+                    message: "Missing Label, use \\label{sec:a}".to_string(),
+                    is_error: true,
+                },
+                Diagnostic {
+                    rule: "bfseries".to_string(),
+                    line: 2,
+                    message: "Use \\bfseries".to_string(),
+                    is_error: false,
+                },
+            ]
+        );
+        assert_eq!(
+            rules.fix(text),
+            "\\section{A}\n\\label{sec:a}\n{\\bfseries x} % {\\bf y}\n% latex-hooks: off bfseries\n{\\bf z}\n"
+        );
+    }
+
+    #[test]
+    fn junit() {
+        let diagnostic = |rule: &str, line, is_error| Diagnostic {
+            rule: rule.to_string(),
+            line,
+            message: "Use <this>".to_string(),
+            is_error,
+        };
+        let results = vec![
+            (
+                PathBuf::from("a&b.tex"),
+                vec![
+                    diagnostic("bfseries", 3, true),
+                    diagnostic("other", 4, false),
+                    diagnostic("bfseries", 5, false),
+                ],
+            ),
+            (PathBuf::from("c.tex"), vec![]),
+        ];
+        assert_eq!(
+            junit_report("custom", "Failed", &["ensure-labels", "bfseries"], &results),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+  <testsuite name="custom.ensure-labels" tests="2" failures="0">
+    <testcase classname="custom.ensure-labels" name="a&amp;b.tex">
+    </testcase>
+    <testcase classname="custom.ensure-labels" name="c.tex">
+    </testcase>
+  </testsuite>
+  <testsuite name="custom.bfseries" tests="2" failures="1">
+    <testcase classname="custom.bfseries" name="a&amp;b.tex">
+      <failure message="Failed">a&amp;b.tex:3 Use &lt;this&gt;
+a&amp;b.tex:5 Use &lt;this&gt;
+</failure>
+    </testcase>
+    <testcase classname="custom.bfseries" name="c.tex">
+    </testcase>
+  </testsuite>
+  <testsuite name="custom.other" tests="2" failures="0">
+    <testcase classname="custom.other" name="a&amp;b.tex">
+    </testcase>
+    <testcase classname="custom.other" name="c.tex">
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+        assert_eq!(
+            RuleSet::new()
+                .with_rule(Bfseries)
+                .with_fixable_rule(EnsureLabels(Config::default()))
+                .names(),
+            vec!["bfseries", "ensure-labels"]
+        );
+    }
+}
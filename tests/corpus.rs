@@ -0,0 +1,185 @@
+//! Run all rules and fixers over a corpus of LaTeX files and random mutations of them.
+//!
+//! The rules must never panic and the fixers must be idempotent. Additional corpus
+//! directories can be passed via the `LATEXHOOKS_CORPUS` environment variable, separated
+//! by `:`.
+
+use latexhooks_core::{fix, run_rules, Config, Disambiguation, SlugCase, SlugStyle};
+use regex::Regex;
+use std::path::Path;
+
+/// Fragments inserted into the corpus files to provoke edge cases
+const FRAGMENTS: &[&str] = &[
+    "\\section{",
+    "\\subsection{A \\emph{B} C}",
+    "\\subsubsection*{x}",
+    "\\section{Hello World}\n\\label{sec:old}\n",
+    "\\label{",
+    "\\label{sec:old}",
+    "\\cref{sec:old,sec:other}",
+    "\\section{About \\ref{sec:old}}",
+    "\\section[Short \\ref{sec:old}]{Long}",
+    "\\paperSection{A}",
+    "\\paragraph{P}",
+    "\\begin{verbatim}",
+    "\\end{verbatim}",
+    "\\verb|\\ref{sec:old}|",
+    "\\lstinline{\\section{A}}",
+    "\\mintinline{latex}!\\label{sec:old}!",
+    "\\verb",
+    "|",
+    "\\begin{comment}\n",
+    "% latex-hooks: off\n",
+    "% latex-hooks: on\n",
+    "% latex-hooks: off missing-label\n",
+    "[",
+    "]",
+    "\\ref{",
+    "{",
+    "}",
+    "%",
+    "% skip-label",
+    "\\",
+    "\n",
+    "\n\n",
+    " ",
+    "ü",
+    "→",
+    "\\section{{{{}}}}",
+];
+
+/// Small xorshift generator, such that the test is deterministic
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 as usize
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            0
+        } else {
+            self.next() % n
+        }
+    }
+}
+
+fn read_corpus(dir: &Path, corpus: &mut Vec<String>) {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .expect("The corpus directory must be readable")
+        .map(|entry| entry.expect("The corpus entry must be readable").path())
+        .collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            read_corpus(&path, corpus);
+        } else if path.extension().is_some_and(|ext| ext == "tex") {
+            if let Ok(text) = std::fs::read_to_string(&path) {
+                corpus.push(text);
+            }
+        }
+    }
+}
+
+fn corpus() -> Vec<String> {
+    let mut corpus = Vec::new();
+    read_corpus(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests"),
+        &mut corpus,
+    );
+    if let Ok(dirs) = std::env::var("LATEXHOOKS_CORPUS") {
+        for dir in dirs.split(':').filter(|dir| !dir.is_empty()) {
+            read_corpus(Path::new(dir), &mut corpus);
+        }
+    }
+    corpus
+}
+
+fn mutate(text: &str, rng: &mut Rng) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    for _ in 0..=rng.below(8) {
+        let pos = rng.below(chars.len() + 1);
+        match rng.below(3) {
+            0 => {
+                let fragment = FRAGMENTS[rng.below(FRAGMENTS.len())];
+                chars.splice(pos..pos, fragment.chars());
+            }
+            1 => {
+                let end = (pos + rng.below(20)).min(chars.len());
+                chars.drain(pos..end);
+            }
+            _ => {
+                let end = (pos + rng.below(40)).min(chars.len());
+                let copy: Vec<char> = chars[pos..end].to_vec();
+                let target = rng.below(chars.len() + 1);
+                chars.splice(target..target, copy);
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+fn check(text: &str) {
+    let configs = [
+        Config::default(),
+        Config {
+            ignore_label_content: true,
+            ..Config::default()
+        },
+        Config {
+            prefixes: vec![("subsection".to_string(), "subsec".to_string())],
+            check_paragraphs: true,
+            label_patterns: vec![
+                ("section".to_string(), Regex::new("^sec:[a-z]+$").unwrap()),
+                (
+                    "subsection".to_string(),
+                    Regex::new("^subsec:[a-z0-9_]+$").unwrap(),
+                ),
+            ],
+            slug_style: SlugStyle {
+                separator: "_".to_string(),
+                stop_words: vec!["the".to_string(), "of".to_string()],
+                ..SlugStyle::default()
+            },
+            ..Config::default()
+        },
+        Config {
+            slug_style: SlugStyle {
+                case: SlugCase::Pascal,
+                ..SlugStyle::default()
+            },
+            use_short_title: true,
+            section_commands: vec!["paperSection".to_string()],
+            disambiguation: Disambiguation::Parent,
+            ..Config::default()
+        },
+    ];
+    for config in &configs {
+        run_rules(text, config);
+        let once = fix(text, config);
+        let twice = fix(&once, config);
+        assert_eq!(once, twice, "The fix is not idempotent for:\n{}", text);
+    }
+}
+
+#[test]
+fn corpus_files() {
+    for text in corpus() {
+        check(&text);
+    }
+}
+
+#[test]
+fn mutated_corpus_files() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    let mut corpus = corpus();
+    corpus.push(String::new());
+    for _ in 0..2000 {
+        let text = &corpus[rng.below(corpus.len())];
+        check(&mutate(text, &mut rng));
+    }
+}
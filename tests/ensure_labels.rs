@@ -0,0 +1,111 @@
+//! Run the `ensure-labels` binary on a project with several files.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Write the files into a new directory below the temporary directory
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("ensure-labels-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+    for (path, content) in files {
+        std::fs::write(directory.join(path), content).unwrap();
+    }
+    directory
+}
+
+/// Run `ensure-labels` in `directory` and return its output, if it fails
+fn ensure_labels(directory: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_ensure-labels"))
+        .args(args)
+        .current_dir(directory)
+        .output()
+        .unwrap();
+    if output.status.success() {
+        None
+    } else {
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// With the root, the disambiguated labels do not depend on the passed files or their order
+#[test]
+fn colliding_slugs_in_document_order() {
+    let directory = project(
+        "collisions",
+        &[
+            (
+                "main.tex",
+                "\\documentclass{book}\n\\begin{document}\n\\include{b}\n\\input{a}\n\\end{document}\n",
+            ),
+            (
+                "a.tex",
+                "\\chapter{Results}\n\\subsection{Evaluation}\n\\label{ssec:evaluation-2}\n",
+            ),
+            (
+                "b.tex",
+                "\\chapter{Method}\n\\subsection{Evaluation}\n\\label{ssec:evaluation}\n",
+            ),
+            (
+                "standalone.tex",
+                "\\subsection{Evaluation}\n\\label{ssec:evaluation}\n",
+            ),
+        ],
+    );
+    for args in [
+        &["--root", "main.tex", "a.tex", "b.tex"][..],
+        &["--root", "main.tex", "b.tex", "a.tex"],
+        &["--root", "main.tex", "a.tex"],
+        &["--root", "main.tex", "b.tex"],
+        &["--root", "main.tex", "standalone.tex", "a.tex"],
+        &["b.tex", "standalone.tex"],
+    ] {
+        assert_eq!(ensure_labels(&directory, args), None, "{:?}", args);
+    }
+
+    // Without the root, the files only disambiguate their own sections
+    for args in [
+        &["a.tex", "b.tex"][..],
+        &["a.tex"],
+        &["--root", "standalone.tex", "b.tex", "a.tex"],
+    ] {
+        assert_eq!(
+            ensure_labels(&directory, args).as_deref(),
+            Some("a.tex:2 Wrong Label 'ssec:evaluation-2', use \\label{ssec:evaluation}\n"),
+            "{:?}",
+            args
+        );
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+/// `--only` and `--skip` restrict the reported rules
+#[test]
+fn rule_selection() {
+    let directory = project(
+        "rules",
+        &[("main.tex", "\\section{A}\n\\section{B}\n\\label{sec:x}\n")],
+    );
+    let wrong = "main.tex:2 Wrong Label 'sec:x', use \\label{sec:b}\n";
+    assert_eq!(
+        ensure_labels(&directory, &["--skip", "missing-label", "main.tex"]).as_deref(),
+        Some(wrong)
+    );
+    assert_eq!(
+        ensure_labels(&directory, &["--only", "wrong-label", "main.tex"]).as_deref(),
+        Some(wrong)
+    );
+    assert_eq!(
+        ensure_labels(&directory, &["--only", "unprocessable-section", "main.tex"]),
+        None
+    );
+    assert_eq!(
+        ensure_labels(
+            &directory,
+            &["--skip", "missing-label,wrong-label", "main.tex"]
+        ),
+        None
+    );
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,62 @@
+"""
+Check the output of the chktex hook on the parsed messages of a fake chktex run.
+
+    python3 -m unittest discover tests
+"""
+import contextlib
+import io
+import os
+import tempfile
+import unittest
+from unittest import mock
+
+from latexhooks import chktex
+
+TEXT = """\
+\\documentclass{article}
+\\begin{document}
+See Figure \\ref{fig:a}.
+
+% latex-hooks: off nonbreaking-space
+Table \\ref{tab:a}
+\\end{document}
+"""
+
+
+class ChktexTest(unittest.TestCase):
+    def setUp(self) -> None:
+        directory = tempfile.TemporaryDirectory()
+        self.addCleanup(directory.cleanup)
+        self.path = os.path.join(directory.name, "main.tex")
+        with open(self.path, "w", encoding="utf-8") as f:
+            f.write(TEXT)
+
+    def search(self, *diagnostics: chktex.Diagnostic) -> str:
+        output = io.StringIO()
+        with contextlib.redirect_stdout(output), mock.patch.object(
+            chktex, "run_chktex", return_value=list(diagnostics)
+        ):
+            chktex.search([self.path], [], {"Error", "Warning"})
+        return output.getvalue()
+
+    def test_report(self) -> None:
+        self.assertEqual(
+            self.search(
+                chktex.Diagnostic(self.path, 3, 11, 2, "Warning", "Use ~ instead."),
+                chktex.Diagnostic(self.path, 3, 80, 2, "Message", "Past the end."),
+            ),
+            f"{self.path}:3 warning nonbreaking-space: Use ~ instead.\n"
+            f"{self.path}:3 info nonbreaking-space: Past the end.\n",
+        )
+
+    def test_disabled(self) -> None:
+        self.assertEqual(
+            self.search(
+                chktex.Diagnostic(self.path, 6, 6, 2, "Warning", "Use ~ instead.")
+            ),
+            "",
+        )
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,166 @@
+"""
+Test the `latex-hooks run` command on a small project with its own configuration.
+
+    python3 -m unittest discover tests
+"""
+import argparse
+import contextlib
+import io
+import os
+import tempfile
+import unittest
+
+import yaml
+
+from latexhooks import cli
+
+CONFIG = """\
+repos:
+  - repo: https://github.com/jonasbb/pre-commit-latex-hooks
+    rev: v1.4.0
+    hooks:
+      - id: cleveref-instead-of-autoref
+      - id: spacing  # Fix the files in place
+        args:
+          - "--fix"
+        exclude: ^generated/
+      - id: commented-floats
+        args: []
+  - repo: https://github.com/pre-commit/pre-commit-hooks
+    rev: v4.4.0
+    hooks:
+      - id: trailing-whitespace
+"""
+
+TEXT = """\
+\\documentclass{article}
+\\begin{document}
+Text  with spaces.
+% \\begin{figure}
+\\end{figure}
+\\end{document}
+"""
+
+
+class RunTest(unittest.TestCase):
+    def setUp(self) -> None:
+        directory = tempfile.TemporaryDirectory()
+        self.addCleanup(directory.cleanup)
+        cwd = os.getcwd()
+        os.chdir(directory.name)
+        self.addCleanup(os.chdir, cwd)
+        with open(".pre-commit-config.yaml", "w", encoding="utf-8") as f:
+            f.write(CONFIG)
+        os.mkdir("generated")
+        for path in ["main.tex", os.path.join("generated", "table.tex")]:
+            with open(path, "w", encoding="utf-8") as f:
+                f.write(TEXT)
+
+    def run_cli(
+        self, *only: str, rule_set: int = max(cli.RULE_SETS), format: str = "text"
+    ) -> str:
+        args = argparse.Namespace(
+            config=".pre-commit-config.yaml",
+            format=format,
+            files=[],
+            only=list(only),
+            skip=[],
+            rule_set=rule_set,
+        )
+        output = io.StringIO()
+        with contextlib.redirect_stdout(output), contextlib.suppress(SystemExit):
+            cli.run(args)
+        return output.getvalue()
+
+    def test_configured_hooks(self) -> None:
+        hooks = cli.configured_hooks(".pre-commit-config.yaml")
+        self.assertEqual(
+            [(hook["id"], hook["args"]) for hook in hooks],
+            [
+                ("cleveref-instead-of-autoref", []),
+                ("spacing", ["--fix"]),
+                ("commented-floats", []),
+            ],
+        )
+        self.assertEqual(
+            cli.hook_files(hooks[1], ["main.tex", "generated/table.tex"]),
+            ["main.tex"],
+        )
+
+    def test_run(self) -> None:
+        output = self.run_cli()
+        self.assertIn("spacing: Failed\n  main.tex:3 Double space", output)
+        self.assertNotIn("generated", output.split("commented-floats")[0])
+        self.assertIn("generated/table.tex:5 \\end{figure} is active", output)
+        self.assertIn("use pre-commit for: cleveref-instead-of-autoref", output)
+        with open("main.tex", encoding="utf-8") as f:
+            self.assertIn("Text with spaces.", f.read())
+        with open(os.path.join("generated", "table.tex"), encoding="utf-8") as f:
+            self.assertIn("Text  with spaces.", f.read())
+
+    def test_junit(self) -> None:
+        output = self.run_cli(format="junit")
+        self.assertIn(
+            '<testsuite name="latex-hooks.commented-floats" tests="2" failures="2">',
+            output,
+        )
+        self.assertIn(
+            '<testcase classname="latex-hooks.spacing" name="main.tex">\n'
+            '      <failure message="spacing failed">main.tex:3 Double space\n'
+            "</failure>",
+            output,
+        )
+        self.assertNotIn("generated", output.split("commented-floats")[0])
+
+    def test_only(self) -> None:
+        output = self.run_cli("commented-floats")
+        self.assertNotIn("spacing", output)
+        self.assertIn("1 of 1 checks failed", output)
+
+    def test_rule_set(self) -> None:
+        output = self.run_cli(rule_set=1)
+        self.assertIn(
+            "Not run, changed after rule set 1: spacing, commented-floats", output
+        )
+        self.assertIn("0 of 0 checks failed", output)
+
+    def test_hook_commands(self) -> None:
+        args = cli.build_parser().parse_args(
+            ["fmt-check", "--local-settings=indent.yaml", "main.tex"]
+        )
+        self.assertEqual(args.local_settings, "indent.yaml")
+        self.assertEqual(args.files, ["main.tex"])
+        args = cli.build_parser().parse_args(
+            ["spellcheck", "--dictionary=en_GB", "main.tex"]
+        )
+        self.assertEqual(args.dictionary, ["en_GB"])
+        args.files[0].close()
+        args = cli.build_parser().parse_args(
+            ["wordcount", "--budget=total=2", "main.tex"]
+        )
+        output = io.StringIO()
+        with contextlib.redirect_stdout(output), self.assertRaises(SystemExit):
+            args.func(args)
+        self.assertIn("total has 3 words, but the budget is 2", output.getvalue())
+        args = cli.build_parser().parse_args(
+            ["fmt-check", "--skip=latexindent-check", "main.tex"]
+        )
+        output = io.StringIO()
+        with contextlib.redirect_stdout(output):
+            args.func(args)
+        self.assertEqual(
+            output.getvalue(), "Not run, latexindent-check is not selected\n"
+        )
+
+    def test_all_hooks_in_rule_set(self) -> None:
+        hooks_file = os.path.join(
+            os.path.dirname(os.path.dirname(os.path.abspath(__file__))),
+            ".pre-commit-hooks.yaml",
+        )
+        with open(hooks_file, encoding="utf-8") as f:
+            hooks = [hook["id"] for hook in yaml.safe_load(f)]
+        self.assertEqual(sorted(hooks), cli.known_rules())
+
+
+if __name__ == "__main__":
+    unittest.main()
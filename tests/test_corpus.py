@@ -0,0 +1,182 @@
+"""
+Run the fixers of the Python hooks over a corpus of LaTeX files and random mutations.
+
+This is the counterpart of `corpus.rs`. The hooks must never raise and their fixes must
+be idempotent. Additional corpus directories can be passed via the `LATEXHOOKS_CORPUS`
+environment variable, separated by `:`.
+
+    python3 -m unittest discover tests
+"""
+import contextlib
+import io
+import os
+import tempfile
+import typing as t
+import unittest
+
+from latexhooks import (
+    check_encoding,
+    float_centering,
+    label_after_caption,
+    nonbreaking_spaces,
+    spacing,
+)
+
+# Fragments inserted into the corpus files to provoke edge cases
+FRAGMENTS = [
+    "\\begin{figure}",
+    "\\end{figure}",
+    "\\begin{figure}[t]\n\\label{fig:a}\n\\caption{A}\n\\end{figure}\n",
+    "\\begin{table}\n\\begin{center}\nx\n\\end{center}\n\\end{table}\n",
+    "\\centering\n",
+    "\\caption{",
+    "\\label{fig:x}",
+    "Figure \\ref{fig:x}",
+    "Section~\\ref{sec:x}",
+    "the~end",
+    "5 ms",
+    "3 B-trees",
+    "  ",
+    " .",
+    " ,",
+    "\\begin{verbatim}",
+    "\\end{verbatim}",
+    "\\verb|a  b .|",
+    "\\lstinline{Figure \\ref{x}}",
+    "\\mintinline{c}!5 ms!",
+    "\\verb",
+    "|",
+    "\\begin{align}",
+    "\\end{align}",
+    "$",
+    "[",
+    "]",
+    "{",
+    "}",
+    "%",
+    "\\",
+    "\n",
+    "\n\n",
+    " ",
+    "ü",
+    "→",
+]
+
+# The hooks with a `--fix`, called with the path of a file and whether to fix it
+FIXERS: t.List[t.Tuple[str, t.Callable[[str, bool], object]]] = [
+    (
+        "check-encoding",
+        lambda path, fix: check_encoding.check_file(
+            path, "forbid", fix, check_encoding.LEGACY_ENCODINGS
+        ),
+    ),
+    ("float-centering", lambda path, fix: float_centering.search([path], True, fix)),
+    (
+        "float-centering-uncentered",
+        lambda path, fix: float_centering.search([path], False, fix),
+    ),
+    ("label-after-caption", lambda path, fix: label_after_caption.search([path], fix)),
+    (
+        "nonbreaking-spaces",
+        lambda path, fix: nonbreaking_spaces.search(
+            [path],
+            nonbreaking_spaces.CHECKS,
+            nonbreaking_spaces.REFERENCE_NAMES,
+            fix,
+        ),
+    ),
+    ("spacing", lambda path, fix: spacing.search([path], fix)),
+]
+
+
+class Rng:
+    """Small xorshift generator, such that the test is deterministic"""
+
+    def __init__(self, seed: int) -> None:
+        self.state = seed
+
+    def next(self) -> int:
+        mask = (1 << 64) - 1
+        self.state ^= (self.state << 13) & mask
+        self.state ^= self.state >> 7
+        self.state ^= (self.state << 17) & mask
+        return self.state
+
+    def below(self, n: int) -> int:
+        return 0 if n == 0 else self.next() % n
+
+
+def read_corpus(directory: str, corpus: t.List[str]) -> None:
+    for name in sorted(os.listdir(directory)):
+        path = os.path.join(directory, name)
+        if os.path.isdir(path):
+            read_corpus(path, corpus)
+        elif name.endswith(".tex"):
+            try:
+                with open(path, encoding="utf-8") as f:
+                    corpus.append(f.read())
+            except UnicodeDecodeError:
+                continue
+
+
+def corpus() -> t.List[str]:
+    texts: t.List[str] = []
+    read_corpus(os.path.dirname(os.path.abspath(__file__)), texts)
+    for directory in os.environ.get("LATEXHOOKS_CORPUS", "").split(":"):
+        if directory:
+            read_corpus(directory, texts)
+    return texts
+
+
+def mutate(text: str, rng: Rng) -> str:
+    chars = list(text)
+    for _ in range(rng.below(8) + 1):
+        pos = rng.below(len(chars) + 1)
+        kind = rng.below(3)
+        if kind == 0:
+            chars[pos:pos] = FRAGMENTS[rng.below(len(FRAGMENTS))]
+        elif kind == 1:
+            del chars[pos : pos + rng.below(20)]
+        else:
+            copy = chars[pos : pos + rng.below(40)]
+            target = rng.below(len(chars) + 1)
+            chars[target:target] = copy
+    return "".join(chars)
+
+
+class CorpusTest(unittest.TestCase):
+    def setUp(self) -> None:
+        directory = tempfile.TemporaryDirectory()
+        self.addCleanup(directory.cleanup)
+        self.path = os.path.join(directory.name, "main.tex")
+
+    def fix(self, run: t.Callable[[str, bool], object], text: str) -> str:
+        with open(self.path, "w", encoding="utf-8", newline="") as f:
+            f.write(text)
+        with contextlib.redirect_stdout(io.StringIO()):
+            run(self.path, True)
+        with open(self.path, encoding="utf-8", newline="") as f:
+            return f.read()
+
+    def check(self, text: str) -> None:
+        for name, run in FIXERS:
+            once = self.fix(run, text)
+            twice = self.fix(run, once)
+            self.assertEqual(
+                once, twice, f"The fix of {name} is not idempotent for:\n{text}"
+            )
+
+    def test_corpus_files(self) -> None:
+        for text in corpus():
+            self.check(text)
+
+    def test_mutated_corpus_files(self) -> None:
+        rng = Rng(0x2545_F491_4F6C_DD1D)
+        texts = corpus() + [""]
+        for _ in range(2000):
+            text = texts[rng.below(len(texts))]
+            self.check(mutate(text, rng))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,162 @@
+"""
+Check the fixes of the Python hooks on examples, comparing the text before and after.
+
+    python3 -m unittest discover tests
+"""
+import contextlib
+import io
+import os
+import tempfile
+import typing as t
+import unittest
+
+from latexhooks import (
+    check_encoding,
+    float_centering,
+    label_after_caption,
+    nonbreaking_spaces,
+    spacing,
+)
+
+
+class FixerTest(unittest.TestCase):
+    def setUp(self) -> None:
+        directory = tempfile.TemporaryDirectory()
+        self.addCleanup(directory.cleanup)
+        self.path = os.path.join(directory.name, "main.tex")
+
+    def assertFixes(
+        self, search: t.Callable[[str], object], cases: t.List[t.Tuple[str, str]]
+    ) -> None:
+        for before, after in cases:
+            with open(self.path, "w", encoding="utf-8") as f:
+                f.write(before)
+            with contextlib.redirect_stdout(io.StringIO()):
+                search(self.path)
+            with open(self.path, encoding="utf-8") as f:
+                self.assertEqual(f.read(), after, before)
+
+    def test_spacing(self) -> None:
+        self.assertFixes(
+            lambda path: spacing.search([path], True),
+            [
+                ("Double  spaces and a space .\n", "Double spaces and a space.\n"),
+                ("A footnote \\footnote{x}\n", "A footnote\\footnote{x}\n"),
+                ("a  $x  y$  b\n", "a $x  y$ b\n"),
+                (
+                    "\\begin{tabular}{ll}\n    a  & b .\n\\end{tabular}\n",
+                    "\\begin{tabular}{ll}\n    a  & b .\n\\end{tabular}\n",
+                ),
+                (
+                    "\\begin{verbatim}\na  b .\n\\end{verbatim}\n",
+                    "\\begin{verbatim}\na  b .\n\\end{verbatim}\n",
+                ),
+                (
+                    "\\verb|x  .| \\verb*+  +,  \\lstinline{a  ,} \\mintinline{c}{b  }\n",
+                    "\\verb|x  .| \\verb*+  +, \\lstinline{a  ,} \\mintinline{c}{b  }\n",
+                ),
+            ],
+        )
+
+    def test_float_centering(self) -> None:
+        self.assertFixes(
+            lambda path: float_centering.search([path], True, True),
+            [
+                (
+                    "\\begin{figure}[t]\n    \\input{a}\n\\end{figure}",
+                    "\\begin{figure}[t]\n    \\centering\n    \\input{a}\n\\end{figure}",
+                ),
+                (
+                    "\\begin{table} \\caption{A}\n\\end{table}\n",
+                    "\\begin{table}\n    \\centering\n    \\caption{A}\n\\end{table}\n",
+                ),
+                (
+                    "\\begin{verbatim}\n\\begin{figure}\\end{figure}\n\\end{verbatim}",
+                    "\\begin{verbatim}\n\\begin{figure}\\end{figure}\n\\end{verbatim}",
+                ),
+                (
+                    "\\verb|\\begin{figure}x\\end{figure}|\n",
+                    "\\verb|\\begin{figure}x\\end{figure}|\n",
+                ),
+            ],
+        )
+        self.assertFixes(
+            lambda path: float_centering.search([path], False, True),
+            [
+                (
+                    "\\begin{figure}\n    \\centering\n    \\input{a}\n\\end{figure}",
+                    "\\begin{figure}\n    \\input{a}\n\\end{figure}",
+                ),
+                (
+                    "\\begin{figure}\n\\lstinline{\\centering}\n\\end{figure}\n",
+                    "\\begin{figure}\n\\lstinline{\\centering}\n\\end{figure}\n",
+                ),
+            ],
+        )
+
+    def test_label_after_caption(self) -> None:
+        self.assertFixes(
+            lambda path: label_after_caption.search([path], True),
+            [
+                (
+                    "\\begin{figure}\n    \\label{fig:a}\n    \\caption{A}\n\\end{figure}",
+                    "\\begin{figure}\n    \\caption{A}\\label{fig:a}\n\\end{figure}",
+                ),
+                (
+                    "\\begin{table}\n\\label{tab:a}\\caption{A}\n\\end{table}\n",
+                    "\\begin{table}\n\\caption{A}\\label{tab:a}\n\\end{table}\n",
+                ),
+                (
+                    "\\begin{comment}\n\\begin{table}\\label{a}\\caption{A}\\end{table}",
+                    "\\begin{comment}\n\\begin{table}\\label{a}\\caption{A}\\end{table}",
+                ),
+                (
+                    "\\begin{figure}\n\\verb|\\label{a}|\n\\caption{A}\n\\end{figure}",
+                    "\\begin{figure}\n\\verb|\\label{a}|\n\\caption{A}\n\\end{figure}",
+                ),
+            ],
+        )
+
+    def test_nonbreaking_spaces(self) -> None:
+        self.assertFixes(
+            lambda path: nonbreaking_spaces.search(
+                [path],
+                nonbreaking_spaces.CHECKS,
+                nonbreaking_spaces.REFERENCE_NAMES,
+                True,
+            ),
+            [
+                ("Figure \\ref{a}, Sec.\n\\ref{b}", "Figure~\\ref{a}, Sec.~\\ref{b}"),
+                ("5 ms, the~other~one 3 B-trees", "5~ms, the other one 3 B-trees"),
+                (
+                    "\\verb|Figure \\ref{a}| \\lstinline!5 ms! \\mintinline{c}{a~b}",
+                    "\\verb|Figure \\ref{a}| \\lstinline!5 ms! \\mintinline{c}{a~b}",
+                ),
+                (
+                    "\\begin{verbatim}\nFigure \\ref{a}, 5 ms\n\\end{verbatim}",
+                    "\\begin{verbatim}\nFigure \\ref{a}, 5 ms\n\\end{verbatim}",
+                ),
+            ],
+        )
+
+    def test_check_encoding(self) -> None:
+        bom = "\ufeff".encode("utf-8")
+        for bom_option, before, after in [
+            ("forbid", "Caf\xe9\n".encode("cp1252"), "Caf\xe9\n".encode("utf-8")),
+            ("forbid", bom + b"Text\n", b"Text\n"),
+            ("require", b"Text\n", bom + b"Text\n"),
+            ("forbid", b"\\verb|\xe9|\n", "\\verb|\xe9|\n".encode("utf-8")),
+            ("forbid", "\xe9\n".encode("utf-8"), "\xe9\n".encode("utf-8")),
+        ]:
+            with open(self.path, "wb") as f:
+                f.write(before)
+            with contextlib.redirect_stdout(io.StringIO()):
+                check_encoding.check_file(
+                    self.path, bom_option, True, check_encoding.LEGACY_ENCODINGS
+                )
+            with open(self.path, "rb") as f:
+                self.assertEqual(f.read(), after, before)
+
+
+if __name__ == "__main__":
+    unittest.main()
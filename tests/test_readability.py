@@ -0,0 +1,55 @@
+"""
+Check the metrics of the readability hook on small sections.
+
+    python3 -m unittest discover tests
+"""
+import contextlib
+import io
+import os
+import tempfile
+import unittest
+
+from latexhooks import readability
+
+TEXT = """\
+\\section{Introduction to the Evaluation of Readability Metrics for Scientific Writing}
+Short text. Another one.
+\\subsection[Short]{A Long Subsection Heading Which Has Many Words In It Too}
+More text here.
+% \\section{Commented}
+"""
+
+
+class ReadabilityTest(unittest.TestCase):
+    def setUp(self) -> None:
+        directory = tempfile.TemporaryDirectory()
+        self.addCleanup(directory.cleanup)
+        self.path = os.path.join(directory.name, "main.tex")
+        with open(self.path, "w", encoding="utf-8") as f:
+            f.write(TEXT)
+
+    def search(self, max_sentence_length: float, show_metrics: bool) -> str:
+        output = io.StringIO()
+        with open(self.path, encoding="utf-8") as f, contextlib.redirect_stdout(output):
+            readability.search([f], max_sentence_length, 16, show_metrics)
+        return output.getvalue()
+
+    def test_headings(self) -> None:
+        self.assertEqual(self.search(3, False), "")
+        self.assertEqual(
+            self.search(3, True),
+            "Introduction to the Evaluation of Readability Metrics for Scientific "
+            "Writing: 2.3 words per sentence, Flesch-Kincaid grade 0.5\n",
+        )
+
+    def test_failure(self) -> None:
+        self.assertEqual(
+            self.search(2, False),
+            f"{self.path}:1 Sentences in 'Introduction to the Evaluation of "
+            "Readability Metrics for Scientific Writing' have 2.3 words on average, "
+            "the maximum is 2\n",
+        )
+
+
+if __name__ == "__main__":
+    unittest.main()